@@ -0,0 +1,302 @@
+//! Builders that encode the columns one row at a time.
+//!
+//! The `{Row}ColumnsBuilder` generated by `#[columnar(vec, builder)]` pushes each field of
+//! the row into the [ColumnBuilder] of its strategy, so the rows do not need to be collected
+//! into a container before encoding. The output is the same as the bytes of [ColumnarVec](crate::ColumnarVec).
+use std::{borrow::Borrow, ops::DerefMut};
+
+use serde::Serialize;
+
+use crate::{
+    column::delta_of_delta::DeltaOfDeltable,
+    columnar_internal::ColumnarEncoder,
+    strategy::{
+        AnyRleEncoder, AutoEncoder, BitPackEncoder, BoolRleEncoder, DeltaBitPackEncoder,
+        DeltaOfDeltaEncoder, DeltaRleEncoder, DictEncoder, FloatXorEncoder,
+    },
+    Autoable, BitPackable, ColumnarError, DeltaRleable, Dictable, FloatXorable, Rleable,
+};
+
+/// Encodes a column one value at a time, into the same bytes as [ColumnTrait::encode](crate::ColumnTrait::encode)
+pub trait ColumnBuilder: Default {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError>;
+}
+
+/// The value `T` can be pushed into the [ColumnBuilder]
+pub trait PushColumn<T>: ColumnBuilder {
+    fn push(&mut self, value: T) -> Result<(), ColumnarError>;
+}
+
+/// The row struct that has a generated `{Row}ColumnsBuilder`, which is used by the
+/// `flatten` fields.
+pub trait BuildColumns {
+    type ColumnsBuilder: ColumnBuilder;
+}
+
+impl<T: Rleable> ColumnBuilder for AnyRleEncoder<T> {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        AnyRleEncoder::finish(self)
+    }
+}
+
+impl<T: Rleable, V: Borrow<T>> PushColumn<V> for AnyRleEncoder<T> {
+    fn push(&mut self, value: V) -> Result<(), ColumnarError> {
+        self.append(value)
+    }
+}
+
+impl ColumnBuilder for BoolRleEncoder {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        BoolRleEncoder::finish(self)
+    }
+}
+
+impl PushColumn<bool> for BoolRleEncoder {
+    fn push(&mut self, value: bool) -> Result<(), ColumnarError> {
+        self.append(value)
+    }
+}
+
+impl ColumnBuilder for DeltaRleEncoder {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        DeltaRleEncoder::finish(self)
+    }
+}
+
+impl<T: DeltaRleable> PushColumn<T> for DeltaRleEncoder {
+    fn push(&mut self, value: T) -> Result<(), ColumnarError> {
+        self.append(value)
+    }
+}
+
+impl ColumnBuilder for DeltaOfDeltaEncoder {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        DeltaOfDeltaEncoder::finish(self)
+    }
+}
+
+impl<T: DeltaOfDeltable> PushColumn<T> for DeltaOfDeltaEncoder {
+    fn push(&mut self, value: T) -> Result<(), ColumnarError> {
+        self.append(value.try_into().map_err(|_| {
+            ColumnarError::RleDecodeError(format!(
+                "{:?} cannot be safely converted from i64",
+                value
+            ))
+        })?)
+    }
+}
+
+impl<T: Dictable> ColumnBuilder for DictEncoder<T> {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        DictEncoder::finish(self)
+    }
+}
+
+impl<T: Dictable, V: Borrow<T>> PushColumn<V> for DictEncoder<T> {
+    fn push(&mut self, value: V) -> Result<(), ColumnarError> {
+        self.append(value)
+    }
+}
+
+impl<T: Autoable> ColumnBuilder for AutoEncoder<T> {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        AutoEncoder::finish(self)
+    }
+}
+
+impl<T: Autoable> PushColumn<T> for AutoEncoder<T> {
+    fn push(&mut self, value: T) -> Result<(), ColumnarError> {
+        self.append(value)
+    }
+}
+
+impl<T: FloatXorable> ColumnBuilder for FloatXorEncoder<T> {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        FloatXorEncoder::finish(self)
+    }
+}
+
+impl<T: FloatXorable> PushColumn<T> for FloatXorEncoder<T> {
+    fn push(&mut self, value: T) -> Result<(), ColumnarError> {
+        self.append(value)
+    }
+}
+
+impl<T: BitPackable> ColumnBuilder for BitPackEncoder<T> {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        BitPackEncoder::finish(self)
+    }
+}
+
+impl<T: BitPackable> PushColumn<T> for BitPackEncoder<T> {
+    fn push(&mut self, value: T) -> Result<(), ColumnarError> {
+        self.append(value)
+    }
+}
+
+impl ColumnBuilder for DeltaBitPackEncoder {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        DeltaBitPackEncoder::finish(self)
+    }
+}
+
+impl<T: DeltaRleable> PushColumn<T> for DeltaBitPackEncoder {
+    fn push(&mut self, value: T) -> Result<(), ColumnarError> {
+        self.append(value)
+    }
+}
+
+/// The builder of [GenericColumn](crate::GenericColumn), the values are serialized as soon as
+/// they are pushed.
+#[derive(Default)]
+pub struct GenericBuilder {
+    ser: ColumnarEncoder,
+    len: usize,
+}
+
+impl ColumnBuilder for GenericBuilder {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        // the length prefix of the serialized `Vec<T>`
+        let mut bytes = postcard::to_allocvec(&self.len)?;
+        bytes.extend(self.ser.into_bytes());
+        Ok(bytes)
+    }
+}
+
+impl<T: Serialize> PushColumn<T> for GenericBuilder {
+    fn push(&mut self, value: T) -> Result<(), ColumnarError> {
+        value.serialize(self.ser.deref_mut())?;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+/// The builder of [NullableColumn](crate::NullableColumn), `B` is the builder of the present values
+#[derive(Default)]
+pub struct NullableBuilder<B> {
+    presence: BoolRleEncoder,
+    values: B,
+}
+
+impl<B: ColumnBuilder> ColumnBuilder for NullableBuilder<B> {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        let mut encoder = ColumnarEncoder::new();
+        self.presence.finish()?.serialize(encoder.deref_mut())?;
+        self.values.finish()?.serialize(encoder.deref_mut())?;
+        Ok(encoder.into_bytes())
+    }
+}
+
+impl<T, B: PushColumn<T>> PushColumn<Option<T>> for NullableBuilder<B> {
+    fn push(&mut self, value: Option<T>) -> Result<(), ColumnarError> {
+        self.presence.append(value.is_some())?;
+        match value {
+            Some(value) => self.values.push(value),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The builder of [ListColumn](crate::ListColumn), `B` is the builder of the elements
+#[derive(Default)]
+pub struct ListBuilder<B> {
+    lengths: AnyRleEncoder<usize>,
+    values: B,
+}
+
+impl<B: ColumnBuilder> ColumnBuilder for ListBuilder<B> {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        let mut encoder = ColumnarEncoder::new();
+        self.lengths.finish()?.serialize(encoder.deref_mut())?;
+        self.values.finish()?.serialize(encoder.deref_mut())?;
+        Ok(encoder.into_bytes())
+    }
+}
+
+impl<I, B> PushColumn<I> for ListBuilder<B>
+where
+    I: IntoIterator,
+    B: PushColumn<I::Item>,
+{
+    fn push(&mut self, value: I) -> Result<(), ColumnarError> {
+        let mut len = 0;
+        for v in value {
+            self.values.push(v)?;
+            len += 1;
+        }
+        self.lengths.append(len)
+    }
+}
+
+/// The builder of [NestedColumn](crate::NestedColumn), `B` is the `{Row}ColumnsBuilder` of
+/// the inner rows.
+#[derive(Default)]
+pub struct NestedBuilder<B> {
+    lengths: AnyRleEncoder<usize>,
+    rows: B,
+}
+
+impl<B: ColumnBuilder> ColumnBuilder for NestedBuilder<B> {
+    fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        let mut encoder = ColumnarEncoder::new();
+        self.lengths.finish()?.serialize(encoder.deref_mut())?;
+        let mut bytes = encoder.into_bytes();
+        // the columns of the inner rows are nested without the length prefix
+        bytes.extend(self.rows.finish()?);
+        Ok(bytes)
+    }
+}
+
+impl<'a, T: 'a, B: PushColumn<&'a T>> PushColumn<&'a Vec<T>> for NestedBuilder<B> {
+    fn push(&mut self, value: &'a Vec<T>) -> Result<(), ColumnarError> {
+        for row in value {
+            self.rows.push(row)?;
+        }
+        self.lengths.append(value.len())
+    }
+}
+
+/// Writes the columns built by the builders into the same bytes as
+/// [RowSer::serialize_columns](crate::RowSer::serialize_columns)
+pub struct ColumnsWriter {
+    bytes: Vec<u8>,
+}
+
+impl ColumnsWriter {
+    pub fn new(columns_len: usize) -> Result<Self, ColumnarError> {
+        Ok(Self {
+            bytes: postcard::to_allocvec(&columns_len)?,
+        })
+    }
+
+    /// Writes the bytes of a column built by [ColumnBuilder]
+    pub fn column(&mut self, column: &[u8]) -> Result<(), ColumnarError> {
+        self.bytes.extend(postcard::to_allocvec(column)?);
+        Ok(())
+    }
+
+    /// Writes the bytes of the nested columns of a `flatten` field
+    pub fn flatten_column(&mut self, columns: &[u8]) {
+        self.bytes.extend_from_slice(columns);
+    }
+
+    /// Writes the bytes of an `optional` column built by [ColumnBuilder]
+    pub fn optional_column(&mut self, index: usize, column: &[u8]) -> Result<(), ColumnarError> {
+        let column = postcard::to_allocvec(column)?;
+        self.optional_flatten_column(index, column)
+    }
+
+    /// Writes the bytes of the nested columns of an `optional` `flatten` field
+    pub fn optional_flatten_column(
+        &mut self,
+        index: usize,
+        columns: Vec<u8>,
+    ) -> Result<(), ColumnarError> {
+        self.bytes.extend(postcard::to_allocvec(&(index, columns))?);
+        Ok(())
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    strategy::{AutoDecoder, AutoEncoder},
+    ColumnAttr, ColumnarError,
+};
+
+use super::{delta_of_delta::DeltaOfDeltable, delta_rle::DeltaRleable, ColumnTrait};
+
+pub trait Autoable: DeltaRleable + DeltaOfDeltable {}
+impl<T> Autoable for T where T: DeltaRleable + DeltaOfDeltable {}
+
+/// The Column that is scheduled to be compressed using [AutoEncoder]
+#[derive(Debug)]
+pub struct AutoColumn<T> {
+    pub data: Vec<T>,
+    pub attr: ColumnAttr,
+}
+
+impl<T> AutoColumn<T> {
+    pub fn new(data: Vec<T>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+}
+
+impl<T> ColumnTrait for AutoColumn<T>
+where
+    T: Autoable,
+{
+    fn attr(&self) -> ColumnAttr {
+        self.attr
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut auto_encoder = AutoEncoder::new();
+        for &data in self.data.iter() {
+            auto_encoder.append(data)?
+        }
+        auto_encoder.finish()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ColumnarError>
+    where
+        Self: Sized,
+    {
+        let mut auto_decoder = AutoDecoder::new(bytes)?;
+        Ok(Self {
+            data: auto_decoder.decode()?,
+            attr: ColumnAttr::empty(),
+        })
+    }
+}
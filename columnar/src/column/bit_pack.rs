@@ -0,0 +1,50 @@
+use crate::{
+    strategy::{BitPackDecoder, BitPackEncoder},
+    ColumnAttr, ColumnarError,
+};
+
+use super::ColumnTrait;
+
+pub trait BitPackable: Copy + TryFrom<i128> + TryInto<i128> + std::fmt::Debug {}
+impl<T> BitPackable for T where T: Copy + TryFrom<i128> + TryInto<i128> + std::fmt::Debug {}
+
+/// The Column that is scheduled to be compressed using [BitPackEncoder]
+#[derive(Debug)]
+pub struct BitPackColumn<T> {
+    pub data: Vec<T>,
+    pub attr: ColumnAttr,
+}
+
+impl<T> BitPackColumn<T> {
+    pub fn new(data: Vec<T>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+}
+
+impl<T: BitPackable> ColumnTrait for BitPackColumn<T> {
+    fn attr(&self) -> ColumnAttr {
+        self.attr
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut bit_pack_encoder = BitPackEncoder::new();
+        for &data in self.data.iter() {
+            bit_pack_encoder.append(data)?
+        }
+        bit_pack_encoder.finish()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ColumnarError>
+    where
+        Self: Sized,
+    {
+        let mut bit_pack_decoder = BitPackDecoder::new(bytes)?;
+        Ok(Self {
+            data: bit_pack_decoder.decode()?,
+            attr: ColumnAttr::empty(),
+        })
+    }
+}
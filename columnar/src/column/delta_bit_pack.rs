@@ -0,0 +1,50 @@
+use crate::{
+    strategy::{DeltaBitPackDecoder, DeltaBitPackEncoder},
+    ColumnAttr, ColumnarError,
+};
+
+use super::{delta_rle::DeltaRleable, ColumnTrait};
+
+/// The Column that is scheduled to be compressed using [DeltaBitPackEncoder]
+#[derive(Debug)]
+pub struct DeltaBitPackColumn<T> {
+    pub data: Vec<T>,
+    pub attr: ColumnAttr,
+}
+
+impl<T> DeltaBitPackColumn<T> {
+    pub fn new(data: Vec<T>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+}
+
+impl<T> ColumnTrait for DeltaBitPackColumn<T>
+where
+    T: DeltaRleable,
+{
+    fn attr(&self) -> ColumnAttr {
+        self.attr
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut delta_bit_pack = DeltaBitPackEncoder::new();
+        for &data in self.data.iter() {
+            delta_bit_pack.append(data)?
+        }
+        delta_bit_pack.finish()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ColumnarError>
+    where
+        Self: Sized,
+    {
+        let mut delta_bit_pack_decoder = DeltaBitPackDecoder::new(bytes)?;
+        Ok(Self {
+            data: delta_bit_pack_decoder.decode()?,
+            attr: ColumnAttr::empty(),
+        })
+    }
+}
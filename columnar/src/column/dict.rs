@@ -0,0 +1,56 @@
+use std::hash::Hash;
+
+use crate::{
+    strategy::{DictDecoder, DictEncoder},
+    ColumnAttr, ColumnarError,
+};
+
+use super::{rle::Rleable, ColumnTrait};
+
+pub trait Dictable: Rleable + Eq + Hash {}
+impl<T> Dictable for T where T: Rleable + Eq + Hash {}
+
+/// The Column that is scheduled to be compressed using [DictEncoder]
+#[derive(Debug)]
+pub struct DictColumn<T> {
+    pub data: Vec<T>,
+    pub attr: ColumnAttr,
+}
+
+impl<T: Dictable> DictColumn<T> {
+    pub fn new(data: Vec<T>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+}
+
+impl<T> ColumnTrait for DictColumn<T>
+where
+    T: Dictable,
+{
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn attr(&self) -> ColumnAttr {
+        self.attr
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut dict_encoder = DictEncoder::<T>::new();
+        for data in self.data.iter() {
+            dict_encoder.append(data)?
+        }
+        dict_encoder.finish()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ColumnarError>
+    where
+        Self: Sized,
+    {
+        let mut dict_decoder = DictDecoder::new(bytes)?;
+        Ok(Self {
+            data: dict_decoder.decode()?,
+            attr: ColumnAttr::empty(),
+        })
+    }
+}
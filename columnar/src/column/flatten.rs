@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ColumnAttr, RowDe, RowSer};
+
+/// The Column of a `flatten` field, whose type is a row struct itself.
+///
+/// The columns of the inner rows are serialized by [RowSer] of the inner struct as a
+/// nested column group, so that each inner field keeps its own strategy. When
+/// serializing, it holds the references of the inner rows.
+#[derive(Debug)]
+pub struct FlattenColumn<T> {
+    pub data: Vec<T>,
+    pub attr: ColumnAttr,
+}
+
+impl<T> FlattenColumn<T> {
+    pub fn new(data: Vec<T>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl<T> From<Vec<T>> for FlattenColumn<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self::new(data, ColumnAttr::empty())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a FlattenColumn<&T> {
+    type Item = &'a T;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, &'a T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        let data: &'a [&'a T] = &self.data;
+        data.iter().copied()
+    }
+}
+
+impl<'c, T> Serialize for FlattenColumn<&'c T>
+where
+    T: RowSer<FlattenColumn<&'c T>>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        T::serialize_columns(self, serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for FlattenColumn<T>
+where
+    T: RowDe<'de, Vec<T>>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(T::deserialize_columns(deserializer)?.into())
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    strategy::{FloatXorDecoder, FloatXorEncoder},
+    ColumnAttr, ColumnarError,
+};
+
+use super::ColumnTrait;
+
+/// Floats that can be encoded by [FloatXorEncoder], through their bit patterns.
+pub trait FloatXorable: Copy + std::fmt::Debug {
+    /// The number of bits of the value
+    const BITS: u8;
+    fn to_xor_bits(self) -> u64;
+    fn from_xor_bits(bits: u64) -> Self;
+}
+
+impl FloatXorable for f64 {
+    const BITS: u8 = 64;
+    fn to_xor_bits(self) -> u64 {
+        self.to_bits()
+    }
+    fn from_xor_bits(bits: u64) -> Self {
+        f64::from_bits(bits)
+    }
+}
+
+impl FloatXorable for f32 {
+    const BITS: u8 = 32;
+    fn to_xor_bits(self) -> u64 {
+        self.to_bits() as u64
+    }
+    fn from_xor_bits(bits: u64) -> Self {
+        f32::from_bits(bits as u32)
+    }
+}
+
+/// The Column that is scheduled to be compressed using [FloatXorEncoder]
+#[derive(Debug)]
+pub struct FloatXorColumn<T> {
+    pub data: Vec<T>,
+    pub attr: ColumnAttr,
+}
+
+impl<T> FloatXorColumn<T> {
+    pub fn new(data: Vec<T>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+}
+
+impl<T: FloatXorable> ColumnTrait for FloatXorColumn<T> {
+    fn attr(&self) -> ColumnAttr {
+        self.attr
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut float_xor_encoder = FloatXorEncoder::new();
+        for &data in self.data.iter() {
+            float_xor_encoder.append(data)?
+        }
+        float_xor_encoder.finish()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ColumnarError>
+    where
+        Self: Sized,
+    {
+        let mut float_xor_decoder = FloatXorDecoder::new(bytes)?;
+        Ok(Self {
+            data: float_xor_decoder.decode()?,
+            attr: ColumnAttr::empty(),
+        })
+    }
+}
@@ -0,0 +1,121 @@
+use std::{borrow::Cow, marker::PhantomData, ops::DerefMut};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    columnar_internal::{ColumnarDecoder, ColumnarEncoder},
+    strategy::{AnyRleDecoder, AnyRleEncoder},
+    ColumnAttr, ColumnarError,
+};
+
+use super::{ColumnData, ColumnTrait};
+
+/// The Column of `Vec<T>`, where `C` is the column of all the elements.
+///
+/// Like the list of Arrow, the length of each list is compressed by [AnyRleEncoder], and
+/// the elements of all the lists are concatenated and encoded by `C`, so that the
+/// strategy of `C` is applied across rows. The encoded bytes are the lengths bytes
+/// followed by the bytes of `C`, both prefixed by their length. When serializing, it can
+/// hold the references of the lists, see [ListData].
+#[derive(Debug)]
+pub struct ListColumn<C: ColumnData, L = Vec<<C as ColumnData>::Item>> {
+    pub data: Vec<L>,
+    pub attr: ColumnAttr,
+    _c: PhantomData<C>,
+}
+
+/// A list of [ListColumn], whose elements are converted into the items of the column
+pub trait ListData<I> {
+    /// Appends the elements of the list to `items`
+    fn extend_items(&self, items: &mut Vec<I>);
+}
+
+impl<T: Clone> ListData<T> for Vec<T> {
+    fn extend_items(&self, items: &mut Vec<T>) {
+        items.extend_from_slice(self)
+    }
+}
+
+impl<T: Clone> ListData<T> for &Vec<T> {
+    fn extend_items(&self, items: &mut Vec<T>) {
+        items.extend_from_slice(self)
+    }
+}
+
+impl<'a, T: Clone> ListData<Cow<'a, T>> for &'a Vec<T> {
+    fn extend_items(&self, items: &mut Vec<Cow<'a, T>>) {
+        items.extend(self.iter().map(Cow::Borrowed))
+    }
+}
+
+impl<C: ColumnData, L> ListColumn<C, L> {
+    pub fn new(data: Vec<L>, attr: ColumnAttr) -> Self {
+        Self {
+            data,
+            attr,
+            _c: PhantomData,
+        }
+    }
+}
+
+impl<C: ColumnData, L: ListData<C::Item>> ListColumn<C, L> {
+    pub(crate) fn encode_lists(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut lengths = AnyRleEncoder::<usize>::new();
+        let mut items = Vec::new();
+        for list in self.data.iter() {
+            let start = items.len();
+            list.extend_items(&mut items);
+            lengths.append(items.len() - start)?
+        }
+        let values = C::from_data(items);
+
+        let mut encoder = ColumnarEncoder::new();
+        lengths.finish()?.serialize(encoder.deref_mut())?;
+        values.encode()?.serialize(encoder.deref_mut())?;
+        Ok(encoder.into_bytes())
+    }
+}
+
+impl<C> ColumnTrait for ListColumn<C>
+where
+    C: ColumnData,
+    C::Item: Clone,
+{
+    fn attr(&self) -> ColumnAttr {
+        self.attr
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        self.encode_lists()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ColumnarError>
+    where
+        Self: Sized,
+    {
+        let mut decoder = ColumnarDecoder::new(bytes);
+        let lengths: &[u8] = Deserialize::deserialize(decoder.deref_mut())?;
+        let values: &[u8] = Deserialize::deserialize(decoder.deref_mut())?;
+        let lengths = AnyRleDecoder::<usize>::new(lengths).decode()?;
+        let mut values = C::decode(values)?.into_data().into_iter();
+        let mut data = Vec::with_capacity(lengths.len());
+        for len in lengths {
+            let list: Vec<_> = values.by_ref().take(len).collect();
+            if list.len() < len {
+                return Err(ColumnarError::ColumnarDecodeError(
+                    "list column has fewer values than the sum of lengths".into(),
+                ));
+            }
+            data.push(list);
+        }
+        if values.next().is_some() {
+            return Err(ColumnarError::ColumnarDecodeError(
+                "list column has more values than the sum of lengths".into(),
+            ));
+        }
+        Ok(Self::new(data, ColumnAttr::empty()))
+    }
+}
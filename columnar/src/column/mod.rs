@@ -1,11 +1,28 @@
+pub mod auto;
+pub mod bit_pack;
 pub mod bool_rle;
+pub mod delta_bit_pack;
 pub mod delta_of_delta;
 pub mod delta_rle;
+pub mod dict;
+pub mod flatten;
+pub mod float_xor;
+pub mod list;
+pub mod nested;
+pub mod nullable;
 pub mod rle;
 pub mod serde_impl;
+pub mod stats;
 
-use crate::{columnar_internal::ColumnarEncoder, ColumnarDecoder, ColumnarError};
-use crate::{BoolRleColumn, DeltaOfDeltaColumn, DeltaRleColumn, DeltaRleable, RleColumn, Rleable};
+use crate::{
+    column::delta_of_delta::DeltaOfDeltable, columnar_internal::ColumnarEncoder, ColumnarDecoder,
+    ColumnarError,
+};
+use crate::{
+    AutoColumn, Autoable, BitPackColumn, BitPackable, BoolRleColumn, DeltaBitPackColumn,
+    DeltaOfDeltaColumn, DeltaRleColumn, DeltaRleable, DictColumn, Dictable, FloatXorColumn,
+    FloatXorable, ListColumn, NullableColumn, RleColumn, Rleable,
+};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::ops::DerefMut;
@@ -22,6 +39,64 @@ pub trait ColumnTrait {
     }
 }
 
+/// The columns that hold their values in a plain `Vec`, so that they can be
+/// wrapped by other columns such as [NullableColumn](crate::NullableColumn).
+pub trait ColumnData: ColumnTrait {
+    type Item;
+    fn from_data(data: Vec<Self::Item>) -> Self;
+    fn into_data(self) -> Vec<Self::Item>;
+}
+
+macro_rules! impl_column_data {
+    ($($column:ident<$t:ident: $bound:path>),* $(,)?) => {
+        $(
+            impl<$t: $bound> ColumnData for $column<$t> {
+                type Item = $t;
+                fn from_data(data: Vec<$t>) -> Self {
+                    Self::new(data, ColumnAttr::empty())
+                }
+                fn into_data(self) -> Vec<$t> {
+                    self.data
+                }
+            }
+        )*
+    };
+}
+
+impl_column_data!(
+    RleColumn<T: Rleable>,
+    DeltaRleColumn<T: DeltaRleable>,
+    DeltaOfDeltaColumn<T: DeltaOfDeltable>,
+    DeltaBitPackColumn<T: DeltaRleable>,
+    BitPackColumn<T: BitPackable>,
+    FloatXorColumn<T: FloatXorable>,
+    DictColumn<T: Dictable>,
+    AutoColumn<T: Autoable>,
+);
+
+impl<T> ColumnData for GenericColumn<T>
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    type Item = T;
+    fn from_data(data: Vec<T>) -> Self {
+        Self::new(data, ColumnAttr::empty())
+    }
+    fn into_data(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl ColumnData for BoolRleColumn {
+    type Item = bool;
+    fn from_data(data: Vec<bool>) -> Self {
+        Self::new(data, ColumnAttr::empty())
+    }
+    fn into_data(self) -> Vec<bool> {
+        self.data
+    }
+}
+
 // TODO: remove index
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ColumnAttr {
@@ -52,6 +127,15 @@ impl<T: From<i64> + Into<i64> + Copy> From<Vec<T>> for DeltaOfDeltaColumn<T> {
     }
 }
 
+impl<T: Autoable> From<Vec<T>> for AutoColumn<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self {
+            data: value,
+            attr: ColumnAttr::empty(),
+        }
+    }
+}
+
 impl<T: DeltaRleable> From<Vec<T>> for DeltaRleColumn<T> {
     fn from(value: Vec<T>) -> Self {
         Self {
@@ -61,6 +145,15 @@ impl<T: DeltaRleable> From<Vec<T>> for DeltaRleColumn<T> {
     }
 }
 
+impl<T: DeltaRleable> From<Vec<T>> for DeltaBitPackColumn<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self {
+            data: value,
+            attr: ColumnAttr::empty(),
+        }
+    }
+}
+
 impl<T: Rleable> From<Vec<T>> for RleColumn<T> {
     fn from(value: Vec<T>) -> Self {
         Self {
@@ -70,6 +163,48 @@ impl<T: Rleable> From<Vec<T>> for RleColumn<T> {
     }
 }
 
+impl<T: Dictable> From<Vec<T>> for DictColumn<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self {
+            data: value,
+            attr: ColumnAttr::empty(),
+        }
+    }
+}
+
+impl<T: BitPackable> From<Vec<T>> for BitPackColumn<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self {
+            data: value,
+            attr: ColumnAttr::empty(),
+        }
+    }
+}
+
+impl<T: FloatXorable> From<Vec<T>> for FloatXorColumn<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self {
+            data: value,
+            attr: ColumnAttr::empty(),
+        }
+    }
+}
+
+impl<C: ColumnData> From<Vec<Option<C::Item>>> for NullableColumn<C> {
+    fn from(value: Vec<Option<C::Item>>) -> Self {
+        Self {
+            data: value,
+            attr: ColumnAttr::empty(),
+        }
+    }
+}
+
+impl<C: ColumnData> From<Vec<Vec<C::Item>>> for ListColumn<C> {
+    fn from(value: Vec<Vec<C::Item>>) -> Self {
+        Self::new(value, ColumnAttr::empty())
+    }
+}
+
 impl<T> From<Vec<T>> for GenericColumn<T>
 where
     T: Serialize + for<'de> Deserialize<'de>,
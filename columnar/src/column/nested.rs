@@ -0,0 +1,122 @@
+use std::ops::DerefMut;
+
+use serde::{de::Error as DeError, ser::Error as SerError, Deserialize, Serialize};
+
+use crate::{
+    columnar_internal::{ColumnarDecoder, ColumnarEncoder},
+    strategy::{AnyRleDecoder, AnyRleEncoder},
+    ColumnAttr, ColumnarError, FlattenColumn, RowDe, RowSer,
+};
+
+/// The Column of a `flatten` field of `class = "vec"`, whose type is `Vec<T>` of a row struct.
+///
+/// Like the repetition levels of Dremel, the number of the inner rows of each row is
+/// compressed by [AnyRleEncoder], and the inner rows of all the rows are encoded into one
+/// shared group of columns by [FlattenColumn], so that the runs of the inner columns can
+/// continue across rows. The layout is the same as [ListColumn](crate::ListColumn), so it
+/// can be iterated by [ListIter](crate::iterable::ListIter). When serializing, it holds
+/// the references of the vectors.
+#[derive(Debug)]
+pub struct NestedColumn<T> {
+    pub data: Vec<T>,
+    pub attr: ColumnAttr,
+}
+
+impl<T> NestedColumn<T> {
+    pub fn new(data: Vec<T>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl<T> From<Vec<T>> for NestedColumn<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self::new(data, ColumnAttr::empty())
+    }
+}
+
+impl<'c, T> NestedColumn<&'c Vec<T>>
+where
+    T: RowSer<FlattenColumn<&'c T>>,
+{
+    pub(crate) fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut lengths = AnyRleEncoder::<usize>::new();
+        for rows in self.data.iter() {
+            lengths.append(rows.len())?
+        }
+        let rows: FlattenColumn<&'c T> = self
+            .data
+            .iter()
+            .copied()
+            .flatten()
+            .collect::<Vec<_>>()
+            .into();
+
+        let mut encoder = ColumnarEncoder::new();
+        lengths.finish()?.serialize(encoder.deref_mut())?;
+        rows.serialize(encoder.deref_mut())?;
+        Ok(encoder.into_bytes())
+    }
+}
+
+impl<'c, T> Serialize for NestedColumn<&'c Vec<T>>
+where
+    T: RowSer<FlattenColumn<&'c T>>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self.encode().map_err(|e| S::Error::custom(e.to_string()))?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, T> NestedColumn<Vec<T>>
+where
+    T: RowDe<'de, Vec<T>>,
+{
+    fn decode(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let mut decoder = ColumnarDecoder::new(bytes);
+        let lengths: &[u8] = Deserialize::deserialize(decoder.deref_mut())?;
+        let rows: FlattenColumn<T> = Deserialize::deserialize(decoder.deref_mut())?;
+        let lengths = AnyRleDecoder::<usize>::new(lengths).decode()?;
+        let mut rows = rows.data.into_iter();
+        let mut data = Vec::with_capacity(lengths.len());
+        for len in lengths {
+            let inner: Vec<_> = rows.by_ref().take(len).collect();
+            if inner.len() < len {
+                return Err(ColumnarError::ColumnarDecodeError(
+                    "nested column has fewer rows than the sum of lengths".into(),
+                ));
+            }
+            data.push(inner);
+        }
+        if rows.next().is_some() {
+            return Err(ColumnarError::ColumnarDecodeError(
+                "nested column has more rows than the sum of lengths".into(),
+            ));
+        }
+        Ok(data.into())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for NestedColumn<Vec<T>>
+where
+    T: RowDe<'de, Vec<T>>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = Deserialize::deserialize(deserializer)?;
+        Self::decode(bytes).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
@@ -0,0 +1,87 @@
+use std::ops::DerefMut;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    columnar_internal::{ColumnarDecoder, ColumnarEncoder},
+    strategy::{BoolRleDecoder, BoolRleEncoder},
+    ColumnAttr, ColumnarError,
+};
+
+use super::{ColumnData, ColumnTrait};
+
+/// The Column of `Option<T>`, where `C` is the column of the present values.
+///
+/// Whether each value is present is compressed by [BoolRleEncoder], and only the
+/// present values are encoded by `C`. The encoded bytes are the presence bytes
+/// followed by the bytes of `C`, both prefixed by their length.
+#[derive(Debug)]
+pub struct NullableColumn<C: ColumnData> {
+    pub data: Vec<Option<C::Item>>,
+    pub attr: ColumnAttr,
+}
+
+impl<C: ColumnData> NullableColumn<C> {
+    pub fn new(data: Vec<Option<C::Item>>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+}
+
+impl<C> ColumnTrait for NullableColumn<C>
+where
+    C: ColumnData,
+    C::Item: Clone,
+{
+    fn attr(&self) -> ColumnAttr {
+        self.attr
+    }
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut presence = BoolRleEncoder::new();
+        for data in self.data.iter() {
+            presence.append(data.is_some())?
+        }
+        let values = C::from_data(self.data.iter().flatten().cloned().collect());
+
+        let mut encoder = ColumnarEncoder::new();
+        presence.finish()?.serialize(encoder.deref_mut())?;
+        values.encode()?.serialize(encoder.deref_mut())?;
+        Ok(encoder.into_bytes())
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ColumnarError>
+    where
+        Self: Sized,
+    {
+        let mut decoder = ColumnarDecoder::new(bytes);
+        let presence: &[u8] = Deserialize::deserialize(decoder.deref_mut())?;
+        let values: &[u8] = Deserialize::deserialize(decoder.deref_mut())?;
+        let presence = BoolRleDecoder::new(presence).decode()?;
+        let mut values = C::decode(values)?.into_data().into_iter();
+        let mut data = Vec::with_capacity(presence.len());
+        for present in presence {
+            if present {
+                let value = values.next().ok_or_else(|| {
+                    ColumnarError::ColumnarDecodeError(
+                        "nullable column has fewer values than present flags".into(),
+                    )
+                })?;
+                data.push(Some(value));
+            } else {
+                data.push(None);
+            }
+        }
+        if values.next().is_some() {
+            return Err(ColumnarError::ColumnarDecodeError(
+                "nullable column has more values than present flags".into(),
+            ));
+        }
+        Ok(Self {
+            data,
+            attr: ColumnAttr::empty(),
+        })
+    }
+}
@@ -3,8 +3,10 @@ use std::marker::PhantomData;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    column::delta_of_delta::DeltaOfDeltable, BoolRleColumn, ColumnTrait, DeltaOfDeltaColumn,
-    DeltaRleColumn, DeltaRleable, GenericColumn, RleColumn, Rleable,
+    column::delta_of_delta::DeltaOfDeltable, AutoColumn, Autoable, BitPackColumn, BitPackable,
+    BoolRleColumn, ColumnData, ColumnTrait, DeltaBitPackColumn, DeltaOfDeltaColumn, DeltaRleColumn,
+    DeltaRleable, DictColumn, Dictable, FloatXorColumn, FloatXorable, GenericColumn, ListColumn,
+    ListData, NullableColumn, RleColumn, Rleable,
 };
 
 impl<T: Rleable> Serialize for RleColumn<T> {
@@ -162,6 +164,85 @@ impl<'de, T: Rleable> Deserialize<'de> for RleColumn<T> {
     }
 }
 
+impl<T: Autoable> Serialize for AutoColumn<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self.encode().map_err(|e| {
+            // eprintln!("Column Serialize Error: {:?}", e);
+            serde::ser::Error::custom(e.to_string())
+        })?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, T: Autoable> Deserialize<'de> for AutoColumn<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        pub struct ColumnVisitor<T>(PhantomData<T>);
+        impl<'de, T: Autoable> serde::de::Visitor<'de> for ColumnVisitor<T> {
+            type Value = AutoColumn<T>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a columnar encoded auto column")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                AutoColumn::decode(v).map_err(|e| {
+                    eprintln!("Column Deserialize Error: {:?}", e);
+                    serde::de::Error::custom(e.to_string())
+                })
+            }
+        }
+        deserializer.deserialize_bytes(ColumnVisitor(Default::default()))
+    }
+}
+
+impl<T: Dictable> Serialize for DictColumn<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self.encode().map_err(|e| {
+            // eprintln!("Column Serialize Error: {:?}", e);
+            serde::ser::Error::custom(e.to_string())
+        })?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, T: Dictable> Deserialize<'de> for DictColumn<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        pub struct ColumnVisitor<T>(PhantomData<T>);
+        impl<'de, T> serde::de::Visitor<'de> for ColumnVisitor<T>
+        where
+            T: Dictable,
+        {
+            type Value = DictColumn<T>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a columnar encoded dict column")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                DictColumn::decode(v).map_err(|e| {
+                    eprintln!("Column Deserialize Error: {:?}", e);
+                    serde::de::Error::custom(e.to_string())
+                })
+            }
+        }
+        deserializer.deserialize_bytes(ColumnVisitor(Default::default()))
+    }
+}
+
 impl<T> Serialize for GenericColumn<T>
 where
     T: Serialize + for<'de> Deserialize<'de>,
@@ -208,3 +289,217 @@ where
         deserializer.deserialize_bytes(ColumnVisitor(Default::default()))
     }
 }
+
+impl<T: FloatXorable> Serialize for FloatXorColumn<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self.encode().map_err(|e| {
+            // eprintln!("Column Serialize Error: {:?}", e);
+            serde::ser::Error::custom(e.to_string())
+        })?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, T: FloatXorable> Deserialize<'de> for FloatXorColumn<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        pub struct ColumnVisitor<T>(PhantomData<T>);
+        impl<'de, T: FloatXorable> serde::de::Visitor<'de> for ColumnVisitor<T> {
+            type Value = FloatXorColumn<T>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a columnar encoded float xor column")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                FloatXorColumn::decode(v).map_err(|e| {
+                    eprintln!("Column Deserialize Error: {:?}", e);
+                    serde::de::Error::custom(e.to_string())
+                })
+            }
+        }
+        deserializer.deserialize_bytes(ColumnVisitor(Default::default()))
+    }
+}
+
+impl<T: BitPackable> Serialize for BitPackColumn<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self.encode().map_err(|e| {
+            // eprintln!("Column Serialize Error: {:?}", e);
+            serde::ser::Error::custom(e.to_string())
+        })?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, T: BitPackable> Deserialize<'de> for BitPackColumn<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        pub struct ColumnVisitor<T>(PhantomData<T>);
+        impl<'de, T: BitPackable> serde::de::Visitor<'de> for ColumnVisitor<T> {
+            type Value = BitPackColumn<T>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a columnar encoded bit pack column")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                BitPackColumn::decode(v).map_err(|e| {
+                    eprintln!("Column Deserialize Error: {:?}", e);
+                    serde::de::Error::custom(e.to_string())
+                })
+            }
+        }
+        deserializer.deserialize_bytes(ColumnVisitor(Default::default()))
+    }
+}
+
+impl<T: DeltaRleable> Serialize for DeltaBitPackColumn<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self.encode().map_err(|e| {
+            // eprintln!("Column Serialize Error: {:?}", e);
+            serde::ser::Error::custom(e.to_string())
+        })?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, T: DeltaRleable> Deserialize<'de> for DeltaBitPackColumn<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        pub struct ColumnVisitor<T>(PhantomData<T>);
+        impl<'de, T: DeltaRleable> serde::de::Visitor<'de> for ColumnVisitor<T> {
+            type Value = DeltaBitPackColumn<T>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a columnar encoded delta bit pack column")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                DeltaBitPackColumn::decode(v).map_err(|e| {
+                    eprintln!("Column Deserialize Error: {:?}", e);
+                    serde::de::Error::custom(e.to_string())
+                })
+            }
+        }
+        deserializer.deserialize_bytes(ColumnVisitor(Default::default()))
+    }
+}
+
+impl<C> Serialize for NullableColumn<C>
+where
+    C: ColumnData,
+    C::Item: Clone,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self.encode().map_err(|e| {
+            // eprintln!("Column Serialize Error: {:?}", e);
+            serde::ser::Error::custom(e.to_string())
+        })?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, C> Deserialize<'de> for NullableColumn<C>
+where
+    C: ColumnData,
+    C::Item: Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        pub struct ColumnVisitor<C>(PhantomData<C>);
+        impl<'de, C> serde::de::Visitor<'de> for ColumnVisitor<C>
+        where
+            C: ColumnData,
+            C::Item: Clone,
+        {
+            type Value = NullableColumn<C>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a columnar encoded nullable column")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                NullableColumn::decode(v).map_err(|e| {
+                    eprintln!("Column Deserialize Error: {:?}", e);
+                    serde::de::Error::custom(e.to_string())
+                })
+            }
+        }
+        deserializer.deserialize_bytes(ColumnVisitor(Default::default()))
+    }
+}
+
+impl<C, L> Serialize for ListColumn<C, L>
+where
+    C: ColumnData,
+    L: ListData<C::Item>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self.encode_lists().map_err(|e| {
+            // eprintln!("Column Serialize Error: {:?}", e);
+            serde::ser::Error::custom(e.to_string())
+        })?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, C> Deserialize<'de> for ListColumn<C>
+where
+    C: ColumnData,
+    C::Item: Clone,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        pub struct ColumnVisitor<C>(PhantomData<C>);
+        impl<'de, C> serde::de::Visitor<'de> for ColumnVisitor<C>
+        where
+            C: ColumnData,
+            C::Item: Clone,
+        {
+            type Value = ListColumn<C>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a columnar encoded list column")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                ListColumn::decode(v).map_err(|e| {
+                    eprintln!("Column Deserialize Error: {:?}", e);
+                    serde::de::Error::custom(e.to_string())
+                })
+            }
+        }
+        deserializer.deserialize_bytes(ColumnVisitor(Default::default()))
+    }
+}
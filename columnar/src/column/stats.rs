@@ -0,0 +1,125 @@
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    strategy::{BoolRleEncoder, DeltaOfDeltaEncoder, DeltaRleEncoder},
+    BoolRleColumn, ColumnAttr, ColumnStats, ColumnarError, DeltaOfDeltaColumn, DeltaRleColumn,
+    DeltaRleable,
+};
+
+use super::{delta_of_delta::DeltaOfDeltable, ColumnData, ColumnTrait};
+
+/// The columns whose encoders track the [ColumnStats] of the values
+pub trait StatsColumnData: ColumnData {
+    /// Encodes `data` like the column does, the bytes start with the header of [ColumnStats]
+    fn encode_with_stats(data: &[Self::Item]) -> Result<Vec<u8>, ColumnarError>;
+}
+
+impl<T: DeltaRleable> StatsColumnData for DeltaRleColumn<T> {
+    fn encode_with_stats(data: &[T]) -> Result<Vec<u8>, ColumnarError> {
+        let mut delta_rle = DeltaRleEncoder::with_stats();
+        for &data in data.iter() {
+            delta_rle.append(data)?;
+        }
+        delta_rle.finish_with_stats()
+    }
+}
+
+impl<T: DeltaOfDeltable> StatsColumnData for DeltaOfDeltaColumn<T> {
+    fn encode_with_stats(data: &[T]) -> Result<Vec<u8>, ColumnarError> {
+        let mut delta_of_delta = DeltaOfDeltaEncoder::with_stats();
+        for &data in data.iter() {
+            delta_of_delta.append(data.try_into().map_err(|_| {
+                ColumnarError::RleEncodeError(format!(
+                    "{:?} cannot be safely converted into i64",
+                    data
+                ))
+            })?)?
+        }
+        delta_of_delta.finish_with_stats()
+    }
+}
+
+impl StatsColumnData for BoolRleColumn {
+    fn encode_with_stats(data: &[bool]) -> Result<Vec<u8>, ColumnarError> {
+        let mut rle_encoder = BoolRleEncoder::with_stats();
+        for &data in data.iter() {
+            rle_encoder.append(data)?;
+        }
+        rle_encoder.finish_with_stats()
+    }
+}
+
+/// The column of a field with `#[columnar(stats)]`, which is encoded by the column `C`
+/// and whose bytes start with the header of [ColumnStats] of the values, so the header can
+/// be read by [ColumnStats::read_column] without decoding the column.
+pub struct StatsColumn<C: ColumnData> {
+    pub data: Vec<C::Item>,
+    pub attr: ColumnAttr,
+}
+
+impl<C: ColumnData> StatsColumn<C> {
+    pub fn new(data: Vec<C::Item>, attr: ColumnAttr) -> Self {
+        Self { data, attr }
+    }
+}
+
+impl<C: StatsColumnData> ColumnTrait for StatsColumn<C> {
+    fn attr(&self) -> ColumnAttr {
+        self.attr
+    }
+
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ColumnarError> {
+        C::encode_with_stats(&self.data)
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, ColumnarError>
+    where
+        Self: Sized,
+    {
+        let (_, payload) = ColumnStats::split(bytes)?;
+        Ok(Self {
+            data: C::decode(payload)?.into_data(),
+            attr: ColumnAttr::empty(),
+        })
+    }
+}
+
+impl<C: StatsColumnData> Serialize for StatsColumn<C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self
+            .encode()
+            .map_err(|e| serde::ser::Error::custom(e.to_string()))?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+impl<'de, C: StatsColumnData> Deserialize<'de> for StatsColumn<C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct ColumnVisitor<C>(PhantomData<C>);
+        impl<C: StatsColumnData> serde::de::Visitor<'_> for ColumnVisitor<C> {
+            type Value = StatsColumn<C>;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a columnar encoded column with stats")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                StatsColumn::decode(v).map_err(|e| serde::de::Error::custom(e.to_string()))
+            }
+        }
+        deserializer.deserialize_bytes(ColumnVisitor(PhantomData))
+    }
+}
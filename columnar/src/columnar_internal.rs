@@ -1,4 +1,7 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    io,
+    ops::{Deref, DerefMut},
+};
 
 use postcard::{de_flavors::Flavor as DeFlavor, ser_flavors::Flavor, Deserializer, Serializer};
 
@@ -158,3 +161,154 @@ impl DerefMut for ColumnarEncoder {
         &mut self.ser
     }
 }
+
+/// The default capacity of the buffer of [WriterFlavor]
+pub const DEFAULT_WRITER_CAPACITY: usize = 8 * 1024;
+
+/// The flavor that writes the output to `W` through a bounded buffer.
+///
+/// The buffered bytes are flushed to `W` when the buffer is full, so the whole output is
+/// never held in memory. The [io::Error] is kept in `error` because postcard can not
+/// carry it, and [WriterFlavor::take_error] should be checked after serializing.
+pub struct WriterFlavor<W> {
+    writer: W,
+    buf: Vec<u8>,
+    capacity: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> WriterFlavor<W> {
+    pub fn new(writer: W, capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            writer,
+            buf: Vec::with_capacity(capacity),
+            capacity,
+            error: None,
+        }
+    }
+
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Writes the buffered bytes and flushes `W`
+    pub fn finish(mut self) -> Result<W, ColumnarError> {
+        if !self.buf.is_empty() {
+            self.writer.write_all(&self.buf)?;
+            self.buf.clear();
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn check<T>(&mut self, result: io::Result<T>) -> postcard::Result<T> {
+        result.map_err(|e| {
+            self.error = Some(e);
+            postcard::Error::SerializeBufferFull
+        })
+    }
+
+    fn flush_buf(&mut self) -> postcard::Result<()> {
+        if !self.buf.is_empty() {
+            let result = self.writer.write_all(&self.buf);
+            self.check(result)?;
+            self.buf.clear();
+        }
+        Ok(())
+    }
+}
+
+impl<W: io::Write> Flavor for WriterFlavor<W> {
+    type Output = W;
+
+    fn try_extend(&mut self, data: &[u8]) -> postcard::Result<()> {
+        if self.buf.len() + data.len() > self.capacity {
+            self.flush_buf()?;
+        }
+        if data.len() >= self.capacity {
+            let result = self.writer.write_all(data);
+            self.check(result)
+        } else {
+            self.buf.extend_from_slice(data);
+            Ok(())
+        }
+    }
+
+    fn try_push(&mut self, data: u8) -> postcard::Result<()> {
+        if self.buf.len() == self.capacity {
+            self.flush_buf()?;
+        }
+        self.buf.push(data);
+        Ok(())
+    }
+
+    fn finalize(mut self) -> postcard::Result<Self::Output> {
+        self.flush_buf()?;
+        let result = self.writer.flush();
+        self.check(result)?;
+        Ok(self.writer)
+    }
+}
+
+/// The flavor that reads the input from `R` into the buffer `buf`.
+///
+/// Only the bytes requested by the deserializer are read. The bytes borrowed by the
+/// deserialized value are kept in `buf`, which only moves forward and is never reused, so
+/// it must be large enough to hold all of them at once, about the whole input. The
+/// [ColumnarError] is kept in `error` because postcard can not carry it.
+///
+/// It does not buffer `R`, so that nothing after the value is consumed, and [DeFlavor::pop]
+/// reads a single byte each time. The callers must pass a buffered reader such as
+/// [io::BufReader] unless `R` is already in memory.
+pub struct ReaderFlavor<'de, R> {
+    reader: R,
+    buf: &'de mut [u8],
+    error: Option<ColumnarError>,
+}
+
+impl<'de, R: io::Read> ReaderFlavor<'de, R> {
+    pub fn new(reader: R, buf: &'de mut [u8]) -> Self {
+        Self {
+            reader,
+            buf,
+            error: None,
+        }
+    }
+
+    fn fail(&mut self, error: ColumnarError) -> postcard::Error {
+        self.error = Some(error);
+        postcard::Error::DeserializeUnexpectedEnd
+    }
+}
+
+impl<'de, R: io::Read + 'de> DeFlavor<'de> for ReaderFlavor<'de, R> {
+    type Remainder = Option<ColumnarError>;
+    type Source = R;
+
+    fn pop(&mut self) -> postcard::Result<u8> {
+        let mut byte = [0u8; 1];
+        match self.reader.read_exact(&mut byte) {
+            Ok(()) => Ok(byte[0]),
+            Err(e) => Err(self.fail(ColumnarError::IOError(e))),
+        }
+    }
+
+    fn try_take_n(&mut self, ct: usize) -> postcard::Result<&'de [u8]> {
+        if ct > self.buf.len() {
+            return Err(self.fail(ColumnarError::ColumnarDecodeError(
+                "the buffer is too small to hold the borrowed bytes".into(),
+            )));
+        }
+        let (head, tail) = std::mem::take(&mut self.buf).split_at_mut(ct);
+        self.buf = tail;
+        match self.reader.read_exact(head) {
+            Ok(()) => Ok(head),
+            Err(e) => Err(self.fail(ColumnarError::IOError(e))),
+        }
+    }
+
+    fn finalize(self) -> postcard::Result<Self::Remainder> {
+        Ok(self.error)
+    }
+}
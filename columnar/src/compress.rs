@@ -0,0 +1,163 @@
+//! General-purpose compression of the bytes of a column or a field of a table, which runs
+//! after the columnar strategy of the field.
+//!
+//! The compressed bytes start with a tag of the compression, so the decoder knows whether
+//! and how to decompress them. The bytes are stored as they are if the compression does not
+//! make them smaller. The tag `2` is reserved for LZ4.
+use std::borrow::Cow;
+#[cfg(feature = "deflate")]
+use std::io::{Read, Write};
+
+use serde::{
+    de::{DeserializeOwned, Error as DeError, Visitor},
+    ser::Error as SerError,
+    Deserialize, Serialize,
+};
+
+use crate::ColumnarError;
+
+/// The tag of the bytes that are stored as they are
+const TAG_STORED: u8 = 0;
+/// The tag of the bytes compressed by deflate
+const TAG_DEFLATE: u8 = 1;
+/// The tag of the bytes compressed by LZ4, which is not supported yet
+const TAG_LZ4: u8 = 2;
+
+/// The maximum number of the bytes [decompress] inflates, so a small corrupt or
+/// malicious input cannot allocate an unbounded amount of memory
+pub const MAX_DECOMPRESSED_LEN: usize = 1 << 30;
+
+/// The general-purpose compression of the bytes, set by `#[columnar(compress = "...")]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Compression {
+    /// The bytes are stored as they are
+    Stored,
+    /// Deflate of `flate2`, which needs the `deflate` feature
+    #[cfg(feature = "deflate")]
+    Deflate,
+}
+
+/// Compresses `bytes` by `compression`, the output starts with the tag of the compression
+pub fn compress(bytes: &[u8], compression: Compression) -> Result<Vec<u8>, ColumnarError> {
+    let compressed: Option<Vec<u8>> = match compression {
+        Compression::Stored => None,
+        #[cfg(feature = "deflate")]
+        Compression::Deflate => {
+            let mut encoder = flate2::write::DeflateEncoder::new(
+                vec![TAG_DEFLATE],
+                flate2::Compression::default(),
+            );
+            encoder.write_all(bytes)?;
+            Some(encoder.finish()?)
+        }
+    };
+    match compressed {
+        Some(compressed) if compressed.len() <= bytes.len() => Ok(compressed),
+        _ => {
+            let mut stored = Vec::with_capacity(bytes.len() + 1);
+            stored.push(TAG_STORED);
+            stored.extend_from_slice(bytes);
+            Ok(stored)
+        }
+    }
+}
+
+/// Decompresses the bytes written by [compress], up to [MAX_DECOMPRESSED_LEN] bytes
+pub fn decompress(bytes: &[u8]) -> Result<Cow<'_, [u8]>, ColumnarError> {
+    decompress_with_limit(bytes, MAX_DECOMPRESSED_LEN)
+}
+
+/// Decompresses the bytes written by [compress], returns an error if they inflate to
+/// more than `limit` bytes
+pub fn decompress_with_limit(bytes: &[u8], limit: usize) -> Result<Cow<'_, [u8]>, ColumnarError> {
+    match bytes.split_first() {
+        Some((&TAG_STORED, payload)) if payload.len() > limit => Err(exceed_limit(limit)),
+        Some((&TAG_STORED, payload)) => Ok(Cow::Borrowed(payload)),
+        #[cfg(feature = "deflate")]
+        Some((&TAG_DEFLATE, payload)) => {
+            let mut decompressed = Vec::new();
+            flate2::read::DeflateDecoder::new(payload)
+                .take(limit as u64 + 1)
+                .read_to_end(&mut decompressed)?;
+            if decompressed.len() > limit {
+                return Err(exceed_limit(limit));
+            }
+            Ok(Cow::Owned(decompressed))
+        }
+        #[cfg(not(feature = "deflate"))]
+        Some((&TAG_DEFLATE, _)) => Err(ColumnarError::ColumnarDecodeError(
+            "the bytes are compressed by deflate, which needs the `deflate` feature".into(),
+        )),
+        Some((&TAG_LZ4, _)) => Err(ColumnarError::ColumnarDecodeError(
+            "LZ4 compression is not supported".into(),
+        )),
+        Some((tag, _)) => Err(ColumnarError::ColumnarDecodeError(
+            format!("unknown compression tag {}", tag).into_boxed_str(),
+        )),
+        None => Err(ColumnarError::ColumnarDecodeError(
+            "missing compression tag".into(),
+        )),
+    }
+}
+
+fn exceed_limit(limit: usize) -> ColumnarError {
+    ColumnarError::ColumnarDecodeError(
+        format!("the decompressed bytes exceed the limit of {} bytes", limit).into_boxed_str(),
+    )
+}
+
+/// Serializes the value by postcard and writes the compressed bytes, which is the
+/// serialized form of a field with `compress`
+#[doc(hidden)]
+pub struct Compressed<'a, T: ?Sized>(pub &'a T, pub Compression);
+
+impl<T: Serialize + ?Sized> Compressed<'_, T> {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ColumnarError> {
+        compress(&postcard::to_allocvec(self.0)?, self.1)
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Compressed<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let bytes = self
+            .to_bytes()
+            .map_err(|e| S::Error::custom(e.to_string()))?;
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+/// The value deserialized from the bytes written by [Compressed], which is owned because
+/// the decompressed bytes do not outlive the deserialization
+#[doc(hidden)]
+pub struct Decompressed<T>(pub T);
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Decompressed<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct DecompressVisitor<T>(std::marker::PhantomData<T>);
+        impl<T: DeserializeOwned> Visitor<'_> for DecompressVisitor<T> {
+            type Value = T;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("compressed bytes")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: DeError,
+            {
+                let bytes = decompress(v).map_err(|e| E::custom(e.to_string()))?;
+                postcard::from_bytes(&bytes).map_err(|e| E::custom(e.to_string()))
+            }
+        }
+        deserializer
+            .deserialize_bytes(DecompressVisitor(std::marker::PhantomData))
+            .map(Decompressed)
+    }
+}
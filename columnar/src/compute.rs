@@ -0,0 +1,289 @@
+//! Aggregations computed on the encoded bytes of a column, run by run.
+//!
+//! A run of equal values is counted once with its length, and a run of equal deltas of
+//! `DeltaRle` is an arithmetic series whose sum, min and max follow from its ends, so the
+//! values of the runs are never expanded. The decoder type selects how the bytes are read,
+//! e.g. `compute::sum::<DeltaRleDecoder<u32>>(&bytes)`.
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{
+    strategy::{AnyRleDecoder, BoolRleDecoder, DeltaRleDecoder, RleRun},
+    ColumnarError, DeltaRleable, Rleable,
+};
+
+/// A run of the values of a column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValueRun<T> {
+    /// `len` copies of `value`
+    Repeat { value: T, len: usize },
+    /// `len` values from `first`, each one is `step` more than the one before it.
+    /// `step` is never 0.
+    Series { first: i128, step: i128, len: usize },
+}
+
+/// The decoder whose runs can be aggregated by the functions of [compute](self)
+pub trait Aggregate<'de> {
+    type Item;
+
+    /// Calls `f` with each run of `bytes`
+    fn for_each_run(
+        bytes: &'de [u8],
+        f: impl FnMut(ValueRun<Self::Item>) -> Result<(), ColumnarError>,
+    ) -> Result<(), ColumnarError>;
+
+    /// Converts a value of a [ValueRun::Series]
+    fn from_i128(value: i128) -> Result<Self::Item, ColumnarError>;
+}
+
+fn no_series<T>(value: i128) -> Result<T, ColumnarError> {
+    Err(ColumnarError::RleDecodeError(format!(
+        "unexpected series value {}",
+        value
+    )))
+}
+
+impl<'de, T: Rleable> Aggregate<'de> for AnyRleDecoder<'de, T> {
+    type Item = T;
+
+    fn for_each_run(
+        bytes: &'de [u8],
+        mut f: impl FnMut(ValueRun<T>) -> Result<(), ColumnarError>,
+    ) -> Result<(), ColumnarError> {
+        for run in AnyRleDecoder::<T>::new(bytes).runs() {
+            match run? {
+                RleRun::Run { value, len } => f(ValueRun::Repeat { value, len })?,
+                RleRun::Literal(values) => {
+                    for value in values {
+                        f(ValueRun::Repeat { value, len: 1 })?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn from_i128(value: i128) -> Result<T, ColumnarError> {
+        no_series(value)
+    }
+}
+
+impl<'de> Aggregate<'de> for BoolRleDecoder<'de> {
+    type Item = bool;
+
+    fn for_each_run(
+        bytes: &'de [u8],
+        mut f: impl FnMut(ValueRun<bool>) -> Result<(), ColumnarError>,
+    ) -> Result<(), ColumnarError> {
+        for run in BoolRleDecoder::new(bytes).runs() {
+            if let RleRun::Run { value, len } = run? {
+                f(ValueRun::Repeat { value, len })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn from_i128(value: i128) -> Result<bool, ColumnarError> {
+        no_series(value)
+    }
+}
+
+impl<'de, T: DeltaRleable> Aggregate<'de> for DeltaRleDecoder<'de, T> {
+    type Item = T;
+
+    fn for_each_run(
+        bytes: &'de [u8],
+        mut f: impl FnMut(ValueRun<T>) -> Result<(), ColumnarError>,
+    ) -> Result<(), ColumnarError> {
+        let mut absolute_value: i128 = 0;
+        let mut delta_run = |delta: i128, len: usize| {
+            let first = absolute_value.saturating_add(delta);
+            absolute_value = absolute_value.saturating_add(delta.saturating_mul(len as i128));
+            if delta == 0 {
+                f(ValueRun::Repeat {
+                    value: Self::from_i128(first)?,
+                    len,
+                })
+            } else {
+                f(ValueRun::Series {
+                    first,
+                    step: delta,
+                    len,
+                })
+            }
+        };
+        for run in AnyRleDecoder::<i128>::new(bytes).runs() {
+            match run? {
+                RleRun::Run { value, len } => delta_run(value, len)?,
+                RleRun::Literal(deltas) => {
+                    for delta in deltas {
+                        delta_run(delta, 1)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn from_i128(value: i128) -> Result<T, ColumnarError> {
+        T::try_from(value).map_err(|_| {
+            ColumnarError::RleDecodeError(format!("{} cannot be safely converted from i128", value))
+        })
+    }
+}
+
+/// The values of a series, which are decoded one by one
+fn series_values<'de, D: Aggregate<'de>>(
+    first: i128,
+    step: i128,
+    len: usize,
+) -> impl Iterator<Item = Result<D::Item, ColumnarError>> {
+    (0..len as i128).map(move |i| D::from_i128(first.saturating_add(step.saturating_mul(i))))
+}
+
+/// The number of the values
+pub fn count<'de, D: Aggregate<'de>>(bytes: &'de [u8]) -> Result<usize, ColumnarError> {
+    let mut count = 0;
+    D::for_each_run(bytes, |run| {
+        count += match run {
+            ValueRun::Repeat { len, .. } | ValueRun::Series { len, .. } => len,
+        };
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+/// The sum of the values, `true` counts as 1
+pub fn sum<'de, D>(bytes: &'de [u8]) -> Result<i128, ColumnarError>
+where
+    D: Aggregate<'de>,
+    D::Item: TryInto<i128>,
+{
+    let mut sum: i128 = 0;
+    D::for_each_run(bytes, |run| {
+        let run_sum = match run {
+            ValueRun::Repeat { value, len } => {
+                let value: i128 = value
+                    .try_into()
+                    .map_err(|_| ColumnarError::RleDecodeError("cannot into i128".to_string()))?;
+                value.checked_mul(len as i128)
+            }
+            // len * first + step * len * (len - 1) / 2
+            ValueRun::Series { first, step, len } => {
+                let len = len as i128;
+                first
+                    .checked_mul(len)
+                    .zip(step.checked_mul(len * (len - 1) / 2))
+                    .and_then(|(a, b)| a.checked_add(b))
+            }
+        };
+        sum = run_sum
+            .and_then(|s| sum.checked_add(s))
+            .ok_or(ColumnarError::OverflowError)?;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// The smallest and the largest value of a run
+fn run_bounds<'de, D: Aggregate<'de>>(
+    run: ValueRun<D::Item>,
+) -> Result<(D::Item, D::Item), ColumnarError>
+where
+    D::Item: Clone,
+{
+    match run {
+        ValueRun::Repeat { value, .. } => Ok((value.clone(), value)),
+        ValueRun::Series { first, step, len } => {
+            let last = first.saturating_add(step.saturating_mul(len as i128 - 1));
+            let (min, max) = if step > 0 {
+                (first, last)
+            } else {
+                (last, first)
+            };
+            Ok((D::from_i128(min)?, D::from_i128(max)?))
+        }
+    }
+}
+
+/// The smallest value, or `None` if the column is empty
+pub fn min<'de, D>(bytes: &'de [u8]) -> Result<Option<D::Item>, ColumnarError>
+where
+    D: Aggregate<'de>,
+    D::Item: Ord + Clone,
+{
+    let mut ans: Option<D::Item> = None;
+    D::for_each_run(bytes, |run| {
+        let (min, _) = run_bounds::<D>(run)?;
+        if ans.as_ref().is_none_or(|ans| min < *ans) {
+            ans = Some(min);
+        }
+        Ok(())
+    })?;
+    Ok(ans)
+}
+
+/// The largest value, or `None` if the column is empty
+pub fn max<'de, D>(bytes: &'de [u8]) -> Result<Option<D::Item>, ColumnarError>
+where
+    D: Aggregate<'de>,
+    D::Item: Ord + Clone,
+{
+    let mut ans: Option<D::Item> = None;
+    D::for_each_run(bytes, |run| {
+        let (_, max) = run_bounds::<D>(run)?;
+        if ans.as_ref().is_none_or(|ans| max > *ans) {
+            ans = Some(max);
+        }
+        Ok(())
+    })?;
+    Ok(ans)
+}
+
+/// The number of the distinct values
+pub fn distinct_count<'de, D>(bytes: &'de [u8]) -> Result<usize, ColumnarError>
+where
+    D: Aggregate<'de>,
+    D::Item: Hash + Eq,
+{
+    let mut values = HashSet::new();
+    D::for_each_run(bytes, |run| {
+        match run {
+            ValueRun::Repeat { value, .. } => {
+                values.insert(value);
+            }
+            ValueRun::Series { first, step, len } => {
+                for value in series_values::<D>(first, step, len) {
+                    values.insert(value?);
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(values.len())
+}
+
+/// The number of the occurrences of each value
+pub fn value_counts<'de, D>(bytes: &'de [u8]) -> Result<HashMap<D::Item, usize>, ColumnarError>
+where
+    D: Aggregate<'de>,
+    D::Item: Hash + Eq,
+{
+    let mut counts = HashMap::new();
+    D::for_each_run(bytes, |run| {
+        match run {
+            ValueRun::Repeat { value, len } => {
+                *counts.entry(value).or_insert(0) += len;
+            }
+            ValueRun::Series { first, step, len } => {
+                for value in series_values::<D>(first, step, len) {
+                    *counts.entry(value?).or_insert(0) += 1;
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(counts)
+}
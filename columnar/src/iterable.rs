@@ -1,8 +1,12 @@
 use std::marker::PhantomData;
 
 use crate::{
-    column::delta_of_delta::DeltaOfDeltable, columnar_internal::Cursor, strategy::MAX_RLE_COUNT,
-    ColumnarError, DeltaOfDeltaDecoder, DeltaRleable, Rleable,
+    column::delta_of_delta::DeltaOfDeltable,
+    columnar_internal::Cursor,
+    strategy::{nth_by_skip_values, StrategyTag},
+    AnyRleDecoder, Autoable, BitPackDecoder, BitPackable, BoolRleDecoder, ColumnarError,
+    DeltaBitPackDecoder, DeltaOfDeltaDecoder, DeltaRleDecoder, DeltaRleable, Dictable,
+    FloatXorDecoder, FloatXorable, RleRun, RleRuns, Rleable,
 };
 use postcard::Deserializer;
 use serde::de::Error;
@@ -62,59 +66,63 @@ where
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.size_hint, None)
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.always_default {
+            return Some(Ok(T::default()));
+        }
+        for _ in 0..n {
+            if let Err(e) = self.next()? {
+                return Some(Err(e));
+            }
+        }
+        self.next()
+    }
 }
 
 pub struct AnyRleIter<'de, T> {
-    de: Deserializer<'de, Cursor<'de>>,
-    last_value: Option<T>,
-    count: isize,
-    literal: bool,
+    decoder: AnyRleDecoder<'de, T>,
 }
 
 impl<'de, T: Rleable> AnyRleIter<'de, T> {
     pub fn new(bytes: &'de [u8]) -> Self {
         Self {
-            de: Deserializer::from_flavor(Cursor::new(bytes)),
-            last_value: None,
-            count: 0,
-            literal: false,
+            decoder: AnyRleDecoder::new_strict(bytes),
         }
     }
 
+    /// Skips `n` values, the values in a run are dropped at once by adjusting `count`.
+    /// Returns the number of the skipped values, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        self.decoder.skip_values(n)
+    }
+
     pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
-        while self.count == 0 {
-            let count = isize::deserialize(&mut self.de);
-            let count = match count {
-                Err(postcard::Error::DeserializeUnexpectedEnd) => return Ok(None),
-                Err(e) => return Err(ColumnarError::from(e)),
-                Ok(c) => c,
-            };
-            // Prevent bad data from causing oom loops
-            if count.unsigned_abs() > MAX_RLE_COUNT {
-                return Err(ColumnarError::RleDecodeError(format!(
-                    "decode Rle count is too large : {}",
-                    self.count
-                )));
-            }
-            match count {
-                n if n > 0 => {
-                    self.count = n;
-                    self.last_value = Some(T::deserialize(&mut self.de)?);
-                    self.literal = false;
-                }
-                n if n < 0 => {
-                    self.count = n.abs();
-                    self.literal = true;
-                }
-                _ => return Err(ColumnarError::RleDecodeError("Invalid count".to_string())),
-            }
-        }
-        self.count -= 1;
-        if self.literal {
-            Ok(Some(T::deserialize(&mut self.de)?))
-        } else {
-            Ok(self.last_value.clone())
-        }
+        self.decoder.try_next()
+    }
+
+    /// Decodes the rest of the current run, or the next run if it is finished
+    pub(crate) fn try_next_run(&mut self) -> Result<Option<RleRun<T>>, ColumnarError> {
+        self.decoder.try_next_run()
+    }
+
+    /// The remaining runs, each one is decoded once instead of value by value
+    pub fn runs(&mut self) -> RleRuns<'_, Self> {
+        RleRuns { decoder: self }
+    }
+
+    /// Takes the next value and the values equal to it in the same run, returns the value and
+    /// the number of the values taken
+    pub fn next_value_run(&mut self) -> Result<Option<(T, usize)>, ColumnarError> {
+        self.decoder.next_value_run()
+    }
+}
+
+impl<'de, T: Rleable> Iterator for RleRuns<'_, AnyRleIter<'de, T>> {
+    type Item = Result<RleRun<T>, ColumnarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.try_next_run().transpose()
     }
 }
 
@@ -124,6 +132,8 @@ impl<'de, T: Rleable> Iterator for AnyRleIter<'de, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.try_next().transpose()
     }
+
+    nth_by_skip_values!();
 }
 
 pub struct DeltaOfDeltaIter<'de, T> {
@@ -139,6 +149,11 @@ impl<'de, T: DeltaOfDeltable> DeltaOfDeltaIter<'de, T> {
     pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
         self.decoder.try_next()
     }
+
+    /// Skips `n` values, the bit reader is fast-forwarded over the zero delta of deltas
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        self.decoder.skip_values(n)
+    }
 }
 
 impl<'de, T: DeltaOfDeltable> Iterator for DeltaOfDeltaIter<'de, T> {
@@ -146,103 +161,562 @@ impl<'de, T: DeltaOfDeltable> Iterator for DeltaOfDeltaIter<'de, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.try_next().transpose()
     }
+
+    nth_by_skip_values!();
+}
+
+pub struct FloatXorIter<'de, T> {
+    decoder: FloatXorDecoder<'de, T>,
+}
+
+impl<'de, T: FloatXorable> FloatXorIter<'de, T> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let decoder = FloatXorDecoder::new(bytes)?;
+        Ok(Self { decoder })
+    }
+
+    pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        self.decoder.try_next()
+    }
+}
+
+impl<'de, T: FloatXorable> Iterator for FloatXorIter<'de, T> {
+    type Item = Result<T, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+}
+
+pub struct BitPackIter<'de, T> {
+    decoder: BitPackDecoder<'de, T>,
+}
+
+impl<'de, T: BitPackable> BitPackIter<'de, T> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let decoder = BitPackDecoder::new(bytes)?;
+        Ok(Self { decoder })
+    }
+
+    pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        self.decoder.try_next()
+    }
+}
+
+impl<'de, T: BitPackable> Iterator for BitPackIter<'de, T> {
+    type Item = Result<T, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+}
+
+pub struct DeltaBitPackIter<'de, T> {
+    decoder: DeltaBitPackDecoder<'de, T>,
+}
+
+impl<'de, T: DeltaRleable> DeltaBitPackIter<'de, T> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let decoder = DeltaBitPackDecoder::new(bytes)?;
+        Ok(Self { decoder })
+    }
+
+    pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        self.decoder.try_next()
+    }
+}
+
+impl<'de, T: DeltaRleable> Iterator for DeltaBitPackIter<'de, T> {
+    type Item = Result<T, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
 }
 
 pub struct DeltaRleIter<'de, T> {
-    rle_iter: AnyRleIter<'de, i128>,
-    absolute_value: i128,
-    _type: PhantomData<T>,
+    decoder: DeltaRleDecoder<'de, T>,
 }
 
 impl<'de, T: DeltaRleable> DeltaRleIter<'de, T> {
     pub fn new(bytes: &'de [u8]) -> Self {
         Self {
-            rle_iter: AnyRleIter::new(bytes),
-            absolute_value: 0,
-            _type: PhantomData,
+            decoder: DeltaRleDecoder::new_strict(bytes),
         }
     }
 
     pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
-        let next = self.rle_iter.try_next()?;
-        if let Some(delta) = next {
-            self.absolute_value = self.absolute_value.saturating_add(delta);
-            Ok(Some(self.absolute_value.try_into().map_err(|_| {
-                ColumnarError::RleDecodeError(format!(
-                    "{} cannot be safely converted from i128",
-                    self.absolute_value
-                ))
-            })?))
-        } else {
-            Ok(None)
+        self.decoder.try_next()?.map(into_delta_rleable).transpose()
+    }
+
+    /// Skips `n` values, `delta * k` is added at once for `k` values in a run.
+    /// Returns the number of the skipped values, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        self.decoder.skip_values(n)
+    }
+
+    /// Takes the next value and the values equal to it in the same run of zero deltas,
+    /// returns the value and the number of the values taken
+    pub fn next_value_run(&mut self) -> Result<Option<(T, usize)>, ColumnarError> {
+        match self.decoder.next_value_run()? {
+            Some((value, len)) => Ok(Some((into_delta_rleable(value)?, len))),
+            None => Ok(None),
         }
     }
 }
 
+fn into_delta_rleable<T: DeltaRleable>(value: i128) -> Result<T, ColumnarError> {
+    value.try_into().map_err(|_| {
+        ColumnarError::RleDecodeError(format!("{} cannot be safely converted from i128", value))
+    })
+}
+
 impl<'de, T: DeltaRleable> Iterator for DeltaRleIter<'de, T> {
     type Item = Result<T, ColumnarError>;
     fn next(&mut self) -> Option<Self::Item> {
         self.try_next().transpose()
     }
+
+    nth_by_skip_values!();
 }
 
 pub struct BoolRleIter<'de> {
-    de: Deserializer<'de, Cursor<'de>>,
-    last_value: bool,
-    count: usize,
+    decoder: BoolRleDecoder<'de>,
 }
 
 impl<'de> BoolRleIter<'de> {
     pub fn new(bytes: &'de [u8]) -> Self {
         Self {
-            de: Deserializer::from_flavor(Cursor::new(bytes)),
-            last_value: true,
-            count: 0,
+            decoder: BoolRleDecoder::new_strict(bytes),
         }
     }
 
+    /// Skips `n` values by subtracting from `count`.
+    /// Returns the number of the skipped values, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        self.decoder.skip_values(n)
+    }
+
+    /// Like [BoolRleIter::skip_values], also returns the number of the skipped `true`
+    pub(crate) fn skip_values_count_true(
+        &mut self,
+        n: usize,
+    ) -> Result<(usize, usize), ColumnarError> {
+        self.decoder.skip_values_count_true(n)
+    }
+
     pub(crate) fn try_next(&mut self) -> Result<Option<bool>, ColumnarError> {
-        while self.count == 0 {
-            let count = usize::deserialize(&mut self.de);
-            self.count = match count {
-                Err(postcard::Error::DeserializeUnexpectedEnd) => {
-                    if self.count == 0 {
-                        return Ok(None);
-                    } else {
-                        return Err(ColumnarError::RleDecodeError(
-                            "Unexpected BoolRle data".to_string(),
-                        ));
-                    }
+        self.decoder.try_next()
+    }
+
+    /// Takes the rest of the current run, returns its value and the number of the values taken
+    pub fn next_value_run(&mut self) -> Result<Option<(bool, usize)>, ColumnarError> {
+        self.decoder.next_value_run()
+    }
+}
+
+impl<'de> Iterator for BoolRleIter<'de> {
+    type Item = Result<bool, ColumnarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+
+    nth_by_skip_values!();
+}
+
+pub struct DictIter<'de, T> {
+    values: Vec<T>,
+    indices: AnyRleIter<'de, usize>,
+}
+
+impl<'de, T: Dictable> DictIter<'de, T> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let mut de = Deserializer::from_flavor(Cursor::new(bytes));
+        let values = Vec::<T>::deserialize(&mut de)?;
+        Ok(Self {
+            values,
+            indices: AnyRleIter::new(de.finalize()?),
+        })
+    }
+
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        self.indices.skip_values(n)
+    }
+
+    pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        match self.indices.try_next()? {
+            Some(index) => self.values.get(index).cloned().map(Some).ok_or_else(|| {
+                ColumnarError::RleDecodeError(format!(
+                    "dict index {} is out of bounds (dict size {})",
+                    index,
+                    self.values.len()
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<'de, T: Dictable> Iterator for DictIter<'de, T> {
+    type Item = Result<T, ColumnarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+
+    nth_by_skip_values!();
+}
+
+/// The iterator of [NullableColumn](crate::NullableColumn), `I` is the iterator of
+/// the present values.
+pub struct NullableIter<'de, I> {
+    presence: BoolRleIter<'de>,
+    values: I,
+}
+
+impl<'de, I: Deserialize<'de>> NullableIter<'de, I> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let mut de = Deserializer::from_flavor(Cursor::new(bytes));
+        let presence: &'de [u8] = Deserialize::deserialize(&mut de)?;
+        let values = I::deserialize(&mut de)?;
+        Ok(Self {
+            presence: BoolRleIter::new(presence),
+            values,
+        })
+    }
+}
+
+impl<'de, I, T> NullableIter<'de, I>
+where
+    I: Iterator<Item = Result<T, ColumnarError>>,
+{
+    pub(crate) fn try_next(&mut self) -> Result<Option<Option<T>>, ColumnarError> {
+        match self.presence.try_next()? {
+            Some(true) => match self.values.next() {
+                Some(value) => Ok(Some(Some(value?))),
+                None => Err(ColumnarError::ColumnarDecodeError(
+                    "nullable column has fewer values than present flags".into(),
+                )),
+            },
+            Some(false) => Ok(Some(None)),
+            None => Ok(None),
+        }
+    }
+
+    /// Skips `n` values, only the present values are skipped in the iterator of values.
+    /// Returns the number of the skipped values, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        let (skipped, present) = self.presence.skip_values_count_true(n)?;
+        if present > 0 {
+            match self.values.nth(present - 1) {
+                Some(value) => {
+                    value?;
+                }
+                None => {
+                    return Err(ColumnarError::ColumnarDecodeError(
+                        "nullable column has fewer values than present flags".into(),
+                    ))
                 }
-                Err(e) => return Err(ColumnarError::from(e)),
-                Ok(c) => c,
+            }
+        }
+        Ok(skipped)
+    }
+}
+
+impl<'de, I, T> Iterator for NullableIter<'de, I>
+where
+    I: Iterator<Item = Result<T, ColumnarError>>,
+{
+    type Item = Result<Option<T>, ColumnarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+
+    nth_by_skip_values!();
+}
+
+/// The iterator of [ListColumn](crate::ListColumn), `I` is the iterator of the elements
+/// of all the lists.
+pub struct ListIter<'de, I> {
+    lengths: AnyRleIter<'de, usize>,
+    values: I,
+    /// the elements of the last list returned by [ListIter::next_list] that are not consumed
+    pending: usize,
+}
+
+impl<'de, I: Deserialize<'de>> ListIter<'de, I> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let mut de = Deserializer::from_flavor(Cursor::new(bytes));
+        let lengths: &'de [u8] = Deserialize::deserialize(&mut de)?;
+        let values = I::deserialize(&mut de)?;
+        Ok(Self {
+            lengths: AnyRleIter::new(lengths),
+            values,
+            pending: 0,
+        })
+    }
+}
+
+impl<'de, I, T> ListIter<'de, I>
+where
+    I: Iterator<Item = Result<T, ColumnarError>>,
+{
+    /// Returns the elements of the next list as an iterator, without collecting them
+    /// into a `Vec`. The elements that are not consumed are skipped by the next call.
+    pub fn next_list(&mut self) -> Result<Option<ListValues<'_, I>>, ColumnarError> {
+        self.skip_pending()?;
+        let Some(len) = self.lengths.try_next()? else {
+            return Ok(None);
+        };
+        self.pending = len;
+        Ok(Some(ListValues {
+            values: &mut self.values,
+            remaining: &mut self.pending,
+        }))
+    }
+
+    fn skip_pending(&mut self) -> Result<(), ColumnarError> {
+        while self.pending > 0 {
+            self.pending -= 1;
+            match self.values.next() {
+                Some(value) => {
+                    value?;
+                }
+                None => return Err(list_values_end()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Skips `n` lists, the elements of them are skipped at once in the iterator of elements.
+    /// Returns the number of the skipped lists, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        let mut skipped = 0;
+        while skipped < n {
+            let Some(len) = self.lengths.try_next()? else {
+                break;
             };
-            // Prevent bad data from causing oom loops
-            if self.count > MAX_RLE_COUNT {
-                return Err(ColumnarError::RleDecodeError(format!(
-                    "decode Rle count is too large : {}",
-                    self.count
-                )));
+            self.pending += len;
+            skipped += 1;
+        }
+        if self.pending > 0 {
+            let pending = std::mem::take(&mut self.pending);
+            match self.values.nth(pending - 1) {
+                Some(value) => {
+                    value?;
+                }
+                None => return Err(list_values_end()),
             }
-            self.last_value = !self.last_value;
         }
-        self.count -= 1;
-        Ok(Some(self.last_value))
+        Ok(skipped)
+    }
+
+    pub(crate) fn try_next(&mut self) -> Result<Option<Vec<T>>, ColumnarError> {
+        match self.next_list()? {
+            Some(list) => Ok(Some(list.collect::<Result<Vec<_>, _>>()?)),
+            None => Ok(None),
+        }
     }
 }
 
-impl<'de> Iterator for BoolRleIter<'de> {
-    type Item = Result<bool, ColumnarError>;
+fn list_values_end() -> ColumnarError {
+    ColumnarError::ColumnarDecodeError(
+        "list column has fewer values than the sum of lengths".into(),
+    )
+}
+
+impl<'de, I, T> Iterator for ListIter<'de, I>
+where
+    I: Iterator<Item = Result<T, ColumnarError>>,
+{
+    type Item = Result<Vec<T>, ColumnarError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         self.try_next().transpose()
     }
+
+    nth_by_skip_values!();
+}
+
+/// The elements of one list of [ListIter]
+pub struct ListValues<'a, I> {
+    values: &'a mut I,
+    remaining: &'a mut usize,
+}
+
+impl<'a, I, T> Iterator for ListValues<'a, I>
+where
+    I: Iterator<Item = Result<T, ColumnarError>>,
+{
+    type Item = Result<T, ColumnarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if *self.remaining == 0 {
+            return None;
+        }
+        *self.remaining -= 1;
+        Some(self.values.next().unwrap_or_else(|| Err(list_values_end())))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(*self.remaining))
+    }
+}
+
+pub enum AutoIter<'de, T> {
+    Generic {
+        de: Deserializer<'de, Cursor<'de>>,
+        remaining: usize,
+        _ty: PhantomData<T>,
+    },
+    Rle(AnyRleIter<'de, T>),
+    DeltaRle(DeltaRleIter<'de, T>),
+    DeltaOfDelta(DeltaOfDeltaIter<'de, T>),
+}
+
+impl<'de, T: Autoable> AutoIter<'de, T> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let (&tag, payload) = bytes.split_first().ok_or_else(|| {
+            ColumnarError::RleDecodeError("Auto column is missing its strategy tag".to_string())
+        })?;
+        let ans = match StrategyTag::try_from(tag)? {
+            StrategyTag::Generic => {
+                let mut de = Deserializer::from_flavor(Cursor::new(payload));
+                let remaining = usize::deserialize(&mut de)?;
+                AutoIter::Generic {
+                    de,
+                    remaining,
+                    _ty: PhantomData,
+                }
+            }
+            StrategyTag::Rle => AutoIter::Rle(AnyRleIter::new(payload)),
+            StrategyTag::DeltaRle => AutoIter::DeltaRle(DeltaRleIter::new(payload)),
+            StrategyTag::DeltaOfDelta => AutoIter::DeltaOfDelta(DeltaOfDeltaIter {
+                decoder: DeltaOfDeltaDecoder::new(payload)?,
+            }),
+        };
+        Ok(ans)
+    }
+
+    pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        match self {
+            AutoIter::Generic { de, remaining, .. } => {
+                if *remaining == 0 {
+                    return Ok(None);
+                }
+                *remaining -= 1;
+                Ok(Some(T::deserialize(de)?))
+            }
+            AutoIter::Rle(iter) => iter.try_next(),
+            AutoIter::DeltaRle(iter) => iter.try_next(),
+            AutoIter::DeltaOfDelta(iter) => iter.try_next(),
+        }
+    }
+}
+
+impl<'de, T: Autoable> Iterator for AutoIter<'de, T> {
+    type Item = Result<T, ColumnarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self {
+            AutoIter::Generic { .. } => {
+                for _ in 0..n {
+                    if let Err(e) = self.next()? {
+                        return Some(Err(e));
+                    }
+                }
+                self.next()
+            }
+            AutoIter::Rle(iter) => iter.nth(n),
+            AutoIter::DeltaRle(iter) => iter.nth(n),
+            AutoIter::DeltaOfDelta(iter) => iter.nth(n),
+        }
+    }
 }
 
 pub trait TableIter<'de> {
     type Iter: Deserialize<'de>;
 }
 
+/// Takes the next run of the filtered column from the rows
+type NextRun<I, V> = fn(&mut I) -> Result<Option<(V, usize)>, ColumnarError>;
+/// Skips `n` rows of the other columns
+type SkipRows<I> = fn(&mut I, usize) -> Result<(), ColumnarError>;
+/// Takes the next row of the other columns, with the value of the filtered column
+type NextWith<I, V, R> = fn(&mut I, V) -> Option<Result<R, ColumnarError>>;
+
+/// The rows whose column matches a predicate, returned by `filter_by_<field>` of the
+/// `iterable` rows. The predicate is evaluated once per run of the column, and the other
+/// columns skip the rows of the rejected runs without decoding them one by one.
+pub struct FilterRuns<I, V, R, P> {
+    rows: I,
+    predicate: P,
+    /// The value of the accepted run and the number of its rows that are not taken
+    run: Option<(V, usize)>,
+    next_run: NextRun<I, V>,
+    skip_rows: SkipRows<I>,
+    next_with: NextWith<I, V, R>,
+}
+
+impl<I, V, R, P> FilterRuns<I, V, R, P>
+where
+    V: Clone,
+    P: FnMut(&V) -> bool,
+{
+    #[doc(hidden)]
+    pub fn new(
+        rows: I,
+        predicate: P,
+        next_run: NextRun<I, V>,
+        skip_rows: SkipRows<I>,
+        next_with: NextWith<I, V, R>,
+    ) -> Self {
+        Self {
+            rows,
+            predicate,
+            run: None,
+            next_run,
+            skip_rows,
+            next_with,
+        }
+    }
+}
+
+impl<I, V, R, P> Iterator for FilterRuns<I, V, R, P>
+where
+    V: Clone,
+    P: FnMut(&V) -> bool,
+{
+    type Item = Result<R, ColumnarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((value, len)) = &mut self.run {
+                if *len > 0 {
+                    *len -= 1;
+                    let value = value.clone();
+                    return (self.next_with)(&mut self.rows, value);
+                }
+                self.run = None;
+            }
+            match (self.next_run)(&mut self.rows) {
+                Ok(Some((value, len))) => {
+                    if (self.predicate)(&value) {
+                        self.run = Some((value, len));
+                    } else if let Err(e) = (self.skip_rows)(&mut self.rows, len) {
+                        return Some(Err(e));
+                    }
+                }
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! multi_zip {
     ($first:expr $(, $rest:expr)*) => {
@@ -310,6 +784,76 @@ impl<'de, T: DeltaOfDeltable> Deserialize<'de> for DeltaOfDeltaIter<'de, T> {
     }
 }
 
+impl<'de, T: FloatXorable> Deserialize<'de> for FloatXorIter<'de, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = Deserialize::deserialize(deserializer)?;
+        FloatXorIter::new(bytes).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+impl<'de, T: BitPackable> Deserialize<'de> for BitPackIter<'de, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = Deserialize::deserialize(deserializer)?;
+        BitPackIter::new(bytes).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+impl<'de, T: DeltaRleable> Deserialize<'de> for DeltaBitPackIter<'de, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = Deserialize::deserialize(deserializer)?;
+        DeltaBitPackIter::new(bytes).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+impl<'de, I: Deserialize<'de>> Deserialize<'de> for NullableIter<'de, I> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = Deserialize::deserialize(deserializer)?;
+        NullableIter::new(bytes).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+impl<'de, I: Deserialize<'de>> Deserialize<'de> for ListIter<'de, I> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = Deserialize::deserialize(deserializer)?;
+        ListIter::new(bytes).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+impl<'de, T: Autoable> Deserialize<'de> for AutoIter<'de, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = Deserialize::deserialize(deserializer)?;
+        AutoIter::new(bytes).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
+impl<'de, T: Dictable> Deserialize<'de> for DictIter<'de, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let bytes: &'de [u8] = Deserialize::deserialize(deserializer)?;
+        DictIter::new(bytes).map_err(|e| D::Error::custom(e.to_string()))
+    }
+}
+
 impl<'de> Deserialize<'de> for BoolRleIter<'de> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -56,45 +56,105 @@
 //! - `#[columnar]` means that some fields (marked by `#[columnar(type = "vec"|"map")]`) of this structure can be serialized and deserialized by columnar encoding
 //! - `#[columnar(vec, map)]` means the struct can be a row inside `Vec-like` or `Map-like`
 //! - `#[columnar(ser, de)]` means the struct can be serialized or deserialized or both by columnar encoding
+//! - `#[columnar(vec, ser, de)]` on an enum means the enum can be a row inside `Vec-like`, the variant of each row is
+//!   stored in a Rle column and the fields of each variant are stored in their own columns
+//! - `#[columnar(vec, builder)]` generates a `{Row}ColumnsBuilder` that encodes the rows one at a time into the
+//!   same bytes as [ColumnarVec], see [ColumnBuilder]
+//! - `#[columnar(vec, columns)]` generates a struct-of-arrays `{Row}Columns` that has a `Vec` of each field and
+//!   is encoded into the same bytes as [ColumnarVec], with `from_rows`/`to_rows` and a borrowing `row(i)`
+//! - `#[columnar(vec, de, projection)]` lets [from_bytes_projected] decode only the selected fields of the rows and
+//!   fill the others with `Default`
 //!
 //! ## Field Attributes
 //!
 //! - `#[columnar(type = "vec"|"map")]`:
 //!   - vec means the decorated field T is a container, holds Value and satisfies `&T: IntoIter<Item=&Value>` `T: FromIterator<Value>`
 //!   - map means the decorated field T is a container, holds Value and satisfies `&T: IntoIter<Item=(&K, &Value)>` `T: FromIterator<(K, Value)>`
-//! - `#[columnar(strategy = "Rle"|"BoolRle"|"DeltaRle"|"DeltaOfDelta")]`: You can only choose one from
+//! - `#[columnar(strategy = "Rle"|"BoolRle"|"DeltaRle"|"DeltaOfDelta"|"Dict"|"Auto"|"FloatXor"|"BitPack"|"DeltaBitPack")]`: You can only choose one from
 //!   - Rle [crate::strategy::AnyRleEncoder]
 //!   - BoolRle [crate::strategy::BoolRleEncoder]
 //!   - DeltaRle [crate::strategy::DeltaRleEncoder]
 //!   - DeltaOfDelta [crate::strategy::DeltaOfDeltaEncoder]
+//!   - Dict [crate::strategy::DictEncoder]
+//!   - Auto [crate::strategy::AutoEncoder], picks the smallest of Rle, DeltaRle, DeltaOfDelta and the generic encoding at encode time
+//!   - FloatXor [crate::strategy::FloatXorEncoder], for `f32` and `f64`
+//!   - BitPack [crate::strategy::BitPackEncoder]
+//!   - DeltaBitPack [crate::strategy::DeltaBitPackEncoder], takes the same types as DeltaRle
 //! - `#[columnar(original_type="u32")]`: this attribute is used to tell the columnar encoding the original type of the field, which is used when the field is a number
 //! - `#[columnar(skip)]`: the same as the [skip](https://serde.rs/field-attrs.html#skip) attribute in serde
+//! - `#[columnar(nullable)]`: the field is `Option<T>`, its presence is stored by BoolRle and only the present values are encoded by the `strategy`, see [NullableColumn]
+//! - `#[columnar(flatten)]`: the field is a `vec` row struct, its fields are encoded into their own columns nested in the columns of the row, see [FlattenColumn]
+//! - `#[columnar(class = "vec", flatten)]`: the field is `Vec<T>` of a `vec` row struct, the inner rows of all rows share the same nested columns with a length column, see [NestedColumn]
+//! - `#[columnar(list)]`: the field is `Vec<T>`, the lengths of the lists are stored separately and the elements of all rows are concatenated and encoded by the `strategy`, see [ListColumn]
 //!
 
 mod err;
 
 pub use err::ColumnarError;
-use std::ops::DerefMut;
+use std::{io, ops::DerefMut};
 mod column;
 pub use column::{
+    auto::{AutoColumn, Autoable},
+    bit_pack::{BitPackColumn, BitPackable},
     bool_rle::BoolRleColumn,
+    delta_bit_pack::DeltaBitPackColumn,
     delta_of_delta::DeltaOfDeltaColumn,
     delta_rle::{DeltaRleColumn, DeltaRleable},
+    dict::{DictColumn, Dictable},
+    flatten::FlattenColumn,
+    float_xor::{FloatXorColumn, FloatXorable},
+    list::{ListColumn, ListData},
+    nested::NestedColumn,
+    nullable::NullableColumn,
     rle::{RleColumn, Rleable},
-    ColumnAttr, ColumnTrait, GenericColumn,
+    stats::{StatsColumn, StatsColumnData},
+    ColumnAttr, ColumnData, ColumnTrait, GenericColumn,
+};
+mod builder;
+pub use builder::{
+    BuildColumns, ColumnBuilder, ColumnsWriter, GenericBuilder, ListBuilder, NestedBuilder,
+    NullableBuilder, PushColumn,
 };
 mod columnar_internal;
-pub use columnar_internal::{ColumnarDecoder, ColumnarEncoder};
+pub use columnar_internal::{
+    ColumnarDecoder, ColumnarEncoder, ReaderFlavor, WriterFlavor, DEFAULT_WRITER_CAPACITY,
+};
+mod compress;
+pub use compress::{
+    compress, decompress, decompress_with_limit, Compression, MAX_DECOMPRESSED_LEN,
+};
+#[doc(hidden)]
+pub use compress::{Compressed, Decompressed};
+pub mod compute;
 pub mod iterable;
+mod paged;
+#[doc(hidden)]
+pub use paged::PageRows;
+pub use paged::{ColumnarPage, ColumnarPages, PagedColumnarVec};
+mod parallel;
+#[doc(hidden)]
+pub use parallel::{column_scope, ColumnScope, EncodeColumn, EncodedColumn};
+mod projection;
+pub use projection::from_bytes_projected;
+#[doc(hidden)]
+pub use projection::{projected_fields, without_projection, SkipColumn};
 mod row;
 pub use itertools::{izip, Itertools, MultiUnzip};
 pub use row::{KeyRowDe, KeyRowSer, RowDe, RowSer};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+mod stats;
+pub use stats::ColumnStats;
 mod strategy;
 pub use strategy::{
-    AnyRleDecoder, AnyRleEncoder, BoolRleDecoder, BoolRleEncoder, DeltaOfDeltaDecoder,
-    DeltaOfDeltaEncoder, DeltaRleDecoder, DeltaRleEncoder,
+    AnyRleDecoder, AnyRleEncoder, AutoDecoder, AutoEncoder, BitPackDecoder, BitPackEncoder,
+    BoolRleDecoder, BoolRleEncoder, DeltaBitPackDecoder, DeltaBitPackEncoder, DeltaOfDeltaDecoder,
+    DeltaOfDeltaEncoder, DeltaRleDecoder, DeltaRleEncoder, DictDecoder, DictEncoder,
+    FloatXorDecoder, FloatXorEncoder, RleRun, RleRuns, StrategyTag,
 };
+mod unknown;
+pub use unknown::UnknownFields;
+mod view;
+pub use view::{ColumnView, RunStart, RunView};
 mod wrap;
 pub use wrap::{ColumnarMap, ColumnarVec};
 
@@ -131,3 +191,56 @@ pub fn iter_from_bytes<'de, T: iterable::TableIter<'de>>(
     T::Iter::deserialize(decoder.deref_mut())
         .map_err(|e| ColumnarError::SerializeError(e as postcard::Error))
 }
+
+/// Serializes `val` into `writer`, buffering at most [DEFAULT_WRITER_CAPACITY] bytes of
+/// the output before writing them.
+pub fn to_writer<W: io::Write, T: Serialize>(writer: W, val: &T) -> Result<W, ColumnarError> {
+    to_writer_with_capacity(writer, val, DEFAULT_WRITER_CAPACITY)
+}
+
+/// Serializes `val` into `writer`, buffering at most `capacity` bytes of the output before
+/// writing them.
+pub fn to_writer_with_capacity<W: io::Write, T: Serialize>(
+    writer: W,
+    val: &T,
+    capacity: usize,
+) -> Result<W, ColumnarError> {
+    let mut ser = postcard::Serializer {
+        output: WriterFlavor::new(writer, capacity),
+    };
+    if let Err(e) = val.serialize(&mut ser) {
+        return Err(match ser.output.take_error() {
+            Some(e) => ColumnarError::IOError(e),
+            None => ColumnarError::SerializeError(e),
+        });
+    }
+    ser.output.finish()
+}
+
+/// Reads all the bytes of `reader`, then deserializes them like [from_bytes], so the
+/// whole input is held in memory. It does not stream the input.
+pub fn from_reader<R: io::Read, T: DeserializeOwned>(mut reader: R) -> Result<T, ColumnarError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    from_bytes(&bytes)
+}
+
+/// Deserializes from `reader`, only reading the bytes that are needed. The bytes of every
+/// column are read into `buf` and kept there, `buf` is never reused, so it must be large
+/// enough to hold all the columns at once, which is about the whole input. It saves the
+/// allocation of [from_reader], not the memory.
+///
+/// The lengths and varints are read one byte at a time, so an unbuffered `reader` such
+/// as a [std::fs::File] should be wrapped in a [std::io::BufReader].
+pub fn from_reader_with_buffer<'de, R: io::Read + 'de, T: Deserialize<'de>>(
+    reader: R,
+    buf: &'de mut [u8],
+) -> Result<T, ColumnarError> {
+    let mut de = postcard::Deserializer::from_flavor(ReaderFlavor::new(reader, buf));
+    let result = T::deserialize(&mut de);
+    let error = de.finalize()?;
+    match (result, error) {
+        (Err(_), Some(error)) => Err(error),
+        (result, _) => result.map_err(ColumnarError::SerializeError),
+    }
+}
@@ -0,0 +1,372 @@
+//! The paged layout of the rows of a vec-like container.
+//!
+//! The rows are split into pages of a fixed number of rows, and each page is encoded into its
+//! own columns as [ColumnarVec] does. So the state of the strategies such as `DeltaRle` and
+//! `DeltaOfDelta` restarts at every page, and a page can be decoded without the pages before
+//! it. The layout is `PAGED_LAYOUT [(row count, page bytes)]`, which is read by
+//! [ColumnarPages] without decoding any page. The layout is opt-in, the unpaged [ColumnarVec]
+//! is unchanged.
+//!
+//! The marker `PAGED_LAYOUT` is read as the length of the sequence of the columns of an
+//! unpaged [ColumnarVec], which is never that large. So the unpaged bytes written before a
+//! field was paged are still read by [PagedColumnarVec].
+use std::{borrow::Cow, marker::PhantomData};
+
+use itertools::Itertools;
+use serde::{
+    de::{value::SeqAccessDeserializer, DeserializeSeed, Error as DeError, SeqAccess, Visitor},
+    ser::{Error as SerError, SerializeTuple},
+    Deserialize, Serialize,
+};
+
+use crate::{
+    row::{RowDe, RowSer},
+    ColumnarError, ColumnarVec,
+};
+
+/// The marker of the paged layout, see the module docs
+const PAGED_LAYOUT: usize = u32::MAX as usize;
+
+/// The wrapper of `Vec-like` container, which is serialized in pages of `page_rows` rows.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PagedColumnarVec<'c, T, IT>
+where
+    for<'a> &'a IT: IntoIterator<Item = &'a T>,
+    IT: FromIterator<T> + Clone,
+{
+    rows: Cow<'c, IT>,
+    page_rows: usize,
+}
+
+impl<'c, T, IT> PagedColumnarVec<'c, T, IT>
+where
+    IT: FromIterator<T> + Clone,
+    for<'a> &'a IT: IntoIterator<Item = &'a T>,
+{
+    /// `page_rows` is the number of the rows of every page but the last one, which is at least 1
+    pub fn new(vec: &'c IT, page_rows: usize) -> Self {
+        Self {
+            rows: Cow::Borrowed(vec),
+            page_rows: page_rows.max(1),
+        }
+    }
+
+    pub fn into_vec(self) -> IT {
+        self.rows.into_owned()
+    }
+}
+
+/// The rows of a page borrowed from the container, which are encoded without being cloned
+#[doc(hidden)]
+pub struct PageRows<'a, T>(Vec<&'a T>);
+
+impl<'c, 'a, T> IntoIterator for &'c PageRows<'a, T> {
+    type Item = &'c T;
+    type IntoIter = std::iter::Map<std::slice::Iter<'c, &'a T>, fn(&'c &'a T) -> &'c T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // `copied` would yield `&'a T` instead of `&'c T`
+        self.0.iter().map(|row| -> &'c T { row })
+    }
+}
+
+/// The columns of a page, which are serialized as [ColumnarVec] does
+struct PageColumns<'p, 'a, T>(&'p PageRows<'a, T>);
+
+impl<'a, T> Serialize for PageColumns<'_, 'a, T>
+where
+    T: RowSer<PageRows<'a, T>>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        T::serialize_columns(self.0, serializer)
+    }
+}
+
+/// The bytes that are serialized by `serialize_bytes`
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+impl<'c, T, IT> Serialize for PagedColumnarVec<'c, T, IT>
+where
+    T: for<'a> RowSer<PageRows<'a, T>>,
+    IT: FromIterator<T> + Clone,
+    for<'a> &'a IT: IntoIterator<Item = &'a T>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut pages = Vec::new();
+        for page in &(&*self.rows).into_iter().chunks(self.page_rows) {
+            let page = PageRows(page.collect());
+            let bytes = postcard::to_allocvec(&PageColumns(&page)).map_err(S::Error::custom)?;
+            pages.push((page.0.len(), bytes));
+        }
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&PAGED_LAYOUT)?;
+        tuple.serialize_element(
+            &pages
+                .iter()
+                .map(|(rows, bytes)| ColumnarPage { rows: *rows, bytes })
+                .collect::<Vec<_>>(),
+        )?;
+        tuple.end()
+    }
+}
+
+impl<'de, 'c, T, IT> Deserialize<'de> for PagedColumnarVec<'c, T, IT>
+where
+    T: RowDe<'de, Vec<T>> + Clone,
+    IT: FromIterator<T> + Clone,
+    for<'a> &'a IT: IntoIterator<Item = &'a T>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct LayoutVisitor<T>(PhantomData<T>);
+        impl<'de, T: RowDe<'de, Vec<T>>> Visitor<'de> for LayoutVisitor<T> {
+            type Value = Layout<'de, T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("the paged or unpaged columnar rows")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let len = next_marker(&mut seq)?;
+                if len == PAGED_LAYOUT {
+                    return Ok(Layout::Paged(next_pages(&mut seq)?));
+                }
+                // the marker is the number of the columns written by `ColumnarVec`
+                let rows = T::deserialize_columns(SeqAccessDeserializer::new(UnpagedColumns {
+                    seq,
+                    len,
+                }))?;
+                Ok(Layout::Unpaged(rows))
+            }
+        }
+
+        let (rows, page_rows) =
+            match deserializer.deserialize_tuple(usize::MAX, LayoutVisitor(PhantomData))? {
+                Layout::Paged(pages) => {
+                    let mut rows = Vec::new();
+                    for page in pages.iter() {
+                        rows.extend(page.decode::<T>().map_err(D::Error::custom)?);
+                    }
+                    (rows, pages.first().map_or(1, |page| page.rows.max(1)))
+                }
+                Layout::Unpaged(rows) => {
+                    let page_rows = rows.len().max(1);
+                    (rows, page_rows)
+                }
+            };
+        Ok(Self {
+            rows: Cow::Owned(rows.into_iter().collect()),
+            page_rows,
+        })
+    }
+}
+
+/// The rows of a paged field
+enum Layout<'de, T> {
+    /// The pages of the paged layout
+    Paged(Vec<ColumnarPage<'de>>),
+    /// The rows written by [ColumnarVec] before the field was paged
+    Unpaged(Vec<T>),
+}
+
+/// Reads the marker of the paged layout, or the number of the columns of the unpaged layout
+fn next_marker<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<usize, A::Error> {
+    seq.next_element()?
+        .ok_or_else(|| A::Error::custom("DeserializeUnexpectedEnd"))
+}
+
+/// Reads the pages after the marker of the paged layout
+fn next_pages<'de, A: SeqAccess<'de>>(seq: &mut A) -> Result<Vec<ColumnarPage<'de>>, A::Error> {
+    seq.next_element()?
+        .ok_or_else(|| A::Error::custom("DeserializeUnexpectedEnd"))
+}
+
+/// The `len` columns of the unpaged layout, which follow its length read by [next_marker]
+struct UnpagedColumns<A> {
+    seq: A,
+    len: usize,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for UnpagedColumns<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        self.seq.next_element_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+/// A page of the paged layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnarPage<'de> {
+    /// The number of the rows
+    pub rows: usize,
+    /// The columns of the rows, encoded by [ColumnarVec]
+    pub bytes: &'de [u8],
+}
+
+impl<'de> ColumnarPage<'de> {
+    /// Decodes the rows of the page
+    pub fn decode<T>(&self) -> Result<Vec<T>, ColumnarError>
+    where
+        T: RowDe<'de, Vec<T>> + Clone,
+    {
+        let rows = postcard::from_bytes::<ColumnarVec<T, Vec<T>>>(self.bytes)?.into_vec();
+        if rows.len() != self.rows {
+            return Err(ColumnarError::ColumnarDecodeError(
+                format!("the page has {} rows instead of {}", rows.len(), self.rows)
+                    .into_boxed_str(),
+            ));
+        }
+        Ok(rows)
+    }
+}
+
+impl<'de> Serialize for ColumnarPage<'de> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.rows)?;
+        tuple.serialize_element(&Bytes(self.bytes))?;
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ColumnarPage<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let (rows, bytes) = <(usize, &'de [u8])>::deserialize(deserializer)?;
+        Ok(Self { rows, bytes })
+    }
+}
+
+/// The pages of the rows serialized by [PagedColumnarVec], which are decoded on demand.
+/// Every page is independent, so the pages can also be decoded in parallel.
+pub struct ColumnarPages<'de, T> {
+    pages: Vec<ColumnarPage<'de>>,
+    /// The index of the first row of each page, followed by the number of all the rows
+    starts: Vec<usize>,
+    _t: PhantomData<T>,
+}
+
+impl<'de, T> ColumnarPages<'de, T> {
+    fn new(pages: Vec<ColumnarPage<'de>>) -> Result<Self, ColumnarError> {
+        let mut starts = Vec::with_capacity(pages.len() + 1);
+        let mut start: usize = 0;
+        starts.push(start);
+        for page in pages.iter() {
+            start = start.checked_add(page.rows).ok_or_else(|| {
+                ColumnarError::ColumnarDecodeError("the number of the rows overflows".into())
+            })?;
+            starts.push(start);
+        }
+        Ok(Self {
+            pages,
+            starts,
+            _t: PhantomData,
+        })
+    }
+
+    /// The number of the rows
+    pub fn len(&self) -> usize {
+        *self.starts.last().unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The pages, each one can be decoded by [ColumnarPage::decode] on its own
+    pub fn pages(&self) -> &[ColumnarPage<'de>] {
+        &self.pages
+    }
+
+    /// The index of the page that holds row `i` and the index of the row in the page,
+    /// or `None` if it is out of bounds
+    pub fn find_row(&self, i: usize) -> Option<(usize, usize)> {
+        if i >= self.len() {
+            return None;
+        }
+        let page = self.starts.partition_point(|&start| start <= i) - 1;
+        Some((page, i - self.starts[page]))
+    }
+}
+
+impl<'de, T: RowDe<'de, Vec<T>> + Clone> ColumnarPages<'de, T> {
+    /// Decodes the rows of page `i`, or `None` if it is out of bounds
+    pub fn decode_page(&self, i: usize) -> Result<Option<Vec<T>>, ColumnarError> {
+        self.pages.get(i).map(|page| page.decode()).transpose()
+    }
+
+    /// Decodes the page that holds row `i` and returns the row
+    pub fn get(&self, i: usize) -> Result<Option<T>, ColumnarError> {
+        let Some((page, offset)) = self.find_row(i) else {
+            return Ok(None);
+        };
+        Ok(self.pages[page].decode()?.into_iter().nth(offset))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for ColumnarPages<'de, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct PagesVisitor;
+        impl<'de> Visitor<'de> for PagesVisitor {
+            type Value = Vec<ColumnarPage<'de>>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("the pages of columnar rows")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                if next_marker(&mut seq)? != PAGED_LAYOUT {
+                    return Err(A::Error::custom(
+                        "the rows are not paged, they can be read by `PagedColumnarVec`",
+                    ));
+                }
+                next_pages(&mut seq)
+            }
+        }
+        ColumnarPages::new(deserializer.deserialize_tuple(2, PagesVisitor)?)
+            .map_err(D::Error::custom)
+    }
+}
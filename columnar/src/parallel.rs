@@ -0,0 +1,102 @@
+//! Encodes and decodes the columns of the rows marked `parallel` on the rayon thread pool of
+//! the `parallel` feature.
+//!
+//! Each column is encoded into its own bytes on the pool, and the bytes are then written in the
+//! order of the fields, so the output is the same as the sequential one. The projection of the
+//! caller is passed to the threads that decode the columns. Without the feature, the tasks run
+//! one after another on the current thread, so the derived code is the same either way.
+use serde::{Serialize, Serializer};
+
+#[cfg(feature = "parallel")]
+use crate::projection::{current_projection, with_projection};
+use crate::{
+    ColumnData, ColumnTrait, ColumnarError, FlattenColumn, ListColumn, ListData, NestedColumn,
+    RowSer,
+};
+
+/// The scope that runs the tasks of the columns
+#[doc(hidden)]
+pub struct ColumnScope<'a, 'scope> {
+    #[cfg(feature = "parallel")]
+    scope: &'a rayon::Scope<'scope>,
+    #[cfg(feature = "parallel")]
+    projection: Option<Vec<String>>,
+    #[cfg(not(feature = "parallel"))]
+    _scope: std::marker::PhantomData<(&'a (), &'scope ())>,
+}
+
+impl<'scope> ColumnScope<'_, 'scope> {
+    pub fn spawn<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'scope,
+    {
+        #[cfg(feature = "parallel")]
+        {
+            let projection = self.projection.clone();
+            self.scope.spawn(move |_| with_projection(projection, f));
+        }
+        #[cfg(not(feature = "parallel"))]
+        f();
+    }
+}
+
+/// Runs the tasks spawned by `f` on the pool, and returns when all of them are done
+#[doc(hidden)]
+pub fn column_scope<'scope, F>(f: F)
+where
+    F: for<'a> FnOnce(&ColumnScope<'a, 'scope>) + Send,
+{
+    #[cfg(feature = "parallel")]
+    {
+        let projection = current_projection();
+        rayon::scope(|scope| f(&ColumnScope { scope, projection }))
+    }
+    #[cfg(not(feature = "parallel"))]
+    f(&ColumnScope {
+        _scope: std::marker::PhantomData,
+    })
+}
+
+/// The column whose serialized form is its encoded bytes
+#[doc(hidden)]
+pub trait EncodeColumn {
+    fn encode_column(&self) -> Result<Vec<u8>, ColumnarError>;
+}
+
+impl<C: ColumnTrait> EncodeColumn for C {
+    fn encode_column(&self) -> Result<Vec<u8>, ColumnarError> {
+        self.encode()
+    }
+}
+
+impl<'c, T> EncodeColumn for NestedColumn<&'c Vec<T>>
+where
+    T: RowSer<FlattenColumn<&'c T>>,
+{
+    fn encode_column(&self) -> Result<Vec<u8>, ColumnarError> {
+        self.encode()
+    }
+}
+
+impl<'c, C, T> EncodeColumn for ListColumn<C, &'c Vec<T>>
+where
+    C: ColumnData,
+    &'c Vec<T>: ListData<C::Item>,
+{
+    fn encode_column(&self) -> Result<Vec<u8>, ColumnarError> {
+        self.encode_lists()
+    }
+}
+
+/// The bytes of an encoded column, which are serialized as the column is
+#[doc(hidden)]
+pub struct EncodedColumn<'a>(pub &'a [u8]);
+
+impl Serialize for EncodedColumn<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
@@ -0,0 +1,111 @@
+//! Column projection, decodes only the selected fields of the rows.
+//!
+//! The columns are length-prefixed bytes, so the columns of the fields that are not
+//! selected are skipped without being decoded, and the fields are filled with their
+//! default values. Only the row structs marked by `#[columnar(vec, de, projection)]` are
+//! projected. The projection applies to the outermost rows being decoded, a selected
+//! `flatten` or `class` field is decoded in full.
+use std::cell::RefCell;
+
+use serde::{de::Visitor, Deserialize};
+
+use crate::{from_bytes, ColumnarError};
+
+thread_local! {
+    static PROJECTION: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous projection when dropped
+struct ProjectionGuard(Option<Vec<String>>);
+
+impl ProjectionGuard {
+    fn set(projection: Option<Vec<String>>) -> Self {
+        Self(PROJECTION.with(|p| p.replace(projection)))
+    }
+}
+
+impl Drop for ProjectionGuard {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        PROJECTION.with(|p| *p.borrow_mut() = previous);
+    }
+}
+
+/// Like [from_bytes], but only the columns of `fields` of the projected rows are decoded,
+/// the other fields are filled with their default values.
+pub fn from_bytes_projected<'de, 'a: 'de, T: Deserialize<'de>>(
+    bytes: &'a [u8],
+    fields: &[&str],
+) -> Result<T, ColumnarError> {
+    let _guard = ProjectionGuard::set(Some(fields.iter().map(|f| f.to_string()).collect()));
+    from_bytes(bytes)
+}
+
+/// Whether each field is selected by the current projection, all the fields are selected
+/// if there is no projection. The number of rows is only known from the fields that are
+/// not `optional`, so if none of them is selected, the first of them is still decoded.
+#[doc(hidden)]
+pub fn projected_fields<const N: usize>(fields: [&str; N], optional: [bool; N]) -> [bool; N] {
+    PROJECTION.with(|p| match &*p.borrow() {
+        None => [true; N],
+        Some(projection) => {
+            let mut ans = fields.map(|f| projection.iter().any(|p| p == f));
+            let counted = ans
+                .iter()
+                .zip(optional)
+                .any(|(&selected, optional)| selected && !optional);
+            if !counted {
+                if let Some(fallback) = optional.iter().position(|optional| !optional) {
+                    ans[fallback] = true;
+                }
+            }
+            ans
+        }
+    })
+}
+
+/// Runs `f` without the projection, which decodes the inner rows of a selected field in full
+#[doc(hidden)]
+pub fn without_projection<R>(f: impl FnOnce() -> R) -> R {
+    with_projection(None, f)
+}
+
+/// The projection of the current thread
+#[cfg(feature = "parallel")]
+pub(crate) fn current_projection() -> Option<Vec<String>> {
+    PROJECTION.with(|p| p.borrow().clone())
+}
+
+/// Runs `f` with `projection`, the previous projection is restored after it
+pub(crate) fn with_projection<R>(projection: Option<Vec<String>>, f: impl FnOnce() -> R) -> R {
+    let _guard = ProjectionGuard::set(projection);
+    f()
+}
+
+/// The bytes of a column that is skipped without being decoded
+#[doc(hidden)]
+pub struct SkipColumn;
+
+impl<'de> Deserialize<'de> for SkipColumn {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct SkipVisitor;
+        impl<'de> Visitor<'de> for SkipVisitor {
+            type Value = SkipColumn;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("column bytes")
+            }
+
+            fn visit_bytes<E>(self, _v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(SkipColumn)
+            }
+        }
+        deserializer.deserialize_bytes(SkipVisitor)
+    }
+}
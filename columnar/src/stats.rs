@@ -0,0 +1,129 @@
+//! The zone map of a column, a small header of the row count, the null count and the range of
+//! the values, which is written before the encoded bytes by `finish_with_stats` of the
+//! encoders, so that a block of rows can be pruned without decoding its columns.
+use std::ops::{Bound, RangeBounds};
+
+use serde::{
+    de::{Error as DeError, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+
+use crate::{ColumnarError, SkipColumn};
+
+/// The version of the header, which is the first byte of the bytes with stats
+const STATS_VERSION: u8 = 1;
+
+/// The statistics of the values of a column
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnStats {
+    /// The number of the rows
+    pub row_count: u64,
+    /// The number of the rows whose value is null, which is only known by the caller of the
+    /// encoder, e.g. the presence of a nullable column
+    pub null_count: u64,
+    /// The smallest value, `None` if there is no value or it is not numeric
+    pub min: Option<i128>,
+    /// The largest value, `None` if there is no value or it is not numeric
+    pub max: Option<i128>,
+}
+
+impl ColumnStats {
+    /// Adds `n` rows of `value`
+    pub(crate) fn push(&mut self, value: i128, n: usize) {
+        if n == 0 {
+            return;
+        }
+        self.row_count += n as u64;
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+    }
+
+    /// The error of `finish_with_stats` of an encoder that does not track the stats
+    pub(crate) fn untracked() -> ColumnarError {
+        ColumnarError::RleEncodeError(
+            "the encoder does not track the stats, it should be created by `with_stats`"
+                .to_string(),
+        )
+    }
+
+    /// Reads the header of the bytes written by `finish_with_stats`, the payload is not decoded
+    pub fn read(bytes: &[u8]) -> Result<Self, ColumnarError> {
+        Ok(Self::split(bytes)?.0)
+    }
+
+    /// Splits the bytes written by `finish_with_stats` into the stats and the payload, which is
+    /// decoded by the decoder of the encoder
+    pub fn split(bytes: &[u8]) -> Result<(Self, &[u8]), ColumnarError> {
+        match bytes.split_first() {
+            Some((&STATS_VERSION, rest)) => Ok(postcard::take_from_bytes(rest)?),
+            Some((version, _)) => Err(ColumnarError::ColumnarDecodeError(
+                format!("unknown stats version {}", version).into_boxed_str(),
+            )),
+            None => Err(ColumnarError::ColumnarDecodeError(
+                "missing stats header".into(),
+            )),
+        }
+    }
+
+    /// Reads the header of the column at `column` of the bytes of the rows encoded by
+    /// `ColumnarVec`, whose field is marked by `#[columnar(stats)]`. `column` is the position
+    /// of the field among the fields that are not `skip` or `optional`, and the fields before
+    /// it must not be `flatten` without `class`, whose columns cannot be skipped. No column
+    /// is decoded.
+    pub fn read_column(bytes: &[u8], column: usize) -> Result<Self, ColumnarError> {
+        struct ColumnVisitor(usize);
+        impl<'de> Visitor<'de> for ColumnVisitor {
+            type Value = ColumnStats;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("the columns of the rows")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                for _ in 0..self.0 {
+                    seq.next_element::<SkipColumn>()?
+                        .ok_or_else(|| A::Error::custom("the column is out of bounds"))?;
+                }
+                let bytes: &[u8] = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::custom("the column is out of bounds"))?;
+                ColumnStats::read(bytes).map_err(A::Error::custom)
+            }
+        }
+        let mut deserializer = postcard::Deserializer::from_bytes(bytes);
+        Ok((&mut deserializer).deserialize_seq(ColumnVisitor(column))?)
+    }
+
+    /// Writes the header followed by `payload`
+    pub fn write(&self, payload: &[u8]) -> Result<Vec<u8>, ColumnarError> {
+        let mut bytes = vec![STATS_VERSION];
+        bytes = postcard::to_extend(self, bytes)?;
+        bytes.extend_from_slice(payload);
+        Ok(bytes)
+    }
+
+    /// Whether some value of the column may be in `range`. It is `true` if the range of the
+    /// values is unknown, and `false` if all the rows are null.
+    pub fn may_contain(&self, range: impl RangeBounds<i128>) -> bool {
+        if self.row_count == self.null_count {
+            return false;
+        }
+        let (Some(min), Some(max)) = (self.min, self.max) else {
+            return true;
+        };
+        let above_start = match range.start_bound() {
+            Bound::Included(&start) => max >= start,
+            Bound::Excluded(&start) => max > start,
+            Bound::Unbounded => true,
+        };
+        let below_end = match range.end_bound() {
+            Bound::Included(&end) => min <= end,
+            Bound::Excluded(&end) => min < end,
+            Bound::Unbounded => true,
+        };
+        above_start && below_end
+    }
+}
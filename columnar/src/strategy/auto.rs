@@ -0,0 +1,261 @@
+use std::{marker::PhantomData, ops::DerefMut};
+
+use serde::Deserialize;
+
+use crate::{
+    column::auto::Autoable,
+    columnar_internal::{ColumnarDecoder, ColumnarEncoder},
+    ColumnarError,
+};
+
+use super::{
+    AnyRleDecoder, AnyRleEncoder, DeltaOfDeltaDecoder, DeltaOfDeltaEncoder, DeltaRleDecoder,
+    DeltaRleEncoder,
+};
+
+/// The one-byte tag written before the payload of an [AutoEncoder] column,
+/// telling the decoder which strategy the payload was encoded with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum StrategyTag {
+    /// The values serialized one after another, the same as `GenericColumn`
+    Generic = 0,
+    Rle = 1,
+    DeltaRle = 2,
+    DeltaOfDelta = 3,
+}
+
+impl TryFrom<u8> for StrategyTag {
+    type Error = ColumnarError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(StrategyTag::Generic),
+            1 => Ok(StrategyTag::Rle),
+            2 => Ok(StrategyTag::DeltaRle),
+            3 => Ok(StrategyTag::DeltaOfDelta),
+            _ => Err(ColumnarError::InvalidStrategy(value)),
+        }
+    }
+}
+
+/// Picks the strategy with the smallest output at encode time.
+///
+/// All the values are buffered until [AutoEncoder::finish], then encoded by
+/// [AnyRleEncoder], [DeltaRleEncoder], [DeltaOfDeltaEncoder] and the generic encoding.
+/// The smallest result is written after its [StrategyTag].
+pub struct AutoEncoder<T> {
+    data: Vec<T>,
+}
+
+impl<T> AutoEncoder<T>
+where
+    T: Autoable,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn append(&mut self, value: T) -> Result<(), ColumnarError> {
+        self.data.push(value);
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        let mut best = (StrategyTag::Generic, self.encode_generic()?);
+        let candidates = [
+            (StrategyTag::Rle, self.encode_rle()),
+            (StrategyTag::DeltaRle, self.encode_delta_rle()),
+            (StrategyTag::DeltaOfDelta, self.encode_delta_of_delta()),
+        ];
+        for (tag, bytes) in candidates {
+            // a strategy may not be able to hold the values, e.g. the delta overflows
+            if let Ok(bytes) = bytes {
+                if bytes.len() < best.1.len() {
+                    best = (tag, bytes);
+                }
+            }
+        }
+
+        let (tag, payload) = best;
+        let mut bytes = Vec::with_capacity(payload.len() + 1);
+        bytes.push(tag as u8);
+        bytes.extend(payload);
+        Ok(bytes)
+    }
+
+    fn encode_generic(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut encoder = ColumnarEncoder::new();
+        serde::Serialize::serialize(&self.data, encoder.deref_mut())?;
+        Ok(encoder.into_bytes())
+    }
+
+    fn encode_rle(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut encoder = AnyRleEncoder::<T>::new();
+        for value in self.data.iter() {
+            encoder.append(value)?;
+        }
+        encoder.finish()
+    }
+
+    fn encode_delta_rle(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut encoder = DeltaRleEncoder::new();
+        for &value in self.data.iter() {
+            encoder.append(value)?;
+        }
+        encoder.finish()
+    }
+
+    fn encode_delta_of_delta(&self) -> Result<Vec<u8>, ColumnarError> {
+        let mut encoder = DeltaOfDeltaEncoder::new();
+        for &value in self.data.iter() {
+            encoder.append(value.try_into().map_err(|_| {
+                ColumnarError::RleEncodeError(format!("{:?} cannot be converted to i64", value))
+            })?)?;
+        }
+        encoder.finish()
+    }
+}
+
+impl<T> Default for AutoEncoder<T> {
+    fn default() -> Self {
+        Self { data: Vec::new() }
+    }
+}
+
+pub struct AutoDecoder<'de, T> {
+    inner: AutoDecoderInner<'de, T>,
+}
+
+enum AutoDecoderInner<'de, T> {
+    Generic {
+        de: ColumnarDecoder<'de>,
+        remaining: usize,
+        _t: PhantomData<T>,
+    },
+    Rle(AnyRleDecoder<'de, T>),
+    DeltaRle(DeltaRleDecoder<'de, T>),
+    DeltaOfDelta(DeltaOfDeltaDecoder<'de, T>),
+}
+
+impl<'de, T> AutoDecoder<'de, T>
+where
+    T: Autoable,
+{
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let (&tag, payload) = bytes.split_first().ok_or_else(|| {
+            ColumnarError::RleDecodeError("Auto column is missing its strategy tag".to_string())
+        })?;
+        let inner = match StrategyTag::try_from(tag)? {
+            StrategyTag::Generic => {
+                let mut de = ColumnarDecoder::new(payload);
+                let remaining = usize::deserialize(de.deref_mut())?;
+                AutoDecoderInner::Generic {
+                    de,
+                    remaining,
+                    _t: PhantomData,
+                }
+            }
+            StrategyTag::Rle => AutoDecoderInner::Rle(AnyRleDecoder::new(payload)),
+            StrategyTag::DeltaRle => AutoDecoderInner::DeltaRle(DeltaRleDecoder::new(payload)),
+            StrategyTag::DeltaOfDelta => {
+                AutoDecoderInner::DeltaOfDelta(DeltaOfDeltaDecoder::new(payload)?)
+            }
+        };
+        Ok(Self { inner })
+    }
+
+    pub fn decode(&mut self) -> Result<Vec<T>, ColumnarError> {
+        let mut values = Vec::new();
+        while let Some(value) = self.try_next()? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    pub fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        match &mut self.inner {
+            AutoDecoderInner::Generic { de, remaining, .. } => {
+                if *remaining == 0 {
+                    return Ok(None);
+                }
+                *remaining -= 1;
+                Ok(Some(T::deserialize(de.deref_mut())?))
+            }
+            AutoDecoderInner::Rle(d) => d.try_next(),
+            AutoDecoderInner::DeltaRle(d) => d.next().transpose(),
+            AutoDecoderInner::DeltaOfDelta(d) => d.try_next(),
+        }
+    }
+}
+
+impl<'de, T: Autoable> Iterator for AutoDecoder<'de, T> {
+    type Item = Result<T, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn encode(data: &[i64]) -> Vec<u8> {
+        let mut encoder = AutoEncoder::new();
+        for &v in data {
+            encoder.append(v).unwrap();
+        }
+        encoder.finish().unwrap()
+    }
+
+    fn decode(bytes: &[u8]) -> Vec<i64> {
+        AutoDecoder::new(bytes).unwrap().decode().unwrap()
+    }
+
+    #[test]
+    fn test_auto_picks_smallest() {
+        let same = vec![7; 100];
+        let bytes = encode(&same);
+        assert_eq!(bytes[0], StrategyTag::Rle as u8);
+        assert_eq!(decode(&bytes), same);
+
+        let increasing: Vec<i64> = (0..100).collect();
+        let bytes = encode(&increasing);
+        assert_eq!(bytes[0], StrategyTag::DeltaRle as u8);
+        assert_eq!(decode(&bytes), increasing);
+
+        let timestamps: Vec<i64> = (0..100).map(|i| 1_700_000_000 + i * 1000 + i % 3).collect();
+        let bytes = encode(&timestamps);
+        assert_eq!(bytes[0], StrategyTag::DeltaOfDelta as u8);
+        assert_eq!(decode(&bytes), timestamps);
+
+        let random = vec![3, -100, 42, 7, 1 << 40];
+        let bytes = encode(&random);
+        assert_eq!(bytes[0], StrategyTag::Generic as u8);
+        assert_eq!(decode(&bytes), random);
+
+        let bytes = encode(&[]);
+        assert_eq!(decode(&bytes), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn test_auto_skips_overflowing_strategy() {
+        let data = vec![u64::MAX, 0, u64::MAX, 0];
+        let mut encoder = AutoEncoder::new();
+        for &v in &data {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        assert_ne!(bytes[0], StrategyTag::DeltaOfDelta as u8);
+        let decoded: Vec<u64> = AutoDecoder::new(&bytes).unwrap().decode().unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_auto_invalid_tag() {
+        assert!(matches!(
+            AutoDecoder::<u32>::new(&[9, 0]),
+            Err(ColumnarError::InvalidStrategy(9))
+        ));
+    }
+}
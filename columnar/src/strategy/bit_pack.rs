@@ -0,0 +1,284 @@
+use std::marker::PhantomData;
+
+use crate::{column::bit_pack::BitPackable, ColumnarError};
+
+use super::bits::{BitReader, BitWriter};
+
+/// The number of values in one block of [BitPackEncoder]
+pub(crate) const BIT_PACK_BLOCK_SIZE: usize = 128;
+
+/// Frame-of-reference bit-packing.
+///
+/// The values are split into blocks of 128 values. For each block the
+/// minimum and the bit width of the largest `value - min` are written, followed by
+/// all the `value - min` packed at that bit width.
+///
+/// The encoded bytes are the number of values, followed by the blocks:
+///
+/// `len: varint` (`min: zigzag varint` `bit_width: u8` `residuals: ceil(n * bit_width / 8) bytes`)*
+#[derive(Debug)]
+pub struct BitPackEncoder<T> {
+    len: usize,
+    block: Vec<i128>,
+    bytes: Vec<u8>,
+    _t: PhantomData<T>,
+}
+
+impl<T> Default for BitPackEncoder<T> {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            block: Vec::with_capacity(BIT_PACK_BLOCK_SIZE),
+            bytes: Vec::new(),
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T: BitPackable> BitPackEncoder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn append(&mut self, value: T) -> Result<(), ColumnarError> {
+        let value: i128 = value.try_into().map_err(|_| {
+            ColumnarError::RleEncodeError(format!("{:?} cannot be converted to i128", value))
+        })?;
+        self.block.push(value);
+        self.len += 1;
+        if self.block.len() == BIT_PACK_BLOCK_SIZE {
+            self.flush_block()?;
+        }
+        Ok(())
+    }
+
+    fn flush_block(&mut self) -> Result<(), ColumnarError> {
+        let block = std::mem::take(&mut self.block);
+        pack_block(&block, &mut self.bytes)?;
+        self.block = block;
+        self.block.clear();
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<Vec<u8>, ColumnarError> {
+        if !self.block.is_empty() {
+            self.flush_block()?;
+        }
+        let mut bytes = postcard::to_allocvec(&self.len)?;
+        bytes.extend(self.bytes);
+        Ok(bytes)
+    }
+}
+
+/// Writes `min`, the bit width and the packed `value - min` of the block
+pub(crate) fn pack_block(block: &[i128], bytes: &mut Vec<u8>) -> Result<(), ColumnarError> {
+    let min = block.iter().copied().min().unwrap_or(0);
+    let max = block.iter().copied().max().unwrap_or(0);
+    let range: u64 = max
+        .checked_sub(min)
+        .and_then(|r| r.try_into().ok())
+        .ok_or_else(|| {
+            ColumnarError::RleEncodeError(format!(
+                "the range of the block [{}, {}] overflows 64 bits",
+                min, max
+            ))
+        })?;
+    let width = (64 - range.leading_zeros()) as u8;
+    bytes.extend(postcard::to_allocvec(&min)?);
+    bytes.push(width);
+    let mut bits = BitWriter::new();
+    for &value in block {
+        bits.write_bits((value - min) as u64, width);
+    }
+    bits.finish_without_len_into(bytes);
+    Ok(())
+}
+
+/// Reads one block written by [pack_block]
+pub(crate) struct PackedBlock<'de> {
+    min: i128,
+    width: u8,
+    bits: BitReader<'de>,
+}
+
+impl<'de> PackedBlock<'de> {
+    /// Returns the block and the bytes after it
+    pub fn new(bytes: &'de [u8], len: usize) -> Result<(Self, &'de [u8]), ColumnarError> {
+        let (min, bytes): (i128, _) = postcard::take_from_bytes(bytes)?;
+        let (&width, bytes) = bytes.split_first().ok_or_else(|| {
+            ColumnarError::RleDecodeError("BitPack block is missing its bit width".to_string())
+        })?;
+        if width > 64 {
+            return Err(ColumnarError::RleDecodeError(format!(
+                "BitPack bit width {} is larger than 64",
+                width
+            )));
+        }
+        let bit_len = len * width as usize;
+        let bits = BitReader::with_bit_len(bytes, bit_len).ok_or_else(|| {
+            ColumnarError::RleDecodeError("BitPack block ends unexpectedly".to_string())
+        })?;
+        Ok((Self { min, width, bits }, &bytes[bit_len.div_ceil(8)..]))
+    }
+
+    pub fn next_value(&mut self) -> Result<i128, ColumnarError> {
+        let residual = self.bits.read_bits(self.width).ok_or_else(|| {
+            ColumnarError::RleDecodeError("BitPack block ends unexpectedly".to_string())
+        })?;
+        self.min.checked_add(residual as i128).ok_or_else(|| {
+            ColumnarError::RleDecodeError("BitPack value overflows i128".to_string())
+        })
+    }
+}
+
+pub struct BitPackDecoder<'de, T> {
+    bytes: &'de [u8],
+    remaining: usize,
+    block: Option<PackedBlock<'de>>,
+    block_remaining: usize,
+    _t: PhantomData<T>,
+}
+
+impl<'de, T: BitPackable> BitPackDecoder<'de, T> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let (remaining, bytes) = postcard::take_from_bytes(bytes)?;
+        Ok(Self {
+            bytes,
+            remaining,
+            block: None,
+            block_remaining: 0,
+            _t: PhantomData,
+        })
+    }
+
+    pub fn decode(&mut self) -> Result<Vec<T>, ColumnarError> {
+        let mut values = Vec::with_capacity(self.remaining.min(BIT_PACK_BLOCK_SIZE));
+        while let Some(value) = self.try_next()? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    pub fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        if self.block_remaining == 0 {
+            let len = self.remaining.min(BIT_PACK_BLOCK_SIZE);
+            let (block, rest) = PackedBlock::new(self.bytes, len)?;
+            self.block = Some(block);
+            self.block_remaining = len;
+            self.bytes = rest;
+        }
+        let value = self.block.as_mut().unwrap().next_value()?;
+        self.block_remaining -= 1;
+        self.remaining -= 1;
+        Ok(Some(T::try_from(value).map_err(|_| {
+            ColumnarError::RleDecodeError(format!("{} cannot be safely converted from i128", value))
+        })?))
+    }
+
+    pub fn finalize(self) -> Result<&'de [u8], ColumnarError> {
+        if self.remaining > 0 {
+            return Err(ColumnarError::RleDecodeError(format!(
+                "{} values of BitPack are not decoded",
+                self.remaining
+            )));
+        }
+        Ok(self.bytes)
+    }
+
+    pub fn take_n_finalize(mut self, n: usize) -> Result<(Vec<T>, &'de [u8]), ColumnarError> {
+        let mut ans = Vec::with_capacity(n);
+        for _ in 0..n {
+            if let Some(v) = self.try_next()? {
+                ans.push(v);
+            } else {
+                return Err(ColumnarError::RleDecodeError(format!(
+                    "The elements of decoder is less than n ({})",
+                    n
+                )));
+            }
+        }
+        Ok((ans, self.finalize()?))
+    }
+}
+
+impl<'de, T: BitPackable> Iterator for BitPackDecoder<'de, T> {
+    type Item = Result<T, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn roundtrip<T: BitPackable + PartialEq>(data: &[T]) -> Vec<u8> {
+        let mut encoder = BitPackEncoder::new();
+        for &v in data {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        let decoded = BitPackDecoder::<T>::new(&bytes).unwrap().decode().unwrap();
+        assert_eq!(decoded, data);
+        bytes
+    }
+
+    #[test]
+    fn test_bit_pack() {
+        roundtrip::<u8>(&[]);
+        roundtrip(&[7u32]);
+        roundtrip(&[5u8; 300]);
+        roundtrip(&[i64::MIN, 0, i64::MAX]);
+        roundtrip(&[u64::MAX, 0, 1, u64::MAX - 1]);
+        roundtrip(
+            &(0..1000)
+                .map(|i| -500 + (i * 37) % 101)
+                .collect::<Vec<i32>>(),
+        );
+    }
+
+    #[test]
+    fn test_bit_pack_size() {
+        // 3 bits per value
+        let data = (0..256).map(|i| (i * 7 % 8) as u8).collect::<Vec<_>>();
+        let bytes = roundtrip(&data);
+        assert_eq!(bytes.len(), 2 + 2 * (1 + 1 + 128 * 3 / 8));
+
+        // the block only holds 0, so no bit is needed
+        let bytes = roundtrip(&[0u16; 100]);
+        assert_eq!(bytes.len(), 1 + 1 + 1);
+    }
+
+    #[test]
+    fn test_bit_pack_range_overflow() {
+        let mut encoder = BitPackEncoder::new();
+        encoder.append(i128::MIN).unwrap();
+        encoder.append(i128::MAX).unwrap();
+        assert!(encoder.finish().is_err());
+    }
+
+    #[test]
+    fn test_bit_pack_truncated() {
+        let mut encoder = BitPackEncoder::new();
+        for i in 0..10u32 {
+            encoder.append(i * 1000).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        let mut decoder = BitPackDecoder::<u32>::new(&bytes[..bytes.len() - 1]).unwrap();
+        assert!(decoder.decode().is_err());
+    }
+
+    #[test]
+    fn test_bit_pack_corrupt_min() {
+        let mut bytes = postcard::to_allocvec(&1usize).unwrap();
+        bytes.extend(postcard::to_allocvec(&i128::MAX).unwrap());
+        // width 1 and residual 1
+        bytes.extend([1, 0b1000_0000]);
+        let mut decoder = BitPackDecoder::<u64>::new(&bytes).unwrap();
+        let err = decoder.decode().unwrap_err();
+        assert!(err.to_string().contains("overflows i128"), "{}", err);
+    }
+}
@@ -0,0 +1,246 @@
+/// Writes values bit by bit, most significant bit first.
+///
+/// The output is one byte with the number of bits used in the last byte
+/// (`8` if it is full), followed by the bytes.
+#[derive(Debug)]
+pub(crate) struct BitWriter {
+    bits: Vec<u64>,
+    last_used_bit: u8,
+}
+
+impl Default for BitWriter {
+    fn default() -> Self {
+        Self {
+            bits: vec![0u64],
+            last_used_bit: 0,
+        }
+    }
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the lowest `count` bits of `value`, the other bits of `value` must be 0.
+    pub fn write_bits(&mut self, value: u64, count: u8) {
+        if count == 0 {
+            return;
+        }
+        if self.last_used_bit == 64 {
+            self.bits.push(value << (64 - count));
+            self.last_used_bit = count;
+        } else {
+            let remaining_bits = 64 - self.last_used_bit;
+            if count > remaining_bits {
+                let bits_latter = count - remaining_bits;
+                let former = self.bits.last_mut().unwrap();
+                *former ^= value >> bits_latter;
+                self.bits.push(value << (64 - bits_latter));
+                self.last_used_bit = bits_latter;
+            } else {
+                let last = self.bits.last_mut().unwrap();
+                *last ^= value << (remaining_bits - count);
+                self.last_used_bit += count;
+            }
+        }
+    }
+
+    pub fn finish_into(self, bytes: &mut Vec<u8>) {
+        bytes.reserve(self.bits.len() * 8 + 1);
+        bytes.push(
+            if self.last_used_bit > 0 && self.last_used_bit.is_multiple_of(8) {
+                8
+            } else {
+                self.last_used_bit % 8
+            },
+        );
+        self.finish_without_len_into(bytes);
+    }
+
+    /// Writes only the bytes, the reader must know the number of bits by other means,
+    /// see [BitReader::with_bit_len].
+    pub fn finish_without_len_into(self, bytes: &mut Vec<u8>) {
+        let used = self.last_used_bit.div_ceil(8);
+        for bits in &self.bits[..self.bits.len() - 1] {
+            bytes.extend(bits.to_be_bytes());
+        }
+        bytes.extend(&self.bits.last().unwrap().to_be_bytes()[..used as usize]);
+    }
+}
+
+/// Reads the bits written by [BitWriter].
+pub(crate) struct BitReader<'de> {
+    bits: &'de [u8],
+    index: usize,
+    current_bits_index: u8,
+    last_used_bit: u8,
+}
+
+impl<'de> BitReader<'de> {
+    /// Returns `None` if `bytes` doesn't start with the used-bits byte.
+    pub fn new(bytes: &'de [u8]) -> Option<Self> {
+        let (&last_used_bit, bits) = bytes.split_first()?;
+        Some(Self {
+            bits,
+            index: 0,
+            current_bits_index: 0,
+            last_used_bit,
+        })
+    }
+
+    /// Reads the first `bit_len` bits of `bytes`, which is written by
+    /// [BitWriter::finish_without_len_into]. Returns `None` if `bytes` is too short.
+    pub fn with_bit_len(bytes: &'de [u8], bit_len: usize) -> Option<Self> {
+        let len = bit_len.div_ceil(8);
+        if bytes.len() < len {
+            return None;
+        }
+        Some(Self {
+            bits: &bytes[..len],
+            index: 0,
+            current_bits_index: 0,
+            last_used_bit: (bit_len + 8 - len * 8) as u8,
+        })
+    }
+
+    /// Returns `None` if there are less than `count` bits left.
+    pub fn read_bits(&mut self, count: u8) -> Option<u64> {
+        if count == 0 {
+            return Some(0);
+        }
+        if self.index >= self.bits.len() {
+            return None;
+        }
+
+        let total_bits = (self.bits.len() - 1) * 8 + self.last_used_bit as usize;
+        let read_bits = self.index * 8 + self.current_bits_index as usize;
+        let remaining_bits = total_bits.saturating_sub(read_bits);
+
+        if remaining_bits < count as usize {
+            return None;
+        }
+
+        let current_byte_remaining = 8 - self.current_bits_index;
+        let ans = if count <= current_byte_remaining {
+            let current_index = self.index;
+            self.current_bits_index += count;
+            if self.current_bits_index == 8 {
+                self.index += 1;
+                self.current_bits_index = 0;
+            }
+            let mask = u8::MAX >> (8 - count);
+            let current_byte = self.bits[current_index];
+            let after_shift = current_byte >> (current_byte_remaining - count);
+            let ans = after_shift & mask;
+            ans as u64
+        } else {
+            let mut ans = (self.bits[self.index] & u8::MAX >> (8 - current_byte_remaining)) as u64;
+            self.index += 1;
+            self.current_bits_index = 0;
+            // read current_byte_remaining
+
+            let mut rest = count - current_byte_remaining;
+            // while per 8 bits
+            while rest > 8 {
+                // read 8 bits
+                ans = (ans << 8) | self.bits[self.index] as u64;
+                self.index += 1;
+                rest -= 8;
+            }
+            // read rest bits
+            ans = (ans << rest) | (self.bits[self.index] >> (8 - rest)) as u64;
+            self.current_bits_index += rest;
+            if self.current_bits_index == 8 {
+                self.index += 1;
+                self.current_bits_index = 0;
+            }
+            ans
+        };
+        Some(ans)
+    }
+
+    /// Skips at most `max` consecutive `0` bits, returns the number of the skipped bits
+    pub fn skip_zeros(&mut self, max: usize) -> usize {
+        if self.index >= self.bits.len() {
+            return 0;
+        }
+        let total_bits = (self.bits.len() - 1) * 8 + self.last_used_bit as usize;
+        let start = self.index * 8 + self.current_bits_index as usize;
+        let end = total_bits.min(start.saturating_add(max));
+        let mut read_bits = start;
+        while read_bits < end {
+            let bit = read_bits % 8;
+            let byte = self.bits[read_bits / 8] << bit;
+            let zeros = (byte.leading_zeros() as usize)
+                .min(8 - bit)
+                .min(end - read_bits);
+            read_bits += zeros;
+            if zeros < 8 - bit {
+                break;
+            }
+        }
+        self.index = read_bits / 8;
+        self.current_bits_index = (read_bits % 8) as u8;
+        read_bits - start
+    }
+
+    pub fn finalize(mut self) -> &'de [u8] {
+        if self.bits.is_empty() {
+            return self.bits;
+        }
+        if self.current_bits_index > 0 {
+            self.index += 1;
+        }
+        &self.bits[self.index..]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bits_roundtrip() {
+        let values = [
+            (1, 1),
+            (0b101, 3),
+            (u64::MAX, 64),
+            (0, 7),
+            (12345, 21),
+            (1, 64),
+        ];
+        let mut writer = BitWriter::new();
+        for &(value, count) in values.iter() {
+            writer.write_bits(value, count);
+        }
+        let mut bytes = Vec::new();
+        writer.finish_into(&mut bytes);
+        let mut reader = BitReader::new(&bytes).unwrap();
+        for &(value, count) in values.iter() {
+            assert_eq!(reader.read_bits(count), Some(value));
+        }
+        assert_eq!(reader.read_bits(1), None);
+    }
+
+    #[test]
+    fn test_skip_zeros() {
+        let mut writer = BitWriter::new();
+        writer.write_bits(0, 3);
+        writer.write_bits(1, 1);
+        writer.write_bits(0, 20);
+        writer.write_bits(0b11, 2);
+        writer.write_bits(0, 5);
+        let mut bytes = Vec::new();
+        writer.finish_into(&mut bytes);
+        let mut reader = BitReader::new(&bytes).unwrap();
+        assert_eq!(reader.skip_zeros(100), 3);
+        assert_eq!(reader.skip_zeros(100), 0);
+        assert_eq!(reader.read_bits(1), Some(1));
+        assert_eq!(reader.skip_zeros(7), 7);
+        assert_eq!(reader.skip_zeros(100), 13);
+        assert_eq!(reader.read_bits(2), Some(0b11));
+        assert_eq!(reader.skip_zeros(100), 5);
+        assert_eq!(reader.read_bits(1), None);
+    }
+}
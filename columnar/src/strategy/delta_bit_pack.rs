@@ -0,0 +1,388 @@
+use std::marker::PhantomData;
+
+use crate::{ColumnarError, DeltaRleable};
+
+use super::{
+    bits::{BitReader, BitWriter},
+    MAX_RLE_COUNT,
+};
+
+const BLOCK_SIZE: usize = 128;
+const MINIBLOCKS_PER_BLOCK: usize = 4;
+
+/// Delta encoding with bit-packed miniblocks, the same layout as Parquet's
+/// `DELTA_BINARY_PACKED`.
+///
+/// The header is the block size, the number of miniblocks in a block, the number of
+/// values and the first value. The deltas between consecutive values are split into
+/// blocks of 128, each block has its minimum delta, then the bit width of each of its
+/// 4 miniblocks, then the miniblocks with `delta - min_delta` packed at their own width.
+///
+/// `block_size: varint` `miniblocks: varint` `len: varint` `first: zigzag varint`
+/// (`min_delta: zigzag varint` `bit_widths: [u8; miniblocks]` `miniblocks`)*
+///
+/// Unlike Parquet, the last miniblock is not padded to its full size.
+#[derive(Debug)]
+pub struct DeltaBitPackEncoder {
+    len: usize,
+    first: Option<i128>,
+    prev: i128,
+    deltas: Vec<i128>,
+    bytes: Vec<u8>,
+}
+
+impl Default for DeltaBitPackEncoder {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            first: None,
+            prev: 0,
+            deltas: Vec::with_capacity(BLOCK_SIZE),
+            bytes: Vec::new(),
+        }
+    }
+}
+
+impl DeltaBitPackEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn append<T: DeltaRleable>(&mut self, value: T) -> Result<(), ColumnarError> {
+        let v: i128 = value
+            .try_into()
+            .map_err(|_| ColumnarError::RleEncodeError("cannot into i128".to_string()))?;
+        self.len += 1;
+        if self.first.is_none() {
+            self.first = Some(v);
+        } else {
+            let delta = v.checked_sub(self.prev).ok_or_else(|| {
+                ColumnarError::RleEncodeError("delta overflow 128 bits".to_string())
+            })?;
+            self.deltas.push(delta);
+            if self.deltas.len() == BLOCK_SIZE {
+                self.flush_block()?;
+            }
+        }
+        self.prev = v;
+        Ok(())
+    }
+
+    fn flush_block(&mut self) -> Result<(), ColumnarError> {
+        let min_delta = self.deltas.iter().copied().min().unwrap_or(0);
+        let mut residuals = Vec::with_capacity(self.deltas.len());
+        for &delta in self.deltas.iter() {
+            let residual: u64 = delta
+                .checked_sub(min_delta)
+                .and_then(|residual| residual.try_into().ok())
+                .ok_or_else(|| {
+                    ColumnarError::RleEncodeError(format!(
+                        "the delta range of the block [{}, {}] overflows 64 bits",
+                        min_delta, delta
+                    ))
+                })?;
+            residuals.push(residual);
+        }
+        self.deltas.clear();
+
+        self.bytes.extend(postcard::to_allocvec(&min_delta)?);
+        let miniblocks = residuals
+            .chunks(BLOCK_SIZE / MINIBLOCKS_PER_BLOCK)
+            .collect::<Vec<_>>();
+        for i in 0..MINIBLOCKS_PER_BLOCK {
+            let max = miniblocks
+                .get(i)
+                .and_then(|m| m.iter().copied().max())
+                .unwrap_or(0);
+            self.bytes.push((64 - max.leading_zeros()) as u8);
+        }
+        let widths_start = self.bytes.len() - MINIBLOCKS_PER_BLOCK;
+        for (i, miniblock) in miniblocks.into_iter().enumerate() {
+            let width = self.bytes[widths_start + i];
+            let mut bits = BitWriter::new();
+            for &residual in miniblock {
+                bits.write_bits(residual, width);
+            }
+            bits.finish_without_len_into(&mut self.bytes);
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<Vec<u8>, ColumnarError> {
+        if !self.deltas.is_empty() {
+            self.flush_block()?;
+        }
+        let mut bytes = postcard::to_allocvec(&(BLOCK_SIZE, MINIBLOCKS_PER_BLOCK, self.len))?;
+        if let Some(first) = self.first {
+            bytes.extend(postcard::to_allocvec(&first)?);
+        }
+        bytes.extend(self.bytes);
+        Ok(bytes)
+    }
+}
+
+pub struct DeltaBitPackDecoder<'de, T> {
+    bytes: &'de [u8],
+    block_size: usize,
+    miniblock_size: usize,
+    remaining: usize,
+    first: Option<i128>,
+    prev: i128,
+    /// the deltas of the current block, in reverse order
+    deltas: Vec<i128>,
+    _t: PhantomData<T>,
+}
+
+impl<'de, T: DeltaRleable> DeltaBitPackDecoder<'de, T> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let ((block_size, miniblocks, remaining), mut bytes): ((usize, usize, usize), _) =
+            postcard::take_from_bytes(bytes)?;
+        if block_size != BLOCK_SIZE || miniblocks != MINIBLOCKS_PER_BLOCK {
+            return Err(ColumnarError::RleDecodeError(format!(
+                "invalid DeltaBitPack block size {} with {} miniblocks",
+                block_size, miniblocks
+            )));
+        }
+        // Prevent bad data from causing oom loops
+        if remaining > MAX_RLE_COUNT {
+            return Err(ColumnarError::RleDecodeError(format!(
+                "decode DeltaBitPack len is too large : {}",
+                remaining
+            )));
+        }
+        let mut first = None;
+        if remaining > 0 {
+            let (v, rest) = postcard::take_from_bytes(bytes)?;
+            first = Some(v);
+            bytes = rest;
+        }
+        Ok(Self {
+            bytes,
+            block_size,
+            miniblock_size: block_size / miniblocks,
+            remaining,
+            first,
+            prev: 0,
+            deltas: Vec::new(),
+            _t: PhantomData,
+        })
+    }
+
+    pub fn decode(&mut self) -> Result<Vec<T>, ColumnarError> {
+        let mut values = Vec::new();
+        while let Some(value) = self.try_next()? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    fn read_block(&mut self) -> Result<(), ColumnarError> {
+        // the first value is not in any block
+        let len = self.remaining.min(self.block_size);
+        let (min_delta, bytes): (i128, _) = postcard::take_from_bytes(self.bytes)?;
+        let miniblocks = self.block_size / self.miniblock_size;
+        if bytes.len() < miniblocks {
+            return Err(ColumnarError::RleDecodeError(
+                "DeltaBitPack block ends unexpectedly".to_string(),
+            ));
+        }
+        let (widths, mut bytes) = bytes.split_at(miniblocks);
+        self.deltas.clear();
+        for (i, &width) in widths.iter().enumerate() {
+            let n = len
+                .saturating_sub(i * self.miniblock_size)
+                .min(self.miniblock_size);
+            if n == 0 {
+                break;
+            }
+            if width > 64 {
+                return Err(ColumnarError::RleDecodeError(format!(
+                    "DeltaBitPack bit width {} is larger than 64",
+                    width
+                )));
+            }
+            let bit_len = n.checked_mul(width as usize).ok_or_else(|| {
+                ColumnarError::RleDecodeError("DeltaBitPack block size overflows".to_string())
+            })?;
+            let mut bits = BitReader::with_bit_len(bytes, bit_len).ok_or_else(|| {
+                ColumnarError::RleDecodeError("DeltaBitPack block ends unexpectedly".to_string())
+            })?;
+            for _ in 0..n {
+                let residual = bits.read_bits(width).unwrap();
+                self.deltas.push(min_delta.saturating_add(residual as i128));
+            }
+            bytes = &bytes[bit_len.div_ceil(8)..];
+        }
+        self.deltas.reverse();
+        self.bytes = bytes;
+        Ok(())
+    }
+
+    fn try_next_i128(&mut self) -> Result<Option<i128>, ColumnarError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        if let Some(first) = self.first.take() {
+            self.remaining -= 1;
+            self.prev = first;
+            return Ok(Some(first));
+        }
+        if self.deltas.is_empty() {
+            self.read_block()?;
+        }
+        let delta = self.deltas.pop().unwrap();
+        self.remaining -= 1;
+        self.prev = self.prev.checked_add(delta).ok_or_else(|| {
+            ColumnarError::RleDecodeError("DeltaBitPack value overflows i128".to_string())
+        })?;
+        Ok(Some(self.prev))
+    }
+
+    pub fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        match self.try_next_i128()? {
+            Some(value) => Ok(Some(T::try_from(value).map_err(|_| {
+                ColumnarError::RleDecodeError(format!(
+                    "{} cannot be safely converted from i128",
+                    value
+                ))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn finalize(self) -> Result<&'de [u8], ColumnarError> {
+        if self.remaining > 0 {
+            return Err(ColumnarError::RleDecodeError(format!(
+                "{} values of DeltaBitPack are not decoded",
+                self.remaining
+            )));
+        }
+        Ok(self.bytes)
+    }
+
+    pub fn take_n_finalize(mut self, n: usize) -> Result<(Vec<T>, &'de [u8]), ColumnarError> {
+        let mut ans = Vec::with_capacity(n);
+        for _ in 0..n {
+            if let Some(v) = self.try_next()? {
+                ans.push(v);
+            } else {
+                return Err(ColumnarError::RleDecodeError(format!(
+                    "The elements of decoder is less than n ({})",
+                    n
+                )));
+            }
+        }
+        Ok((ans, self.finalize()?))
+    }
+}
+
+impl<'de, T: DeltaRleable> Iterator for DeltaBitPackDecoder<'de, T> {
+    type Item = Result<T, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::strategy::DeltaRleEncoder;
+
+    fn roundtrip<T: DeltaRleable + std::fmt::Debug>(data: &[T]) -> Vec<u8> {
+        let mut encoder = DeltaBitPackEncoder::new();
+        for &v in data {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        let decoded = DeltaBitPackDecoder::<T>::new(&bytes)
+            .unwrap()
+            .decode()
+            .unwrap();
+        assert_eq!(decoded, data);
+        bytes
+    }
+
+    #[test]
+    fn test_delta_bit_pack() {
+        roundtrip::<u32>(&[]);
+        roundtrip(&[7u32]);
+        roundtrip(&[7u32, 7]);
+        roundtrip(&(0..1000).collect::<Vec<u64>>());
+        roundtrip(&(0..1000).rev().map(|i| i * 3 - 1000).collect::<Vec<i32>>());
+        roundtrip(&[i64::MIN, -1, i64::MAX]);
+        roundtrip(&(0..161).map(|i| (i * i) % 97).collect::<Vec<i16>>());
+    }
+
+    #[test]
+    fn test_delta_bit_pack_noisy_deltas() {
+        let mut lamport = 0u32;
+        let data = (0..1000)
+            .map(|i| {
+                lamport += 1 + (i * 7 % 5);
+                lamport
+            })
+            .collect::<Vec<_>>();
+        let bytes = roundtrip(&data);
+
+        let mut delta_rle = DeltaRleEncoder::new();
+        for &v in data.iter() {
+            delta_rle.append(v).unwrap();
+        }
+        let delta_rle = delta_rle.finish().unwrap();
+        // 3 bits per value, and 5 bytes of min delta and bit widths per block
+        assert!(bytes.len() < 1000 * 3 / 8 + 8 * 5 + 10);
+        assert!(bytes.len() * 2 < delta_rle.len());
+    }
+
+    #[test]
+    fn test_delta_bit_pack_range_overflow() {
+        let mut encoder = DeltaBitPackEncoder::new();
+        for v in [i64::MIN, i64::MAX, i64::MIN] {
+            encoder.append(v).unwrap();
+        }
+        assert!(encoder.finish().is_err());
+
+        let mut encoder = DeltaBitPackEncoder::new();
+        for v in [0, i128::MAX, 0, i128::MIN] {
+            encoder.append(v).unwrap();
+        }
+        assert!(encoder.finish().is_err());
+    }
+
+    #[test]
+    fn test_delta_bit_pack_corrupt_block_size() {
+        let mut bytes = postcard::to_allocvec(&(usize::MAX, 1usize, usize::MAX)).unwrap();
+        bytes.extend(postcard::to_allocvec(&0i128).unwrap());
+        bytes.extend(postcard::to_allocvec(&0i128).unwrap());
+        bytes.push(64);
+        assert!(DeltaBitPackDecoder::<u64>::new(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_delta_bit_pack_corrupt_width_0() {
+        // a miniblock of width 0 has no payload, so only the header bounds the values
+        let header = |block_size: usize, miniblocks: usize, len: usize| {
+            let mut bytes = postcard::to_allocvec(&(block_size, miniblocks, len)).unwrap();
+            bytes.extend(postcard::to_allocvec(&0i128).unwrap());
+            bytes.extend(postcard::to_allocvec(&0i128).unwrap());
+            bytes.extend([0; MINIBLOCKS_PER_BLOCK]);
+            bytes
+        };
+        let bytes = header(1 << 34, 1, 1 << 34);
+        assert!(DeltaBitPackDecoder::<u64>::new(&bytes).is_err());
+        let bytes = header(BLOCK_SIZE, MINIBLOCKS_PER_BLOCK, usize::MAX);
+        assert!(DeltaBitPackDecoder::<u64>::new(&bytes).is_err());
+
+        let bytes = header(BLOCK_SIZE, MINIBLOCKS_PER_BLOCK, 100);
+        let mut decoder = DeltaBitPackDecoder::<u64>::new(&bytes).unwrap();
+        assert_eq!(decoder.decode().unwrap(), vec![0; 100]);
+    }
+
+    #[test]
+    fn test_delta_bit_pack_truncated() {
+        let data = (0..300).map(|i| i * 11 % 13).collect::<Vec<u32>>();
+        let bytes = roundtrip(&data);
+        let mut decoder = DeltaBitPackDecoder::<u32>::new(&bytes[..bytes.len() - 3]).unwrap();
+        assert!(decoder.decode().is_err());
+    }
+}
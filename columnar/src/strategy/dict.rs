@@ -0,0 +1,162 @@
+use std::{borrow::Borrow, collections::HashMap, ops::DerefMut};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    column::dict::Dictable,
+    columnar_internal::{ColumnarDecoder, ColumnarEncoder},
+    ColumnarError,
+};
+
+use super::{AnyRleDecoder, AnyRleEncoder};
+
+/// Stores each distinct value once and the column itself as Rle-compressed indices
+/// into that dictionary.
+///
+/// The encoded bytes are the dictionary (a postcard sequence of the distinct values in
+/// order of first appearance) followed by the [AnyRleEncoder] output of the indices.
+pub struct DictEncoder<T> {
+    values: Vec<T>,
+    lookup: HashMap<T, usize>,
+    indices: AnyRleEncoder<usize>,
+}
+
+impl<T> DictEncoder<T>
+where
+    T: Dictable,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn append<BT: Borrow<T>>(&mut self, value: BT) -> Result<(), ColumnarError> {
+        let value = value.borrow();
+        let index = match self.lookup.get(value) {
+            Some(&index) => index,
+            None => {
+                let index = self.values.len();
+                self.values.push(value.clone());
+                self.lookup.insert(value.clone(), index);
+                index
+            }
+        };
+        self.indices.append(index)
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        let mut ser = ColumnarEncoder::new();
+        self.values.serialize(ser.deref_mut())?;
+        let mut bytes = ser.into_bytes();
+        bytes.extend(self.indices.finish()?);
+        Ok(bytes)
+    }
+}
+
+impl<T> Default for DictEncoder<T> {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            lookup: HashMap::new(),
+            indices: AnyRleEncoder::default(),
+        }
+    }
+}
+
+pub struct DictDecoder<'de, T> {
+    values: Vec<T>,
+    indices: AnyRleDecoder<'de, usize>,
+}
+
+impl<'de, T> DictDecoder<'de, T>
+where
+    T: Dictable,
+{
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let mut de = ColumnarDecoder::new(bytes);
+        let values = Vec::<T>::deserialize(de.deref_mut())?;
+        let rest = de.finalize()?;
+        Ok(Self {
+            values,
+            indices: AnyRleDecoder::new(rest),
+        })
+    }
+
+    pub fn decode(&mut self) -> Result<Vec<T>, ColumnarError> {
+        let mut values = Vec::new();
+        while let Some(value) = self.try_next()? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    pub fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        match self.indices.try_next()? {
+            Some(index) => self.values.get(index).cloned().map(Some).ok_or_else(|| {
+                ColumnarError::RleDecodeError(format!(
+                    "dict index {} is out of bounds (dict size {})",
+                    index,
+                    self.values.len()
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    pub fn finalize(self) -> Result<&'de [u8], ColumnarError> {
+        self.indices.finalize()
+    }
+}
+
+impl<'de, T: Dictable> Iterator for DictDecoder<'de, T> {
+    type Item = Result<T, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dict() {
+        let data = ["map", "list", "map", "text", "list", "map"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+        let mut encoder = DictEncoder::<String>::new();
+        for s in data.iter() {
+            encoder.append(s).unwrap();
+        }
+        let buf = encoder.finish().unwrap();
+        let mut decoder = DictDecoder::<String>::new(&buf).unwrap();
+        assert_eq!(decoder.decode().unwrap(), data);
+    }
+
+    #[test]
+    fn test_dict_stores_each_value_once() {
+        let mut encoder = DictEncoder::<String>::new();
+        for i in 0..1000 {
+            encoder
+                .append(["Map", "List", "Text"][i % 3].to_string())
+                .unwrap();
+        }
+        let buf = encoder.finish().unwrap();
+        // 3 strings in the dictionary, and 1000 small literal indices
+        assert!(buf.len() < 1100);
+        let mut decoder = DictDecoder::<String>::new(&buf).unwrap();
+        assert_eq!(decoder.decode().unwrap().len(), 1000);
+    }
+
+    #[test]
+    fn test_dict_invalid_index() {
+        let mut encoder = DictEncoder::<u8>::new();
+        encoder.append(1).unwrap();
+        let mut buf = encoder.finish().unwrap();
+        // replace the indices with a run of two `5`, which is out of the dictionary
+        buf.truncate(2);
+        buf.extend([4, 5]);
+        let mut decoder = DictDecoder::<u8>::new(&buf).unwrap();
+        assert!(decoder.decode().is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use std::marker::PhantomData;
+
+use crate::{column::float_xor::FloatXorable, ColumnarError};
+
+use super::bits::{BitReader, BitWriter};
+
+/// Gorilla-style float compression.
+///
+/// The first value is written with all its bits. Every following value is XORed with
+/// the previous one and written as:
+///
+/// - `0`: the XOR is zero, i.e. the value is the same as the previous one
+/// - `10` + meaningful bits: the meaningful bits of the XOR fit in the window
+///   (leading zeros, trailing zeros) of the last `11` record
+/// - `11` + 6 bits of leading zeros + 6 bits of (meaningful bits length - 1) + meaningful bits
+///
+/// The bits are written by the same bit writer as [DeltaOfDeltaEncoder](super::DeltaOfDeltaEncoder).
+#[derive(Debug)]
+pub struct FloatXorEncoder<T> {
+    bits: BitWriter,
+    prev: Option<u64>,
+    /// (leading zeros, trailing zeros) of the last written window
+    window: Option<(u8, u8)>,
+    _t: PhantomData<T>,
+}
+
+impl<T> Default for FloatXorEncoder<T> {
+    fn default() -> Self {
+        Self {
+            bits: BitWriter::new(),
+            prev: None,
+            window: None,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T: FloatXorable> FloatXorEncoder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn append(&mut self, value: T) -> Result<(), ColumnarError> {
+        let value = value.to_xor_bits();
+        let Some(prev) = self.prev.replace(value) else {
+            self.bits.write_bits(value, T::BITS);
+            return Ok(());
+        };
+
+        let xor = prev ^ value;
+        if xor == 0 {
+            self.bits.write_bits(0, 1);
+            return Ok(());
+        }
+
+        let leading = xor.leading_zeros() as u8;
+        let trailing = xor.trailing_zeros() as u8;
+        match self.window {
+            Some((prev_leading, prev_trailing))
+                if leading >= prev_leading && trailing >= prev_trailing =>
+            {
+                self.bits.write_bits(0b10, 2);
+                self.bits
+                    .write_bits(xor >> prev_trailing, 64 - prev_leading - prev_trailing);
+            }
+            _ => {
+                let meaningful = 64 - leading - trailing;
+                self.bits.write_bits(0b11, 2);
+                self.bits.write_bits(leading as u64, 6);
+                self.bits.write_bits((meaningful - 1) as u64, 6);
+                self.bits.write_bits(xor >> trailing, meaningful);
+                self.window = Some((leading, trailing));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, ColumnarError> {
+        let mut bytes = Vec::new();
+        self.bits.finish_into(&mut bytes);
+        Ok(bytes)
+    }
+}
+
+pub struct FloatXorDecoder<'de, T> {
+    bits: BitReader<'de>,
+    prev: Option<u64>,
+    window: Option<(u8, u8)>,
+    _t: PhantomData<T>,
+}
+
+impl<'de, T: FloatXorable> FloatXorDecoder<'de, T> {
+    pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
+        let bits = BitReader::new(bytes)
+            .ok_or_else(|| ColumnarError::RleDecodeError("Invalid FloatXor input".to_string()))?;
+        Ok(Self {
+            bits,
+            prev: None,
+            window: None,
+            _t: PhantomData,
+        })
+    }
+
+    pub fn decode(&mut self) -> Result<Vec<T>, ColumnarError> {
+        let mut values = Vec::new();
+        while let Some(value) = self.try_next()? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    pub fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        let Some(prev) = self.prev else {
+            let Some(value) = self.bits.read_bits(T::BITS) else {
+                return Ok(None);
+            };
+            self.prev = Some(value);
+            return Ok(Some(T::from_xor_bits(value)));
+        };
+
+        let xor = match self.bits.read_bits(1) {
+            None => return Ok(None),
+            Some(0) => 0,
+            Some(_) => {
+                let (leading, trailing) = if self.read(1)? == 0 {
+                    self.window.ok_or_else(|| {
+                        ColumnarError::RleDecodeError(
+                            "FloatXor reuses a window before any is written".to_string(),
+                        )
+                    })?
+                } else {
+                    let leading = self.read(6)? as u8;
+                    let meaningful = self.read(6)? as u8 + 1;
+                    let trailing = 64u8.checked_sub(leading + meaningful).ok_or_else(|| {
+                        ColumnarError::RleDecodeError(format!(
+                            "FloatXor window overflow: {} leading zeros and {} meaningful bits",
+                            leading, meaningful
+                        ))
+                    })?;
+                    self.window = Some((leading, trailing));
+                    (leading, trailing)
+                };
+                self.read(64 - leading - trailing)? << trailing
+            }
+        };
+
+        let value = prev ^ xor;
+        self.prev = Some(value);
+        Ok(Some(T::from_xor_bits(value)))
+    }
+
+    fn read(&mut self, count: u8) -> Result<u64, ColumnarError> {
+        self.bits.read_bits(count).ok_or_else(|| {
+            ColumnarError::RleDecodeError("FloatXor bits end unexpectedly".to_string())
+        })
+    }
+
+    pub fn finalize(self) -> Result<&'de [u8], ColumnarError> {
+        Ok(self.bits.finalize())
+    }
+
+    pub fn take_n_finalize(mut self, n: usize) -> Result<(Vec<T>, &'de [u8]), ColumnarError> {
+        let mut ans = Vec::with_capacity(n);
+        for _ in 0..n {
+            if let Some(v) = self.try_next()? {
+                ans.push(v);
+            } else {
+                return Err(ColumnarError::RleDecodeError(format!(
+                    "The elements of decoder is less than n ({})",
+                    n
+                )));
+            }
+        }
+        Ok((ans, self.finalize()?))
+    }
+}
+
+impl<'de, T: FloatXorable> Iterator for FloatXorDecoder<'de, T> {
+    type Item = Result<T, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn roundtrip<T: FloatXorable + PartialEq>(data: &[T]) -> Vec<u8> {
+        let mut encoder = FloatXorEncoder::new();
+        for &v in data {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        let decoded = FloatXorDecoder::<T>::new(&bytes).unwrap().decode().unwrap();
+        assert_eq!(
+            decoded.iter().map(|v| v.to_xor_bits()).collect::<Vec<_>>(),
+            data.iter().map(|v| v.to_xor_bits()).collect::<Vec<_>>()
+        );
+        bytes
+    }
+
+    #[test]
+    fn test_float_xor() {
+        roundtrip::<f64>(&[]);
+        roundtrip(&[1.5f64]);
+        roundtrip(&[
+            0.0f64,
+            -0.0,
+            1.0,
+            1.0,
+            f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MIN_POSITIVE,
+            f64::MAX,
+            12.25,
+            12.5,
+        ]);
+        roundtrip(&[0.1f32, 0.2, 0.2, -3.75, f32::NAN, f32::MAX]);
+    }
+
+    #[test]
+    fn test_float_xor_size() {
+        let data = (0..1000)
+            .map(|i| 20.0 + (i % 4) as f64 * 0.5)
+            .collect::<Vec<_>>();
+        let bytes = roundtrip(&data);
+        assert!(bytes.len() < 1000);
+
+        let same = vec![0.3f32; 1000];
+        let bytes = roundtrip(&same);
+        // 32 bits for the first value and one bit for each of the others
+        assert_eq!(bytes.len(), 1 + (32 + 999usize).div_ceil(8));
+    }
+}
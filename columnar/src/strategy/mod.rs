@@ -1,7 +1,33 @@
+mod auto;
+mod bit_pack;
+mod bits;
+mod delta_bit_pack;
+mod dict;
+mod float_xor;
 mod rle;
+pub use auto::{AutoDecoder, AutoEncoder, StrategyTag};
+pub use bit_pack::{BitPackDecoder, BitPackEncoder};
+pub use delta_bit_pack::{DeltaBitPackDecoder, DeltaBitPackEncoder};
+pub use dict::{DictDecoder, DictEncoder};
+pub use float_xor::{FloatXorDecoder, FloatXorEncoder};
 pub use rle::{
     AnyRleDecoder, AnyRleEncoder, BoolRleDecoder, BoolRleEncoder, DeltaOfDeltaDecoder,
-    DeltaOfDeltaEncoder, DeltaRleDecoder, DeltaRleEncoder,
+    DeltaOfDeltaEncoder, DeltaRleDecoder, DeltaRleEncoder, RleRun, RleRuns,
 };
 
 pub const MAX_RLE_COUNT: usize = 1e9 as usize;
+
+/// The `nth` of the decoders and iterators with `skip_values`, which skips the `n` values
+/// without decoding them one by one before taking the next one
+macro_rules! nth_by_skip_values {
+    () => {
+        fn nth(&mut self, n: usize) -> Option<Self::Item> {
+            match self.skip_values(n) {
+                Ok(skipped) if skipped == n => self.next(),
+                Ok(_) => None,
+                Err(e) => Some(Err(e)),
+            }
+        }
+    };
+}
+pub(crate) use nth_by_skip_values;
@@ -3,19 +3,23 @@
 use crate::{
     column::{delta_of_delta::DeltaOfDeltable, rle::Rleable},
     columnar_internal::{ColumnarDecoder, ColumnarEncoder},
-    ColumnarError, DeltaRleable,
+    ColumnStats, ColumnarError, DeltaRleable,
 };
 use serde::{Deserialize, Serialize};
 
 use std::{borrow::Borrow, marker::PhantomData, ops::DerefMut};
 
-use super::MAX_RLE_COUNT;
+use super::{
+    bits::{BitReader, BitWriter},
+    nth_by_skip_values, MAX_RLE_COUNT,
+};
 
 #[derive(Default)]
 pub struct BoolRleEncoder {
     ser: ColumnarEncoder,
     last: bool,
     count: usize,
+    stats: Option<ColumnStats>,
 }
 
 impl BoolRleEncoder {
@@ -23,7 +27,19 @@ impl BoolRleEncoder {
         Self::default()
     }
 
+    /// Like [BoolRleEncoder::new], but the encoder also tracks the [ColumnStats] of the
+    /// appended values, which are written by [BoolRleEncoder::finish_with_stats]
+    pub fn with_stats() -> Self {
+        Self {
+            stats: Some(ColumnStats::default()),
+            ..Default::default()
+        }
+    }
+
     pub fn append(&mut self, value: bool) -> Result<(), ColumnarError> {
+        if let Some(stats) = &mut self.stats {
+            stats.push(value as i128, 1);
+        }
         if value == self.last {
             self.count += 1;
         } else {
@@ -34,17 +50,77 @@ impl BoolRleEncoder {
         Ok(())
     }
 
+    /// Appends `n` copies of `value`, which encodes the same bytes as appending them one by one
+    pub fn append_run(&mut self, value: bool, n: usize) -> Result<(), ColumnarError> {
+        if n == 0 {
+            return Ok(());
+        }
+        if let Some(stats) = &mut self.stats {
+            stats.push(value as i128, n);
+        }
+        if value == self.last {
+            self.count += n;
+        } else {
+            self.count.serialize(self.ser.deref_mut())?;
+            self.last = value;
+            self.count = n;
+        }
+        Ok(())
+    }
+
     pub fn finish(mut self) -> Result<Vec<u8>, ColumnarError> {
         if self.count > 0 {
             self.count.serialize(self.ser.deref_mut()).unwrap();
         }
         Ok(self.ser.into_bytes())
     }
+
+    /// The stats of the appended values, where `false` is 0 and `true` is 1, `None` if the
+    /// encoder is not created by [BoolRleEncoder::with_stats]
+    pub fn stats(&self) -> Option<ColumnStats> {
+        self.stats
+    }
+
+    /// Like [BoolRleEncoder::finish], the bytes start with the header of [ColumnStats]
+    pub fn finish_with_stats(self) -> Result<Vec<u8>, ColumnarError> {
+        let stats = self.stats.ok_or_else(ColumnStats::untracked)?;
+        stats.write(&self.finish()?)
+    }
 }
 
 pub struct AnyRleEncoder<T> {
     ser: ColumnarEncoder,
     state: RleState<T>,
+    stats: Option<AnyRleStats<T>>,
+}
+
+/// The [ColumnStats] tracked by [AnyRleEncoder::with_stats]
+struct AnyRleStats<T> {
+    stats: ColumnStats,
+    /// Whether all the values fit in `i128`, otherwise the range is unknown
+    numeric: bool,
+    to_i128: fn(&T) -> Option<i128>,
+}
+
+impl<T> AnyRleStats<T> {
+    fn push(&mut self, value: &T, n: usize) {
+        match (self.to_i128)(value) {
+            Some(value) => self.stats.push(value, n),
+            None => {
+                self.numeric = false;
+                self.stats.row_count += n as u64;
+            }
+        }
+    }
+
+    fn finish(&self) -> ColumnStats {
+        let mut stats = self.stats;
+        if !self.numeric {
+            stats.min = None;
+            stats.max = None;
+        }
+        stats
+    }
 }
 
 impl<T> AnyRleEncoder<T>
@@ -55,10 +131,47 @@ where
         Self::default()
     }
 
+    /// Like [AnyRleEncoder::new], but the encoder also tracks the [ColumnStats] of the
+    /// appended values, which are written by [AnyRleEncoder::finish_with_stats]. The range
+    /// of the values is `None` if a value does not fit in `i128`.
+    pub fn with_stats() -> Self
+    where
+        T: TryInto<i128>,
+    {
+        Self {
+            stats: Some(AnyRleStats {
+                stats: ColumnStats::default(),
+                numeric: true,
+                to_i128: |value| value.clone().try_into().ok(),
+            }),
+            ..Default::default()
+        }
+    }
+
     pub fn append<BT: Borrow<T>>(&mut self, value: BT) -> Result<(), ColumnarError> {
+        if let Some(stats) = &mut self.stats {
+            stats.push(value.borrow(), 1);
+        }
         self.append_value(value)
     }
 
+    /// Appends `n` copies of `value`, which encodes the same bytes as appending them one by one
+    pub fn append_run<BT: Borrow<T>>(&mut self, value: BT, n: usize) -> Result<(), ColumnarError> {
+        if let Some(stats) = &mut self.stats {
+            stats.push(value.borrow(), n);
+        }
+        // the state is always a run of `value` after appending it twice
+        for _ in 0..n.min(2) {
+            self.append_value(value.borrow())?;
+        }
+        if n > 2 {
+            if let RleState::Run(_, len) = &mut self.state {
+                *len += n - 2;
+            }
+        }
+        Ok(())
+    }
+
     pub fn finish(mut self) -> Result<Vec<u8>, ColumnarError> {
         match self.take_state() {
             RleState::LoneVal(value) => self.flush_lit_run(vec![value]),
@@ -85,6 +198,18 @@ where
         self.ser
     }
 
+    /// The stats of the appended values, `None` if the encoder is not created by
+    /// [AnyRleEncoder::with_stats]
+    pub fn stats(&self) -> Option<ColumnStats> {
+        self.stats.as_ref().map(AnyRleStats::finish)
+    }
+
+    /// Like [AnyRleEncoder::finish], the bytes start with the header of [ColumnStats]
+    pub fn finish_with_stats(self) -> Result<Vec<u8>, ColumnarError> {
+        let stats = self.stats().ok_or_else(ColumnStats::untracked)?;
+        stats.write(&self.finish()?)
+    }
+
     fn append_value<BT: Borrow<T>>(&mut self, value: BT) -> Result<(), ColumnarError> {
         self.state = match self.take_state() {
             RleState::Empty => RleState::LoneVal(value.borrow().clone()),
@@ -150,6 +275,7 @@ impl<T> Default for AnyRleEncoder<T> {
         Self {
             ser: Default::default(),
             state: RleState::Empty,
+            stats: None,
         }
     }
 }
@@ -162,11 +288,41 @@ enum RleState<T> {
     Run(T, usize),
 }
 
+/// A run decoded by `runs()` of the Rle decoders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RleRun<T> {
+    /// `len` copies of `value`
+    Run { value: T, len: usize },
+    /// The values of a literal run, which are not repeated
+    Literal(Vec<T>),
+}
+
+impl<T> RleRun<T> {
+    /// The number of the values in the run
+    pub fn len(&self) -> usize {
+        match self {
+            RleRun::Run { len, .. } => *len,
+            RleRun::Literal(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The iterator over the remaining runs of a decoder, returned by its `runs()`
+pub struct RleRuns<'a, D> {
+    pub(crate) decoder: &'a mut D,
+}
+
 pub struct AnyRleDecoder<'de, T> {
     de: ColumnarDecoder<'de>,
     last_value: Option<T>,
     count: isize,
     literal: bool,
+    /// Whether a malformed run header is an error, otherwise it ends the runs
+    strict: bool,
 }
 
 impl<'de, T> AnyRleDecoder<'de, T>
@@ -179,6 +335,16 @@ where
             last_value: None,
             count: 0,
             literal: false,
+            strict: false,
+        }
+    }
+
+    /// Like [AnyRleDecoder::new], but a malformed run header is an error rather than the
+    /// end of the runs, as the iterators of [crate::iterable] expect
+    pub(crate) fn new_strict(bytes: &'de [u8]) -> Self {
+        Self {
+            strict: true,
+            ..Self::new(bytes)
         }
     }
 
@@ -190,32 +356,63 @@ where
         Ok(values)
     }
 
+    /// Reads the header of the next run, returns `false` if there are no more runs
+    fn next_run(&mut self) -> Result<bool, ColumnarError> {
+        let count = match isize::deserialize(self.de.deref_mut()) {
+            Err(e) if self.strict && e != postcard::Error::DeserializeUnexpectedEnd => {
+                return Err(ColumnarError::from(e))
+            }
+            Err(_) => return Ok(false),
+            Ok(c) => c,
+        };
+        // Prevent bad data from causing oom loops
+        if count.unsigned_abs() > MAX_RLE_COUNT {
+            return Err(ColumnarError::RleDecodeError(format!(
+                "decode Rle count is too large : {}",
+                self.count
+            )));
+        }
+        match count {
+            n if n > 0 => {
+                self.count = n;
+                self.last_value = Some(T::deserialize(self.de.deref_mut())?);
+                self.literal = false;
+            }
+            n if n < 0 => {
+                self.count = n.abs();
+                self.literal = true;
+            }
+            _ => return Err(ColumnarError::RleDecodeError("Invalid count".to_string())),
+        }
+        Ok(true)
+    }
+
+    /// Skips `n` values, the values in a run are skipped without being decoded.
+    /// Returns the number of the skipped values, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        let mut skipped = 0;
+        while skipped < n {
+            if self.count == 0 && !self.next_run()? {
+                break;
+            }
+            if self.literal {
+                T::deserialize(self.de.deref_mut())?;
+                self.count -= 1;
+                skipped += 1;
+            } else {
+                let len = (n - skipped).min(self.count as usize);
+                self.count -= len as isize;
+                skipped += len;
+            }
+        }
+        Ok(skipped)
+    }
+
     pub fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
         while self.count == 0 {
-            let count = isize::deserialize(self.de.deref_mut());
-            if count.is_err() {
+            if !self.next_run()? {
                 return Ok(None);
             }
-            let count = count.unwrap();
-            // Prevent bad data from causing oom loops
-            if count.unsigned_abs() > MAX_RLE_COUNT {
-                return Err(ColumnarError::RleDecodeError(format!(
-                    "decode Rle count is too large : {}",
-                    self.count
-                )));
-            }
-            match count {
-                n if n > 0 => {
-                    self.count = n;
-                    self.last_value = Some(T::deserialize(self.de.deref_mut())?);
-                    self.literal = false;
-                }
-                n if n < 0 => {
-                    self.count = n.abs();
-                    self.literal = true;
-                }
-                _ => return Err(ColumnarError::RleDecodeError("Invalid count".to_string())),
-            }
         }
         self.count -= 1;
         if self.literal {
@@ -225,6 +422,50 @@ where
         }
     }
 
+    /// Decodes the rest of the current run, or the next run if it is finished
+    pub fn try_next_run(&mut self) -> Result<Option<RleRun<T>>, ColumnarError> {
+        while self.count == 0 {
+            if !self.next_run()? {
+                return Ok(None);
+            }
+        }
+        let len = std::mem::take(&mut self.count) as usize;
+        if self.literal {
+            let mut values = Vec::new();
+            for _ in 0..len {
+                values.push(T::deserialize(self.de.deref_mut())?);
+            }
+            Ok(Some(RleRun::Literal(values)))
+        } else {
+            Ok(Some(RleRun::Run {
+                value: self.last_value.clone().unwrap(),
+                len,
+            }))
+        }
+    }
+
+    /// The remaining runs, each one is decoded once instead of value by value
+    pub fn runs(&mut self) -> RleRuns<'_, Self> {
+        RleRuns { decoder: self }
+    }
+
+    /// Takes the next value and the values equal to it in the same run, returns the value and
+    /// the number of the values taken
+    pub(crate) fn next_value_run(&mut self) -> Result<Option<(T, usize)>, ColumnarError> {
+        while self.count == 0 {
+            if !self.next_run()? {
+                return Ok(None);
+            }
+        }
+        if self.literal {
+            self.count -= 1;
+            Ok(Some((T::deserialize(self.de.deref_mut())?, 1)))
+        } else {
+            let len = std::mem::take(&mut self.count) as usize;
+            Ok(Some((self.last_value.clone().unwrap(), len)))
+        }
+    }
+
     pub fn finalize(self) -> Result<&'de [u8], ColumnarError> {
         self.de.finalize()
     }
@@ -249,6 +490,8 @@ pub struct BoolRleDecoder<'de> {
     de: ColumnarDecoder<'de>,
     last_value: bool,
     count: usize,
+    /// Whether a malformed run header is an error, otherwise it ends the runs
+    strict: bool,
 }
 
 impl<'de> BoolRleDecoder<'de> {
@@ -257,6 +500,16 @@ impl<'de> BoolRleDecoder<'de> {
             de: ColumnarDecoder::new(bytes),
             last_value: true,
             count: 0,
+            strict: false,
+        }
+    }
+
+    /// Like [BoolRleDecoder::new], but a malformed run header is an error rather than the
+    /// end of the runs, as the iterators of [crate::iterable] expect
+    pub(crate) fn new_strict(bytes: &'de [u8]) -> Self {
+        Self {
+            strict: true,
+            ..Self::new(bytes)
         }
     }
 
@@ -268,26 +521,102 @@ impl<'de> BoolRleDecoder<'de> {
         Ok(values)
     }
 
+    /// Starts decoding at the run of `bytes`, where `last_value` is the value of the run before it
+    pub(crate) fn with_last_value(bytes: &'de [u8], last_value: bool) -> Self {
+        Self {
+            de: ColumnarDecoder::new(bytes),
+            last_value,
+            count: 0,
+            strict: false,
+        }
+    }
+
+    /// Reads the length of the next run, returns `false` if there are no more runs
+    fn next_run(&mut self) -> Result<bool, ColumnarError> {
+        self.count = match usize::deserialize(self.de.deref_mut()) {
+            Err(e) if self.strict && e != postcard::Error::DeserializeUnexpectedEnd => {
+                return Err(ColumnarError::from(e))
+            }
+            Err(_) => return Ok(false),
+            Ok(c) => c,
+        };
+        // Prevent bad data from causing oom loops
+        if self.count > MAX_RLE_COUNT {
+            return Err(ColumnarError::RleDecodeError(format!(
+                "decode Rle count is too large : {}",
+                self.count
+            )));
+        }
+        self.last_value = !self.last_value;
+        Ok(true)
+    }
+
+    /// Skips `n` values without decoding them one by one.
+    /// Returns the number of the skipped values, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        Ok(self.skip_values_count_true(n)?.0)
+    }
+
+    /// Like [BoolRleDecoder::skip_values], also returns the number of the skipped `true`
+    pub(crate) fn skip_values_count_true(
+        &mut self,
+        n: usize,
+    ) -> Result<(usize, usize), ColumnarError> {
+        let mut skipped = 0;
+        let mut trues = 0;
+        while skipped < n {
+            if self.count == 0 && !self.next_run()? {
+                break;
+            }
+            let len = (n - skipped).min(self.count);
+            self.count -= len;
+            skipped += len;
+            if self.last_value {
+                trues += len;
+            }
+        }
+        Ok((skipped, trues))
+    }
+
     pub fn try_next(&mut self) -> Result<Option<bool>, ColumnarError> {
         while self.count == 0 {
-            let count = usize::deserialize(self.de.deref_mut());
-            if count.is_err() && self.count == 0 {
+            if !self.next_run()? {
                 return Ok(None);
             }
-            self.count = count.unwrap();
-            // Prevent bad data from causing oom loops
-            if self.count > MAX_RLE_COUNT {
-                return Err(ColumnarError::RleDecodeError(format!(
-                    "decode Rle count is too large : {}",
-                    self.count
-                )));
-            }
-            self.last_value = !self.last_value;
         }
         self.count -= 1;
         Ok(Some(self.last_value))
     }
 
+    /// Decodes the rest of the current run, or the next non-empty run if it is finished.
+    /// The runs of `BoolRle` are never literal.
+    pub fn try_next_run(&mut self) -> Result<Option<RleRun<bool>>, ColumnarError> {
+        while self.count == 0 {
+            if !self.next_run()? {
+                return Ok(None);
+            }
+        }
+        Ok(Some(RleRun::Run {
+            value: self.last_value,
+            len: std::mem::take(&mut self.count),
+        }))
+    }
+
+    /// Takes the rest of the current run, returns its value and the number of the values taken
+    pub(crate) fn next_value_run(&mut self) -> Result<Option<(bool, usize)>, ColumnarError> {
+        while self.count == 0 {
+            if !self.next_run()? {
+                return Ok(None);
+            }
+        }
+        Ok(Some((self.last_value, std::mem::take(&mut self.count))))
+    }
+
+    /// The remaining runs, each one is decoded once instead of value by value
+    pub fn runs(&mut self) -> RleRuns<'_, Self> {
+        RleRuns { decoder: self }
+    }
+
     pub fn finalize(self) -> Result<&'de [u8], ColumnarError> {
         self.de.finalize()
     }
@@ -312,6 +641,7 @@ impl<'de> BoolRleDecoder<'de> {
 pub struct DeltaRleEncoder {
     rle: AnyRleEncoder<i128>,
     absolute_value: i128,
+    stats: Option<ColumnStats>,
 }
 
 impl DeltaRleEncoder {
@@ -319,19 +649,55 @@ impl DeltaRleEncoder {
         Self::default()
     }
 
+    /// Like [DeltaRleEncoder::new], but the encoder also tracks the [ColumnStats] of the
+    /// appended values, which are written by [DeltaRleEncoder::finish_with_stats]
+    pub fn with_stats() -> Self {
+        Self {
+            stats: Some(ColumnStats::default()),
+            ..Default::default()
+        }
+    }
+
     pub fn append<T: DeltaRleable>(&mut self, value: T) -> Result<(), ColumnarError> {
         let v: i128 = value
             .try_into()
             .map_err(|_| ColumnarError::RleEncodeError("cannot into i128".to_string()))?;
         let delta = v.saturating_sub(self.absolute_value);
         self.absolute_value = v;
+        if let Some(stats) = &mut self.stats {
+            stats.push(v, 1);
+        }
         self.rle.append(delta)
     }
 
+    /// Appends `n` copies of `value`, which encodes the same bytes as appending them one by one
+    pub fn append_run<T: DeltaRleable>(&mut self, value: T, n: usize) -> Result<(), ColumnarError> {
+        if n == 0 {
+            return Ok(());
+        }
+        self.append(value)?;
+        if let Some(stats) = &mut self.stats {
+            stats.row_count += n as u64 - 1;
+        }
+        self.rle.append_run(0, n - 1)
+    }
+
     pub fn finish(self) -> Result<Vec<u8>, ColumnarError> {
         self.rle.finish()
     }
 
+    /// The stats of the appended values, `None` if the encoder is not created by
+    /// [DeltaRleEncoder::with_stats]
+    pub fn stats(&self) -> Option<ColumnStats> {
+        self.stats
+    }
+
+    /// Like [DeltaRleEncoder::finish], the bytes start with the header of [ColumnStats]
+    pub fn finish_with_stats(self) -> Result<Vec<u8>, ColumnarError> {
+        let stats = self.stats.ok_or_else(ColumnStats::untracked)?;
+        stats.write(&self.finish()?)
+    }
+
     pub fn take_encoder(self) -> ColumnarEncoder {
         self.rle.take_encoder()
     }
@@ -365,7 +731,50 @@ impl<'de, T: DeltaRleable> DeltaRleDecoder<'de, T> {
         Ok(values)
     }
 
-    fn try_next(&mut self) -> Result<Option<i128>, ColumnarError> {
+    /// Like [DeltaRleDecoder::new], but a malformed run header is an error rather than the
+    /// end of the runs, as the iterators of [crate::iterable] expect
+    pub(crate) fn new_strict(bytes: &'de [u8]) -> Self {
+        Self {
+            rle: AnyRleDecoder::new_strict(bytes),
+            absolute_value: 0,
+            _t: PhantomData,
+        }
+    }
+
+    /// Starts decoding at the run of `bytes`, where `absolute_value` is the value before it
+    pub(crate) fn with_absolute_value(bytes: &'de [u8], absolute_value: i128) -> Self {
+        Self {
+            rle: AnyRleDecoder::new(bytes),
+            absolute_value,
+            _t: PhantomData,
+        }
+    }
+
+    /// Skips `n` values, the deltas in a run are added at once.
+    /// Returns the number of the skipped values, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        let mut skipped = 0;
+        while skipped < n {
+            if self.rle.count == 0 && !self.rle.next_run()? {
+                break;
+            }
+            if self.rle.literal {
+                self.try_next()?;
+                skipped += 1;
+            } else {
+                let len = (n - skipped).min(self.rle.count as usize);
+                let delta = self.rle.last_value.unwrap_or_default();
+                self.absolute_value = self
+                    .absolute_value
+                    .saturating_add(delta.saturating_mul(len as i128));
+                self.rle.count -= len as isize;
+                skipped += len;
+            }
+        }
+        Ok(skipped)
+    }
+
+    pub(crate) fn try_next(&mut self) -> Result<Option<i128>, ColumnarError> {
         let next = self.rle.try_next()?;
         if let Some(delta) = next {
             self.absolute_value = self.absolute_value.saturating_add(delta);
@@ -375,6 +784,28 @@ impl<'de, T: DeltaRleable> DeltaRleDecoder<'de, T> {
         }
     }
 
+    /// Takes the next value and the values equal to it in the same run of zero deltas,
+    /// returns the value and the number of the values taken
+    pub(crate) fn next_value_run(&mut self) -> Result<Option<(i128, usize)>, ColumnarError> {
+        let rle = &mut self.rle;
+        while rle.count == 0 {
+            if !rle.next_run()? {
+                return Ok(None);
+            }
+        }
+        let (delta, len) = if rle.literal {
+            rle.count -= 1;
+            (i128::deserialize(rle.de.deref_mut())?, 1)
+        } else if rle.last_value == Some(0) {
+            (0, std::mem::take(&mut rle.count) as usize)
+        } else {
+            rle.count -= 1;
+            (rle.last_value.unwrap_or_default(), 1)
+        };
+        self.absolute_value = self.absolute_value.saturating_add(delta);
+        Ok(Some((self.absolute_value, len)))
+    }
+
     pub fn finalize(self) -> Result<&'de [u8], ColumnarError> {
         self.rle.finalize()
     }
@@ -402,27 +833,13 @@ impl<'de, T: DeltaRleable> DeltaRleDecoder<'de, T> {
 
 const MAX_DELTA_OF_DELTA: i64 = 1 << 20;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DeltaOfDeltaEncoder {
-    bits: Vec<u64>,
-    last_used_bit: u8,
+    bits: BitWriter,
     head_num: Option<i64>,
     prev_value: i64,
     prev_delta: i64,
-    use_bit: bool,
-}
-
-impl Default for DeltaOfDeltaEncoder {
-    fn default() -> Self {
-        Self {
-            bits: vec![0u64],
-            head_num: None,
-            last_used_bit: 0,
-            prev_value: 0,
-            prev_delta: 0,
-            use_bit: false,
-        }
-    }
+    stats: Option<ColumnStats>,
 }
 
 impl DeltaOfDeltaEncoder {
@@ -430,14 +847,25 @@ impl DeltaOfDeltaEncoder {
         Self::default()
     }
 
+    /// Like [DeltaOfDeltaEncoder::new], but the encoder also tracks the [ColumnStats] of the
+    /// appended values, which are written by [DeltaOfDeltaEncoder::finish_with_stats]
+    pub fn with_stats() -> Self {
+        Self {
+            stats: Some(ColumnStats::default()),
+            ..Default::default()
+        }
+    }
+
     pub fn append(&mut self, value: i64) -> Result<(), ColumnarError> {
         // println!("append value {}", value);
         if self.head_num.is_none() {
             self.head_num = Some(value);
             self.prev_value = value;
+            if let Some(stats) = &mut self.stats {
+                stats.push(value as i128, 1);
+            }
             return Ok(());
         }
-        self.use_bit = true;
         let delta = value
             .checked_sub(self.prev_value)
             .ok_or(ColumnarError::RleEncodeError(
@@ -452,95 +880,70 @@ impl DeltaOfDeltaEncoder {
                 ))?;
         self.prev_value = value;
         self.prev_delta = delta;
+        if let Some(stats) = &mut self.stats {
+            stats.push(value as i128, 1);
+        }
         if delta_of_delta == 0 {
-            self.write_bits(0, 1);
+            self.bits.write_bits(0, 1);
         } else if (-63..=64).contains(&delta_of_delta) {
-            self.write_bits(0b10, 2);
-            self.write_bits((delta_of_delta + 63) as u64, 7);
+            self.bits.write_bits(0b10, 2);
+            self.bits.write_bits((delta_of_delta + 63) as u64, 7);
         } else if (-255..=256).contains(&delta_of_delta) {
-            self.write_bits(0b110, 3);
-            self.write_bits((delta_of_delta + 255) as u64, 9);
+            self.bits.write_bits(0b110, 3);
+            self.bits.write_bits((delta_of_delta + 255) as u64, 9);
         } else if (-2047..=2048).contains(&delta_of_delta) {
-            self.write_bits(0b1110, 4);
-            self.write_bits((delta_of_delta + 2047) as u64, 12);
+            self.bits.write_bits(0b1110, 4);
+            self.bits.write_bits((delta_of_delta + 2047) as u64, 12);
         } else if ((-MAX_DELTA_OF_DELTA + 1)..=MAX_DELTA_OF_DELTA).contains(&delta_of_delta) {
-            self.write_bits(0b11110, 5);
-            self.write_bits((delta_of_delta + MAX_DELTA_OF_DELTA - 1) as u64, 21);
+            self.bits.write_bits(0b11110, 5);
+            self.bits
+                .write_bits((delta_of_delta + MAX_DELTA_OF_DELTA - 1) as u64, 21);
         } else {
-            self.write_bits(0b11111, 5);
-            self.write_bits(delta_of_delta as u64, 64);
+            self.bits.write_bits(0b11111, 5);
+            self.bits.write_bits(delta_of_delta as u64, 64);
         }
         Ok(())
     }
 
-    fn write_bits(&mut self, value: u64, count: u8) {
-        if self.last_used_bit == 64 {
-            self.bits.push(value << (64 - count));
-            self.last_used_bit = count;
-        } else {
-            let remaining_bits = 64 - self.last_used_bit;
-            if count > remaining_bits {
-                let bits_latter = count - remaining_bits;
-                let former = self.bits.last_mut().unwrap();
-                *former ^= value >> bits_latter;
-                self.bits.push(value << (64 - bits_latter));
-                self.last_used_bit = bits_latter;
-            } else {
-                let last = self.bits.last_mut().unwrap();
-                *last ^= value << (remaining_bits - count);
-                self.last_used_bit += count;
-            }
-        }
-    }
-
     #[inline(never)]
     pub fn finish(self) -> Result<Vec<u8>, ColumnarError> {
-        let mut bytes = Vec::with_capacity(self.bits.len() * 8 + 1 + 8);
-        bytes.extend_from_slice(&postcard::to_allocvec(&self.head_num)?);
-        let used = self.last_used_bit.div_ceil(8);
-        bytes.push(if self.last_used_bit % 8 == 0 && self.use_bit {
-            8
-        } else {
-            self.last_used_bit % 8
-        });
-        for bits in &self.bits[..self.bits.len() - 1] {
-            bytes.extend(bits.to_be_bytes());
-        }
-        bytes.extend(&self.bits.last().unwrap().to_be_bytes()[..used as usize]);
-        // println!("bits {:?}", &bytes);
+        let mut bytes = postcard::to_allocvec(&self.head_num)?;
+        self.bits.finish_into(&mut bytes);
         Ok(bytes)
     }
+
+    /// The stats of the appended values, `None` if the encoder is not created by
+    /// [DeltaOfDeltaEncoder::with_stats]
+    pub fn stats(&self) -> Option<ColumnStats> {
+        self.stats
+    }
+
+    /// Like [DeltaOfDeltaEncoder::finish], the bytes start with the header of [ColumnStats]
+    pub fn finish_with_stats(self) -> Result<Vec<u8>, ColumnarError> {
+        let stats = self.stats.ok_or_else(ColumnStats::untracked)?;
+        stats.write(&self.finish()?)
+    }
 }
 
 pub struct DeltaOfDeltaDecoder<'de, T> {
-    bits: &'de [u8],
+    bits: BitReader<'de>,
     head_num: Option<i64>,
     prev_value: i64,
     prev_delta: i64,
-    index: usize,
-    current_bits_index: u8,
-    last_used_bit: u8,
     _t: PhantomData<T>,
 }
 
 impl<'de, T: DeltaOfDeltable> DeltaOfDeltaDecoder<'de, T> {
     pub fn new(bytes: &'de [u8]) -> Result<Self, ColumnarError> {
         let (head_num, bytes) = postcard::take_from_bytes(bytes)?;
-        if bytes.is_empty() {
-            return Err(ColumnarError::RleDecodeError(
-                "Invalid DeltaOfDelta input".to_string(),
-            ));
-        }
-        let last_used_bit = bytes[0];
-        let bits = &bytes[1..];
+        let bits = BitReader::new(bytes).ok_or_else(|| {
+            ColumnarError::RleDecodeError("Invalid DeltaOfDelta input".to_string())
+        })?;
         Ok(Self {
             bits,
             head_num,
             prev_value: 0,
             prev_delta: 0,
-            index: 0,
-            current_bits_index: 0,
-            last_used_bit,
             _t: PhantomData,
         })
     }
@@ -553,26 +956,61 @@ impl<'de, T: DeltaOfDeltable> DeltaOfDeltaDecoder<'de, T> {
         Ok(values)
     }
 
+    /// Skips `n` values, the bits of the zero delta of deltas are skipped at once.
+    /// Returns the number of the skipped values, which is less than `n` at the end.
+    pub(crate) fn skip_values(&mut self, n: usize) -> Result<usize, ColumnarError> {
+        let mut skipped = 0;
+        while skipped < n {
+            let zeros = if self.head_num.is_some() {
+                0
+            } else {
+                self.bits.skip_zeros(n - skipped)
+            };
+            if zeros > 0 {
+                self.prev_value += self.prev_delta * zeros as i64;
+                skipped += zeros;
+            } else if self.next_value()?.is_some() {
+                skipped += 1;
+            } else {
+                break;
+            }
+        }
+        Ok(skipped)
+    }
+
     pub(crate) fn try_next(&mut self) -> Result<Option<T>, ColumnarError> {
+        if self.next_value()?.is_none() {
+            return Ok(None);
+        }
+        // println!("prev_value {}", self.prev_value);
+        Ok(Some(self.prev_value.try_into().map_err(|_| {
+            ColumnarError::RleDecodeError(format!(
+                "{} cannot be safely converted from i128",
+                self.prev_value
+            ))
+        })?))
+    }
+
+    fn next_value(&mut self) -> Result<Option<i64>, ColumnarError> {
         if self.head_num.is_some() {
             self.prev_value = self.head_num.unwrap();
             self.head_num = None;
         } else {
-            match self.read_bits(1) {
+            match self.bits.read_bits(1) {
                 Some(0) => self.prev_value += self.prev_delta,
                 Some(1) => {
-                    let (num_bits, bias) = if self.read_bits(1).unwrap() == 0 {
+                    let (num_bits, bias) = if self.bits.read_bits(1).unwrap() == 0 {
                         (7, 63)
-                    } else if self.read_bits(1).unwrap() == 0 {
+                    } else if self.bits.read_bits(1).unwrap() == 0 {
                         (9, 255)
-                    } else if self.read_bits(1).unwrap() == 0 {
+                    } else if self.bits.read_bits(1).unwrap() == 0 {
                         (12, 2047)
-                    } else if self.read_bits(1).unwrap() == 0 {
+                    } else if self.bits.read_bits(1).unwrap() == 0 {
                         (21, MAX_DELTA_OF_DELTA - 1)
                     } else {
                         (64, 0)
                     };
-                    let delta_of_delta = self.read_bits(num_bits).unwrap() as i64 - bias;
+                    let delta_of_delta = self.bits.read_bits(num_bits).unwrap() as i64 - bias;
                     self.prev_delta += delta_of_delta;
                     self.prev_value += self.prev_delta;
                 }
@@ -584,75 +1022,11 @@ impl<'de, T: DeltaOfDeltable> DeltaOfDeltaDecoder<'de, T> {
                 }
             };
         }
-        // println!("prev_value {}", self.prev_value);
-        Ok(Some(self.prev_value.try_into().map_err(|_| {
-            ColumnarError::RleDecodeError(format!(
-                "{} cannot be safely converted from i128",
-                self.prev_value
-            ))
-        })?))
+        Ok(Some(self.prev_value))
     }
 
-    fn read_bits(&mut self, count: u8) -> Option<u64> {
-        if self.index >= self.bits.len() {
-            return None;
-        }
-
-        let total_bits = (self.bits.len() - 1) * 8 + self.last_used_bit as usize;
-        let read_bits = self.index * 8 + self.current_bits_index as usize;
-        let remaining_bits = total_bits - read_bits;
-
-        if remaining_bits < count as usize {
-            return None;
-        }
-
-        let current_byte_remaining = 8 - self.current_bits_index;
-        let ans = if count <= current_byte_remaining {
-            let current_index = self.index;
-            self.current_bits_index += count;
-            if self.current_bits_index == 8 {
-                self.index += 1;
-                self.current_bits_index = 0;
-            }
-            let mask = u8::MAX >> (8 - count);
-            let current_byte = self.bits[current_index];
-            let after_shift = current_byte >> (current_byte_remaining - count);
-            let ans = after_shift & mask;
-            ans as u64
-        } else {
-            let mut ans = (self.bits[self.index] & u8::MAX >> (8 - current_byte_remaining)) as u64;
-            self.index += 1;
-            self.current_bits_index = 0;
-            // read current_byte_remaining
-
-            let mut rest = count - current_byte_remaining;
-            // while per 8 bits
-            while rest > 8 {
-                // read 8 bits
-                ans = (ans << 8) | self.bits[self.index] as u64;
-                self.index += 1;
-                rest -= 8;
-            }
-            // read rest bits
-            ans = (ans << rest) | (self.bits[self.index] >> (8 - rest)) as u64;
-            self.current_bits_index += rest;
-            if self.current_bits_index == 8 {
-                self.index += 1;
-                self.current_bits_index = 0;
-            }
-            ans
-        };
-        Some(ans)
-    }
-
-    pub fn finalize(mut self) -> Result<&'de [u8], ColumnarError> {
-        if self.bits.is_empty() {
-            return Ok(self.bits);
-        }
-        if self.current_bits_index > 0 {
-            self.index += 1;
-        }
-        Ok(&self.bits[self.index..])
+    pub fn finalize(self) -> Result<&'de [u8], ColumnarError> {
+        Ok(self.bits.finalize())
     }
 
     pub fn take_n_finalize(mut self, n: usize) -> Result<(Vec<T>, &'de [u8]), ColumnarError> {
@@ -676,6 +1050,22 @@ impl<'de, T: Rleable> Iterator for AnyRleDecoder<'de, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.try_next().transpose()
     }
+
+    nth_by_skip_values!();
+}
+
+impl<'de, T: Rleable> Iterator for RleRuns<'_, AnyRleDecoder<'de, T>> {
+    type Item = Result<RleRun<T>, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.try_next_run().transpose()
+    }
+}
+
+impl Iterator for RleRuns<'_, BoolRleDecoder<'_>> {
+    type Item = Result<RleRun<bool>, ColumnarError>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decoder.try_next_run().transpose()
+    }
 }
 
 impl Iterator for BoolRleDecoder<'_> {
@@ -683,6 +1073,8 @@ impl Iterator for BoolRleDecoder<'_> {
     fn next(&mut self) -> Option<Self::Item> {
         self.try_next().transpose()
     }
+
+    nth_by_skip_values!();
 }
 
 impl<'de, T: DeltaRleable> Iterator for DeltaRleDecoder<'de, T> {
@@ -699,6 +1091,8 @@ impl<'de, T: DeltaRleable> Iterator for DeltaRleDecoder<'de, T> {
             Err(e) => Some(Err(e)),
         }
     }
+
+    nth_by_skip_values!();
 }
 
 impl<'de, T: DeltaOfDeltable> Iterator for DeltaOfDeltaDecoder<'de, T> {
@@ -706,6 +1100,8 @@ impl<'de, T: DeltaOfDeltable> Iterator for DeltaOfDeltaDecoder<'de, T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.try_next().transpose()
     }
+
+    nth_by_skip_values!();
 }
 
 #[cfg(test)]
@@ -728,6 +1124,32 @@ mod test {
         assert_eq!(rle_decoder.decode().unwrap(), vec![1000, 1000, 2, 2, 2]);
     }
 
+    #[test]
+    fn test_rle_corrupt_run_header() {
+        use super::*;
+        // the header of a run is a bad varint, rather than the end of the bytes, which
+        // ends the runs of the decoders but is an error of the iterators
+        let buf = [0xff; 11];
+        assert!(AnyRleDecoder::<u64>::new(&buf).decode().unwrap().is_empty());
+        assert!(BoolRleDecoder::new(&buf).decode().unwrap().is_empty());
+        assert!(DeltaRleDecoder::<u64>::new(&buf)
+            .decode()
+            .unwrap()
+            .is_empty());
+        assert!(crate::iterable::AnyRleIter::<u64>::new(&buf)
+            .next()
+            .unwrap()
+            .is_err());
+        assert!(crate::iterable::BoolRleIter::new(&buf)
+            .next()
+            .unwrap()
+            .is_err());
+        assert!(crate::iterable::DeltaRleIter::<u64>::new(&buf)
+            .next()
+            .unwrap()
+            .is_err());
+    }
+
     #[test]
     fn test_bool_rle() {
         use super::*;
@@ -780,6 +1202,51 @@ mod test {
         assert_eq!(values, vec![1, 2, 3, 4, 5, 6]);
     }
 
+    #[test]
+    fn test_nth() {
+        use super::*;
+        let values: Vec<u32> = (0..1000u32)
+            .map(|i| if i % 200 < 100 { i / 10 } else { i * 7 % 13 })
+            .collect();
+        let mut rle = AnyRleEncoder::<u32>::new();
+        let mut bool_rle = BoolRleEncoder::new();
+        let mut delta_rle = DeltaRleEncoder::new();
+        let mut delta_of_delta = DeltaOfDeltaEncoder::new();
+        for &v in values.iter() {
+            rle.append(v).unwrap();
+            bool_rle.append(v % 3 == 0).unwrap();
+            delta_rle.append(v).unwrap();
+            delta_of_delta.append(v as i64).unwrap();
+        }
+        let rle = rle.finish().unwrap();
+        let bool_rle = bool_rle.finish().unwrap();
+        let delta_rle = delta_rle.finish().unwrap();
+        let delta_of_delta = delta_of_delta.finish().unwrap();
+        for step in [0, 1, 9, 37, 150, 999, 2000] {
+            let mut expected = values.iter();
+            let mut rle = AnyRleDecoder::<u32>::new(&rle);
+            let mut bool_rle = BoolRleDecoder::new(&bool_rle);
+            let mut delta_rle = DeltaRleDecoder::<u32>::new(&delta_rle);
+            let mut delta_of_delta = DeltaOfDeltaDecoder::<i64>::new(&delta_of_delta).unwrap();
+            loop {
+                let v = expected.nth(step).copied();
+                assert_eq!(rle.nth(step).transpose().unwrap(), v);
+                assert_eq!(
+                    bool_rle.nth(step).transpose().unwrap(),
+                    v.map(|v| v % 3 == 0)
+                );
+                assert_eq!(delta_rle.nth(step).transpose().unwrap(), v);
+                assert_eq!(
+                    delta_of_delta.nth(step).transpose().unwrap(),
+                    v.map(|v| v as i64)
+                );
+                if v.is_none() {
+                    break;
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_size() {
         let mut rng = rand::thread_rng();
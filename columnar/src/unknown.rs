@@ -0,0 +1,108 @@
+//! The optional entries unknown to this version, which are kept by the field with
+//! `#[columnar(unknown_fields)]` and written back verbatim, so an old version can relay
+//! the data of a newer version without losing the fields it does not know.
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::ser::SerializeSeq;
+
+/// The optional entries unknown to this version, by their index
+///
+/// The entries of rows are the columns of all the rows decoded together, so every row
+/// shares them. They are written back only if the rows are still exactly the rows they
+/// were decoded with, in the same order, otherwise they are dropped because they no longer
+/// match the rows.
+#[derive(Debug, Clone, Default)]
+pub struct UnknownFields {
+    entries: Arc<BTreeMap<usize, Vec<u8>>>,
+    /// The number of the rows decoded together, 0 for a table
+    rows: usize,
+    /// The position of the row among the rows decoded together
+    row: usize,
+}
+
+impl UnknownFields {
+    /// The number of the unknown entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The bytes of the entry at `index`
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        self.entries.get(&index).map(Vec::as_slice)
+    }
+
+    /// The entries in ascending order of the index
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[u8])> {
+        self.entries
+            .iter()
+            .map(|(index, bytes)| (*index, bytes.as_slice()))
+    }
+
+    #[doc(hidden)]
+    pub fn from_entries<B>(entries: impl IntoIterator<Item = (usize, B)>, rows: usize) -> Self
+    where
+        B: Into<Vec<u8>>,
+    {
+        let entries: BTreeMap<usize, Vec<u8>> = entries
+            .into_iter()
+            .map(|(index, bytes)| (index, bytes.into()))
+            .collect();
+        if entries.is_empty() {
+            return Self::default();
+        }
+        Self {
+            entries: Arc::new(entries),
+            rows,
+            row: 0,
+        }
+    }
+
+    /// The entries of the row at `row` of the rows decoded together
+    #[doc(hidden)]
+    pub fn at_row(&self, row: usize) -> Self {
+        Self {
+            entries: Arc::clone(&self.entries),
+            rows: self.rows,
+            row,
+        }
+    }
+
+    /// The entries shared by all the `fields` of the rows, if the rows are the ones they
+    /// were decoded with
+    #[doc(hidden)]
+    pub fn shared_by_rows<'a>(fields: impl IntoIterator<Item = &'a Self>) -> Option<&'a Self> {
+        let mut fields = fields.into_iter();
+        let first = fields.next()?;
+        if first.is_empty() || first.row != 0 {
+            return None;
+        }
+        let mut rows = 1;
+        for field in fields {
+            if !Arc::ptr_eq(&field.entries, &first.entries) || field.row != rows {
+                return None;
+            }
+            rows += 1;
+        }
+        (rows == first.rows).then_some(first)
+    }
+
+    #[doc(hidden)]
+    pub fn serialize_entries<S: SerializeSeq>(&self, seq: &mut S) -> Result<(), S::Error> {
+        for (index, bytes) in self.entries.iter() {
+            seq.serialize_element(&(index, bytes))?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for UnknownFields {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl Eq for UnknownFields {}
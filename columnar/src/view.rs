@@ -0,0 +1,297 @@
+//! Random access into the encoded bytes of a column.
+//!
+//! [ColumnView] builds an index of the runs on first use, which holds the number of the
+//! values before each run and the byte offset of its header, so that a value is found by
+//! binary search and only the run that holds it is decoded.
+use std::{cell::OnceCell, ops::Range};
+
+use crate::{
+    strategy::{AnyRleDecoder, BoolRleDecoder, DeltaRleDecoder, MAX_RLE_COUNT},
+    ColumnarError, DeltaRleable, Rleable,
+};
+
+/// The start of a run in the index of [ColumnView]
+#[derive(Debug, Clone, Copy)]
+pub struct RunStart<B> {
+    /// The index of the first value of the run
+    pub index: usize,
+    /// The byte offset of the header of the run
+    pub offset: usize,
+    /// The state of the decoder before the run, e.g. the absolute value of `DeltaRle`
+    pub base: B,
+}
+
+/// The decoder whose runs can be indexed by [ColumnView]
+pub trait RunView<'de> {
+    type Item;
+    type Base: Copy;
+
+    /// Returns the start of each run, followed by the end of the bytes whose `index` is the
+    /// number of all the values
+    fn index_runs(bytes: &'de [u8]) -> Result<Vec<RunStart<Self::Base>>, ColumnarError>;
+
+    /// Decodes `n` values after skipping `skip` values of the run starting at `bytes`
+    fn decode_run(
+        bytes: &'de [u8],
+        base: Self::Base,
+        skip: usize,
+        n: usize,
+    ) -> Result<Vec<Self::Item>, ColumnarError>;
+}
+
+/// The random access view of the bytes encoded by [AnyRleEncoder](crate::AnyRleEncoder),
+/// [DeltaRleEncoder](crate::DeltaRleEncoder) or [BoolRleEncoder](crate::BoolRleEncoder),
+/// e.g. `ColumnView::<DeltaRleDecoder<u32>>::new(&bytes)`.
+pub struct ColumnView<'de, D: RunView<'de>> {
+    bytes: &'de [u8],
+    index: OnceCell<Vec<RunStart<D::Base>>>,
+}
+
+impl<'de, D: RunView<'de>> ColumnView<'de, D> {
+    pub fn new(bytes: &'de [u8]) -> Self {
+        Self {
+            bytes,
+            index: OnceCell::new(),
+        }
+    }
+
+    fn index(&self) -> Result<&[RunStart<D::Base>], ColumnarError> {
+        if let Some(index) = self.index.get() {
+            return Ok(index);
+        }
+        let index = D::index_runs(self.bytes)?;
+        Ok(self.index.get_or_init(|| index))
+    }
+
+    /// The number of the values
+    pub fn len(&self) -> Result<usize, ColumnarError> {
+        Ok(self.index()?.last().map_or(0, |end| end.index))
+    }
+
+    pub fn is_empty(&self) -> Result<bool, ColumnarError> {
+        Ok(self.len()? == 0)
+    }
+
+    /// The value at `i`, or `None` if it is out of bounds
+    pub fn get(&self, i: usize) -> Result<Option<D::Item>, ColumnarError> {
+        Ok(self.range(i..i.saturating_add(1))?.pop())
+    }
+
+    /// The values in `range`, which stops at the end of the column
+    pub fn range(&self, range: Range<usize>) -> Result<Vec<D::Item>, ColumnarError> {
+        let index = self.index()?;
+        let end = range.end.min(self.len()?);
+        if range.start >= end {
+            return Ok(Vec::new());
+        }
+        let run = &index[index.partition_point(|r| r.index <= range.start) - 1];
+        let values = D::decode_run(
+            &self.bytes[run.offset..],
+            run.base,
+            range.start - run.index,
+            end - range.start,
+        )?;
+        if values.len() < end - range.start {
+            return Err(ColumnarError::RleDecodeError(
+                "the run index is out of the bytes".to_string(),
+            ));
+        }
+        Ok(values)
+    }
+}
+
+fn take_from_bytes<'a, T: serde::Deserialize<'a>>(
+    bytes: &mut &'a [u8],
+) -> Result<T, ColumnarError> {
+    let (value, rest) = postcard::take_from_bytes(bytes)?;
+    *bytes = rest;
+    Ok(value)
+}
+
+fn check_count(count: usize) -> Result<usize, ColumnarError> {
+    // Prevent bad data from causing oom loops
+    if count > MAX_RLE_COUNT {
+        return Err(ColumnarError::RleDecodeError(format!(
+            "decode Rle count is too large : {}",
+            count
+        )));
+    }
+    Ok(count)
+}
+
+/// Walks the runs of `AnyRle` and returns the number of all the values, `f` is called with
+/// the offset and the count of each run, and moves the bytes to the end of the run.
+fn index_any_rle_runs<'de>(
+    bytes: &'de [u8],
+    mut f: impl FnMut(usize, isize, &mut &'de [u8]) -> Result<(), ColumnarError>,
+) -> Result<usize, ColumnarError> {
+    let mut rest = bytes;
+    let mut len = 0;
+    while !rest.is_empty() {
+        let offset = bytes.len() - rest.len();
+        let count: isize = take_from_bytes(&mut rest)?;
+        if count == 0 {
+            return Err(ColumnarError::RleDecodeError("Invalid count".to_string()));
+        }
+        len += check_count(count.unsigned_abs())?;
+        f(offset, count, &mut rest)?;
+    }
+    Ok(len)
+}
+
+impl<'de, T: Rleable> RunView<'de> for AnyRleDecoder<'de, T> {
+    type Item = T;
+    type Base = ();
+
+    fn index_runs(bytes: &'de [u8]) -> Result<Vec<RunStart<()>>, ColumnarError> {
+        let mut runs = Vec::new();
+        let mut index = 0;
+        let len = index_any_rle_runs(bytes, |offset, count, rest| {
+            runs.push(RunStart {
+                index,
+                offset,
+                base: (),
+            });
+            index += count.unsigned_abs();
+            let values = if count > 0 { 1 } else { count.unsigned_abs() };
+            for _ in 0..values {
+                take_from_bytes::<T>(rest)?;
+            }
+            Ok(())
+        })?;
+        runs.push(RunStart {
+            index: len,
+            offset: bytes.len(),
+            base: (),
+        });
+        Ok(runs)
+    }
+
+    fn decode_run(
+        bytes: &'de [u8],
+        _base: (),
+        skip: usize,
+        n: usize,
+    ) -> Result<Vec<T>, ColumnarError> {
+        let mut decoder = AnyRleDecoder::<T>::new(bytes);
+        decoder.skip_values(skip)?;
+        let mut values = Vec::with_capacity(n);
+        while values.len() < n {
+            match decoder.try_next()? {
+                Some(value) => values.push(value),
+                None => break,
+            }
+        }
+        Ok(values)
+    }
+}
+
+impl<'de, T: DeltaRleable> RunView<'de> for DeltaRleDecoder<'de, T> {
+    type Item = T;
+    type Base = i128;
+
+    fn index_runs(bytes: &'de [u8]) -> Result<Vec<RunStart<i128>>, ColumnarError> {
+        let mut runs = Vec::new();
+        let mut index = 0;
+        let mut absolute_value: i128 = 0;
+        let len = index_any_rle_runs(bytes, |offset, count, rest| {
+            runs.push(RunStart {
+                index,
+                offset,
+                base: absolute_value,
+            });
+            index += count.unsigned_abs();
+            if count > 0 {
+                let delta: i128 = take_from_bytes(rest)?;
+                absolute_value = absolute_value.saturating_add(delta.saturating_mul(count as i128));
+            } else {
+                for _ in 0..count.unsigned_abs() {
+                    let delta: i128 = take_from_bytes(rest)?;
+                    absolute_value = absolute_value.saturating_add(delta);
+                }
+            }
+            Ok(())
+        })?;
+        runs.push(RunStart {
+            index: len,
+            offset: bytes.len(),
+            base: absolute_value,
+        });
+        Ok(runs)
+    }
+
+    fn decode_run(
+        bytes: &'de [u8],
+        base: i128,
+        skip: usize,
+        n: usize,
+    ) -> Result<Vec<T>, ColumnarError> {
+        let mut decoder = DeltaRleDecoder::<T>::with_absolute_value(bytes, base);
+        decoder.skip_values(skip)?;
+        let mut values = Vec::with_capacity(n);
+        while values.len() < n {
+            match decoder.try_next()? {
+                Some(value) => values.push(T::try_from(value).map_err(|_| {
+                    ColumnarError::RleDecodeError(format!(
+                        "{} cannot be safely converted from i128",
+                        value
+                    ))
+                })?),
+                None => break,
+            }
+        }
+        Ok(values)
+    }
+}
+
+impl<'de> RunView<'de> for BoolRleDecoder<'de> {
+    type Item = bool;
+    /// The value of the run before it
+    type Base = bool;
+
+    fn index_runs(bytes: &'de [u8]) -> Result<Vec<RunStart<bool>>, ColumnarError> {
+        let mut runs = Vec::new();
+        let mut rest = bytes;
+        let mut len = 0;
+        // the first run is `false`
+        let mut last_value = true;
+        while !rest.is_empty() {
+            let offset = bytes.len() - rest.len();
+            let count = check_count(take_from_bytes(&mut rest)?)?;
+            // the empty runs only flip the value
+            if count > 0 {
+                runs.push(RunStart {
+                    index: len,
+                    offset,
+                    base: last_value,
+                });
+            }
+            len += count;
+            last_value = !last_value;
+        }
+        runs.push(RunStart {
+            index: len,
+            offset: bytes.len(),
+            base: last_value,
+        });
+        Ok(runs)
+    }
+
+    fn decode_run(
+        bytes: &'de [u8],
+        base: bool,
+        skip: usize,
+        n: usize,
+    ) -> Result<Vec<bool>, ColumnarError> {
+        let mut decoder = BoolRleDecoder::with_last_value(bytes, base);
+        decoder.skip_values(skip)?;
+        let mut values = Vec::with_capacity(n);
+        while values.len() < n {
+            match decoder.try_next()? {
+                Some(value) => values.push(value),
+                None => break,
+            }
+        }
+        Ok(values)
+    }
+}
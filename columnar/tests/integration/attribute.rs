@@ -5,7 +5,9 @@ use std::{
 };
 
 use serde::{Deserialize, Serialize};
-use serde_columnar::{columnar, from_bytes, to_vec, DeltaRleable, Rleable};
+use serde_columnar::{
+    columnar, from_bytes, to_vec, ColumnarMap, ColumnarVec, DeltaRleable, Rleable,
+};
 
 #[test]
 fn derive_serialize() {
@@ -617,3 +619,740 @@ fn delta_of_delta() {
     assert_eq!(table, read_table);
     insta::assert_yaml_snapshot!(bytes);
 }
+
+#[test]
+fn dict() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct A {
+        #[columnar(strategy = "Dict")]
+        container: String,
+        #[columnar(strategy = "Dict")]
+        peer: u64,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Table {
+        #[columnar(class = "vec")]
+        data: Vec<A>,
+    }
+
+    let table = Table {
+        data: ["Map", "List", "Map", "Text", "List"]
+            .iter()
+            .enumerate()
+            .map(|(i, c)| A {
+                container: c.to_string(),
+                peer: (i % 2) as u64,
+            })
+            .collect(),
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+    insta::assert_yaml_snapshot!(bytes);
+}
+
+#[test]
+fn auto() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct A {
+        #[columnar(strategy = "Auto")]
+        same: u32,
+        #[columnar(strategy = "Auto")]
+        counter: u64,
+        #[columnar(strategy = "Auto")]
+        time: i64,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Table {
+        #[columnar(class = "vec")]
+        data: Vec<A>,
+    }
+
+    let table = Table {
+        data: (0..10)
+            .map(|i| A {
+                same: 5,
+                counter: i,
+                time: 1_000_000 + i as i64 * 60 + (i % 2) as i64,
+            })
+            .collect(),
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+    insta::assert_yaml_snapshot!(bytes);
+}
+
+#[test]
+fn float_xor() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct A {
+        #[columnar(strategy = "FloatXor")]
+        temperature: f64,
+        #[columnar(strategy = "FloatXor")]
+        ratio: f32,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Table {
+        #[columnar(class = "vec")]
+        data: Vec<A>,
+    }
+
+    let table = Table {
+        data: (0..10)
+            .map(|i| A {
+                temperature: 20.0 + (i % 3) as f64 * 0.25,
+                ratio: 0.5,
+            })
+            .collect(),
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+    insta::assert_yaml_snapshot!(bytes);
+}
+
+#[test]
+fn bit_pack() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct A {
+        #[columnar(strategy = "BitPack")]
+        kind: u8,
+        #[columnar(strategy = "BitPack")]
+        len: usize,
+        #[columnar(optional, index = 0, strategy = "BitPack")]
+        pos: i64,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Table {
+        #[columnar(class = "vec")]
+        data: Vec<A>,
+    }
+
+    let table = Table {
+        data: (0..10)
+            .map(|i| A {
+                kind: [3, 0, 2, 1][i % 4],
+                len: 10 + i * 3 % 7,
+                pos: -(i as i64),
+            })
+            .collect(),
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+    insta::assert_yaml_snapshot!(bytes);
+}
+
+#[test]
+fn delta_bit_pack() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct A {
+        #[columnar(strategy = "DeltaBitPack")]
+        lamport: u32,
+        #[columnar(optional, index = 0, strategy = "DeltaBitPack")]
+        counter: i32,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Table {
+        #[columnar(class = "vec")]
+        data: Vec<A>,
+    }
+
+    let table = Table {
+        data: (0..10)
+            .map(|i| A {
+                lamport: 10 + i * 3 + i % 2,
+                counter: -(i as i32),
+            })
+            .collect(),
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+    insta::assert_yaml_snapshot!(bytes);
+}
+
+#[test]
+fn nullable() {
+    #[columnar(vec, map, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct A {
+        #[columnar(nullable, strategy = "DeltaRle")]
+        counter: Option<u64>,
+        #[columnar(nullable, strategy = "Rle")]
+        peer: Option<String>,
+        #[columnar(nullable, strategy = "BoolRle")]
+        deleted: Option<bool>,
+        #[columnar(nullable)]
+        name: Option<String>,
+        #[columnar(nullable, optional, index = 0, strategy = "DeltaRle")]
+        lamport: Option<u32>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Table {
+        #[columnar(class = "vec")]
+        vec: Vec<A>,
+        #[columnar(class = "map")]
+        map: BTreeMap<u32, A>,
+    }
+
+    let rows: Vec<A> = (0..10)
+        .map(|i| A {
+            counter: (i % 4 != 3).then_some(i as u64),
+            peer: (i < 5).then(|| "alice".to_string()),
+            deleted: (i % 2 == 0).then_some(i > 5),
+            name: (i == 7).then(|| "seven".to_string()),
+            lamport: Some(100 + i),
+        })
+        .collect();
+    let table = Table {
+        map: rows
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, a)| (i as u32, a))
+            .collect(),
+        vec: rows,
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+    insta::assert_yaml_snapshot!(bytes);
+}
+
+#[test]
+fn nullable_delta_rle_is_smaller() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Nullable {
+        #[columnar(nullable, strategy = "DeltaRle")]
+        counter: Option<u64>,
+    }
+
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Generic {
+        counter: Option<u64>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct NullableTable {
+        #[columnar(class = "vec")]
+        rows: Vec<Nullable>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct GenericTable {
+        #[columnar(class = "vec")]
+        rows: Vec<Generic>,
+    }
+
+    let counters = (0..1000u64).map(|i| (i % 100 != 99).then_some(1000 + i));
+    let nullable = NullableTable {
+        rows: counters
+            .clone()
+            .map(|counter| Nullable { counter })
+            .collect(),
+    };
+    let generic = GenericTable {
+        rows: counters.map(|counter| Generic { counter }).collect(),
+    };
+    let nullable_bytes = to_vec(&nullable).unwrap();
+    let generic_bytes = to_vec(&generic).unwrap();
+    assert!(nullable_bytes.len() * 10 < generic_bytes.len());
+    assert_eq!(nullable, from_bytes(&nullable_bytes).unwrap());
+}
+
+#[test]
+fn enum_row() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    enum Op {
+        Insert {
+            #[columnar(strategy = "DeltaRle")]
+            pos: u32,
+            content: String,
+        },
+        Delete {
+            #[columnar(strategy = "DeltaRle")]
+            pos: u32,
+            #[columnar(strategy = "Rle")]
+            len: u32,
+            #[columnar(skip)]
+            cached: u64,
+        },
+        Move(#[columnar(strategy = "DeltaRle")] u32, u32),
+        Clear,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct OpTable {
+        #[columnar(class = "vec")]
+        ops: Vec<Op>,
+    }
+
+    let table = OpTable {
+        ops: vec![
+            Op::Insert {
+                pos: 0,
+                content: "abc".to_string(),
+            },
+            Op::Insert {
+                pos: 3,
+                content: "de".to_string(),
+            },
+            Op::Delete {
+                pos: 1,
+                len: 2,
+                cached: 0,
+            },
+            Op::Move(4, 0),
+            Op::Clear,
+            Op::Delete {
+                pos: 0,
+                len: 2,
+                cached: 0,
+            },
+            Op::Insert {
+                pos: 0,
+                content: "f".to_string(),
+            },
+        ],
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table: OpTable = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+    insta::assert_yaml_snapshot!(bytes);
+
+    let empty = OpTable { ops: vec![] };
+    let bytes = to_vec(&empty).unwrap();
+    assert_eq!(empty, from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn enum_row_with_nullable_and_class() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Item {
+        #[columnar(strategy = "DeltaRle")]
+        id: u64,
+    }
+
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    enum Event<'a> {
+        Batch {
+            #[columnar(class = "vec")]
+            items: Vec<Item>,
+            #[columnar(nullable, strategy = "Rle")]
+            label: Option<Cow<'a, str>>,
+        },
+        Single(Item),
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct EventTable<'a> {
+        #[columnar(class = "vec")]
+        events: Vec<Event<'a>>,
+    }
+
+    let table = EventTable {
+        events: vec![
+            Event::Single(Item { id: 1 }),
+            Event::Batch {
+                items: vec![Item { id: 2 }, Item { id: 3 }],
+                label: None,
+            },
+            Event::Batch {
+                items: vec![],
+                label: Some(Cow::Borrowed("empty")),
+            },
+            Event::Single(Item { id: 4 }),
+        ],
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table: EventTable = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+}
+
+#[test]
+fn tuple_struct_row() {
+    #[columnar(vec, map, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Id(
+        #[columnar(strategy = "DeltaRle")] u64,
+        #[columnar(strategy = "Rle")] u32,
+    );
+
+    #[columnar(vec, map, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Span(Id, #[columnar(strategy = "DeltaRle")] u32);
+
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Lamport(#[columnar(strategy = "DeltaRle")] u32);
+
+    #[columnar(vec, map, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct NamedId {
+        #[columnar(strategy = "DeltaRle")]
+        counter: u64,
+        #[columnar(strategy = "Rle")]
+        peer: u32,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct SpanTable {
+        #[columnar(class = "vec")]
+        ids: Vec<Id>,
+        #[columnar(class = "map")]
+        id_map: BTreeMap<u32, Id>,
+        #[columnar(class = "vec")]
+        spans: Vec<Span>,
+        #[columnar(class = "vec")]
+        lamports: Vec<Lamport>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct NamedTable {
+        #[columnar(class = "vec")]
+        ids: Vec<NamedId>,
+        #[columnar(class = "map")]
+        id_map: BTreeMap<u32, NamedId>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct TupleTable(#[columnar(class = "vec")] Vec<Id>, u8);
+
+    let ids: Vec<Id> = (0..10).map(|i| Id(100 + i, (i / 4) as u32)).collect();
+    let table = SpanTable {
+        id_map: ids.iter().cloned().map(|id| (id.1 * 10, id)).collect(),
+        spans: ids.iter().cloned().map(|id| Span(id, 2)).collect(),
+        lamports: (0..10).map(Lamport).collect(),
+        ids: ids.clone(),
+    };
+    let bytes = to_vec(&table).unwrap();
+    assert_eq!(table, from_bytes(&bytes).unwrap());
+
+    // the same column layout as the named struct
+    let named_ids: Vec<NamedId> = ids
+        .iter()
+        .map(|id| NamedId {
+            counter: id.0,
+            peer: id.1,
+        })
+        .collect();
+    let named = NamedTable {
+        id_map: named_ids
+            .iter()
+            .cloned()
+            .map(|id| (id.peer * 10, id))
+            .collect(),
+        ids: named_ids,
+    };
+    assert_eq!(
+        to_vec(&ColumnarVec::<_, Vec<Id>>::new(&table.ids)).unwrap(),
+        to_vec(&ColumnarVec::<_, Vec<NamedId>>::new(&named.ids)).unwrap()
+    );
+    assert_eq!(
+        to_vec(&ColumnarMap::<_, _, BTreeMap<u32, Id>>::new(&table.id_map)).unwrap(),
+        to_vec(&ColumnarMap::<_, _, BTreeMap<u32, NamedId>>::new(
+            &named.id_map
+        ))
+        .unwrap()
+    );
+
+    let table = TupleTable(ids, 7);
+    let bytes = to_vec(&table).unwrap();
+    assert_eq!(table, from_bytes(&bytes).unwrap());
+    insta::assert_yaml_snapshot!(bytes);
+}
+
+#[test]
+fn flatten() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Id {
+        #[columnar(strategy = "Rle")]
+        peer: u64,
+        #[columnar(strategy = "DeltaRle")]
+        counter: u32,
+    }
+
+    #[columnar(vec, map, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Change {
+        #[columnar(flatten)]
+        id: Id,
+        #[columnar(strategy = "DeltaRle")]
+        lamport: u32,
+        #[columnar(flatten, optional, index = 0)]
+        parent: Id,
+    }
+
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct GenericChange {
+        id: Id,
+        #[columnar(strategy = "DeltaRle")]
+        lamport: u32,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct ChangeTable {
+        #[columnar(class = "vec")]
+        vec: Vec<Change>,
+        #[columnar(class = "map")]
+        map: BTreeMap<u32, Change>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct GenericChangeTable {
+        #[columnar(class = "vec")]
+        vec: Vec<GenericChange>,
+    }
+
+    let changes: Vec<Change> = (0..100)
+        .map(|i| Change {
+            id: Id {
+                peer: i / 30,
+                counter: i as u32,
+            },
+            lamport: 1000 + i as u32,
+            parent: Id {
+                peer: 0,
+                counter: i.saturating_sub(1) as u32,
+            },
+        })
+        .collect();
+    let table = ChangeTable {
+        map: changes.iter().cloned().map(|c| (c.lamport, c)).collect(),
+        vec: changes.clone(),
+    };
+    let bytes = to_vec(&table).unwrap();
+    assert_eq!(table, from_bytes(&bytes).unwrap());
+    insta::assert_yaml_snapshot!(bytes);
+
+    let generic = GenericChangeTable {
+        vec: changes
+            .into_iter()
+            .map(|c| GenericChange {
+                id: c.id,
+                lamport: c.lamport,
+            })
+            .collect(),
+    };
+    let generic_bytes = to_vec(&generic).unwrap();
+    let flatten_bytes = to_vec(&ColumnarVec::<_, Vec<Change>>::new(&table.vec)).unwrap();
+    // the parent ids take some bytes too
+    assert!(flatten_bytes.len() * 4 < generic_bytes.len());
+}
+
+#[test]
+fn flatten_nested_vec() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Op {
+        #[columnar(strategy = "DeltaRle")]
+        counter: u32,
+        #[columnar(strategy = "Rle")]
+        container: u64,
+    }
+
+    #[columnar(vec, map, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Change {
+        #[columnar(strategy = "DeltaRle")]
+        lamport: u32,
+        #[columnar(class = "vec", flatten)]
+        ops: Vec<Op>,
+        #[columnar(class = "vec", flatten, optional, index = 0)]
+        undo: Vec<Op>,
+    }
+
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct MiniTableChange {
+        #[columnar(strategy = "DeltaRle")]
+        lamport: u32,
+        #[columnar(class = "vec")]
+        ops: Vec<Op>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct ChangeTable {
+        #[columnar(class = "vec")]
+        vec: Vec<Change>,
+        #[columnar(class = "map")]
+        map: BTreeMap<u32, Change>,
+    }
+
+    let mut counter = 0;
+    let changes: Vec<Change> = (0..1000u32)
+        .map(|i| Change {
+            lamport: i,
+            ops: (0..2 + i / 500)
+                .map(|_| {
+                    counter += 1;
+                    Op {
+                        counter,
+                        container: 7,
+                    }
+                })
+                .collect(),
+            undo: vec![],
+        })
+        .collect();
+    let table = ChangeTable {
+        map: changes
+            .iter()
+            .take(10)
+            .cloned()
+            .map(|c| (c.lamport, c))
+            .collect(),
+        vec: changes.iter().take(10).cloned().collect(),
+    };
+    let bytes = to_vec(&table).unwrap();
+    assert_eq!(table, from_bytes(&bytes).unwrap());
+    insta::assert_yaml_snapshot!(bytes);
+
+    let mini_tables: Vec<MiniTableChange> = changes
+        .iter()
+        .map(|c| MiniTableChange {
+            lamport: c.lamport,
+            ops: c.ops.clone(),
+        })
+        .collect();
+    let mini_table_bytes =
+        to_vec(&ColumnarVec::<_, Vec<MiniTableChange>>::new(&mini_tables)).unwrap();
+    let nested_bytes = to_vec(&ColumnarVec::<_, Vec<Change>>::new(&changes)).unwrap();
+    assert!(nested_bytes.len() * 10 < mini_table_bytes.len());
+    assert_eq!(
+        changes,
+        from_bytes::<ColumnarVec<_, Vec<Change>>>(&nested_bytes)
+            .unwrap()
+            .into_vec()
+    );
+}
+
+#[test]
+fn list() {
+    #[columnar(vec, map, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Change {
+        #[columnar(list, strategy = "DeltaRle")]
+        deps: Vec<u64>,
+        #[columnar(list, strategy = "Rle")]
+        peers: Vec<String>,
+        #[columnar(list)]
+        names: Vec<String>,
+        #[columnar(list, optional, index = 0, strategy = "BoolRle")]
+        flags: Vec<bool>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Table {
+        #[columnar(class = "vec")]
+        vec: Vec<Change>,
+        #[columnar(class = "map")]
+        map: BTreeMap<u32, Change>,
+    }
+
+    let rows: Vec<Change> = (0..10u64)
+        .map(|i| Change {
+            deps: (0..i % 4).map(|d| 100 + i + d).collect(),
+            peers: (0..i % 3).map(|_| "alice".to_string()).collect(),
+            names: if i == 7 {
+                vec!["seven".to_string()]
+            } else {
+                vec![]
+            },
+            flags: (0..i % 2).map(|_| i > 5).collect(),
+        })
+        .collect();
+    let table = Table {
+        map: rows
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, a)| (i as u32, a))
+            .collect(),
+        vec: rows,
+    };
+    let bytes = to_vec(&table).unwrap();
+    let read_table = from_bytes(&bytes).unwrap();
+    assert_eq!(table, read_table);
+    insta::assert_yaml_snapshot!(bytes);
+}
+
+#[test]
+fn list_delta_rle_is_smaller() {
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct List {
+        #[columnar(list, strategy = "DeltaRle")]
+        deps: Vec<u64>,
+    }
+
+    #[columnar(vec, ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Generic {
+        deps: Vec<u64>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct ListTable {
+        #[columnar(class = "vec")]
+        rows: Vec<List>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct GenericTable {
+        #[columnar(class = "vec")]
+        rows: Vec<Generic>,
+    }
+
+    // each change depends on the previous change
+    let deps = (0..1000u64).map(|i| (i.saturating_sub(1)..i).map(|d| 100_000 + d).collect());
+    let list = ListTable {
+        rows: deps.clone().map(|deps| List { deps }).collect(),
+    };
+    let generic = GenericTable {
+        rows: deps.map(|deps| Generic { deps }).collect(),
+    };
+    let list_bytes = to_vec(&list).unwrap();
+    let generic_bytes = to_vec(&generic).unwrap();
+    assert!(list_bytes.len() * 10 < generic_bytes.len());
+    assert_eq!(list, from_bytes(&list_bytes).unwrap());
+}
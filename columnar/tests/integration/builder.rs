@@ -0,0 +1,49 @@
+use serde_columnar::{columnar, from_bytes, to_vec, ColumnarVec};
+
+use super::fixture::{rows, Row, RowColumnsBuilder};
+
+#[test]
+fn builder_is_same_as_columnar_vec() {
+    for n in [0, 1, 2, 100] {
+        let rows = rows(n);
+        let mut builder = RowColumnsBuilder::new();
+        for row in rows.iter() {
+            builder.push(row).unwrap();
+        }
+        assert_eq!(builder.len(), rows.len());
+        let bytes = builder.finish().unwrap();
+        assert_eq!(
+            bytes,
+            to_vec(&ColumnarVec::<_, Vec<Row>>::new(&rows)).unwrap()
+        );
+        let decoded = from_bytes::<ColumnarVec<_, Vec<Row>>>(&bytes)
+            .unwrap()
+            .into_vec();
+        assert_eq!(decoded, rows);
+    }
+}
+
+#[test]
+fn builder_of_lazy_rows() {
+    #[columnar(vec, ser, de, builder)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Change(
+        #[columnar(strategy = "DeltaRle")] u32,
+        #[columnar(strategy = "Rle")] String,
+    );
+
+    let mut builder = ChangeColumnsBuilder::new();
+    assert!(builder.is_empty());
+    for i in 0..1000 {
+        // the row is dropped right after pushing
+        builder.push(&Change(i, (i / 100).to_string())).unwrap();
+    }
+    let bytes = builder.finish().unwrap();
+    let rows: Vec<Change> = (0..1000)
+        .map(|i| Change(i, (i / 100).to_string()))
+        .collect();
+    assert_eq!(
+        bytes,
+        to_vec(&ColumnarVec::<_, Vec<Change>>::new(&rows)).unwrap()
+    );
+}
@@ -0,0 +1,74 @@
+use serde_columnar::{columnar, from_bytes, to_vec, ColumnarVec};
+
+use super::fixture::{rows, Row, RowColumns};
+
+#[test]
+fn columns_are_same_as_columnar_vec() {
+    for n in [0, 1, 2, 100] {
+        let rows = rows(n);
+        let bytes = to_vec(&ColumnarVec::<_, Vec<Row>>::new(&rows)).unwrap();
+        let columns: RowColumns = from_bytes(&bytes).unwrap();
+        assert_eq!(columns.len(), rows.len());
+        assert_eq!(
+            columns.delta_rle,
+            rows.iter().map(|r| r.delta_rle).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            columns.children,
+            rows.iter().map(|r| r.children.clone()).collect::<Vec<_>>()
+        );
+        assert_eq!(to_vec(&columns).unwrap(), bytes);
+        assert_eq!(columns.to_rows(), rows);
+        assert_eq!(RowColumns::from_rows(rows.clone()).into_rows(), rows);
+    }
+}
+
+#[test]
+fn columns_row() {
+    let rows = rows(10);
+    let columns: RowColumns = rows.iter().cloned().collect();
+    let row = columns.row(3).unwrap();
+    assert_eq!(row.rle, &rows[3].rle);
+    assert_eq!(row.nullable, &rows[3].nullable);
+    assert_eq!(row.id, &rows[3].id);
+    assert!(columns.row(10).is_none());
+}
+
+#[test]
+fn columns_of_tuple_struct() {
+    #[columnar(vec, ser, de, columns)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Change(
+        #[columnar(strategy = "DeltaRle")] u32,
+        #[columnar(strategy = "Rle")] String,
+    );
+
+    let rows: Vec<Change> = (0..1000)
+        .map(|i| Change(i, (i / 100).to_string()))
+        .collect();
+    let bytes = to_vec(&ColumnarVec::<_, Vec<Change>>::new(&rows)).unwrap();
+    let columns: ChangeColumns = from_bytes(&bytes).unwrap();
+    // scans one column without reassembling the rows
+    assert_eq!(columns.field_0.iter().sum::<u32>(), 999 * 1000 / 2);
+    assert_eq!(columns.row(500).unwrap().field_1, "5");
+    assert_eq!(columns.into_rows(), rows);
+}
+
+#[test]
+fn columns_of_different_lengths() {
+    #[columnar(vec, ser, de, columns)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Pair {
+        #[columnar(strategy = "DeltaRle")]
+        a: u32,
+        b: String,
+    }
+
+    let columns = PairColumns {
+        a: vec![1, 2, 3],
+        b: vec!["a".to_string()],
+    };
+    let bytes = to_vec(&columns).unwrap();
+    assert!(from_bytes::<PairColumns>(&bytes).is_err());
+    assert!(from_bytes::<ColumnarVec<_, Vec<Pair>>>(&bytes).is_ok());
+}
@@ -0,0 +1,117 @@
+use serde_columnar::{
+    columnar, compress, decompress, decompress_with_limit, from_bytes, from_bytes_projected,
+    to_vec, ColumnarVec, Compression,
+};
+
+use super::fixture::{rows, Child, Id, Row};
+
+/// The inner rows of the fixture [Row], whose columns are compressed
+#[columnar(vec, ser, de, projection)]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CompressedRow {
+    #[columnar(flatten, compress = "deflate")]
+    id: Id,
+    #[columnar(class = "vec", flatten, compress = "deflate")]
+    children: Vec<Child>,
+    #[columnar(optional, index = 0, flatten, compress = "deflate")]
+    parent: Id,
+}
+
+#[columnar(ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct Table<'a> {
+    #[columnar(class = "vec", compress = "deflate")]
+    rows: Vec<Row<'a>>,
+    #[columnar(compress = "deflate")]
+    name: String,
+    #[columnar(optional, index = 0, class = "vec", compress = "deflate")]
+    extra: Vec<Row<'a>>,
+}
+
+fn compressed_rows(n: u32) -> Vec<CompressedRow> {
+    rows(n)
+        .into_iter()
+        .map(|row| CompressedRow {
+            id: row.id,
+            children: row.children,
+            parent: row.parent,
+        })
+        .collect()
+}
+
+#[test]
+fn compress_bytes() {
+    let bytes: Vec<u8> = (0..10_000).map(|i| (i % 7) as u8).collect();
+    let compressed = compress(&bytes, Compression::Deflate).unwrap();
+    assert_eq!(compressed[0], 1);
+    assert!(compressed.len() < bytes.len() / 10);
+    assert_eq!(decompress(&compressed).unwrap(), bytes);
+
+    let stored = compress(&bytes, Compression::Stored).unwrap();
+    assert_eq!(stored[0], 0);
+    assert_eq!(decompress(&stored).unwrap(), bytes);
+
+    // the bytes are stored as they are if deflate does not make them smaller
+    let tiny = compress(&[1, 2, 3], Compression::Deflate).unwrap();
+    assert_eq!(tiny, [0, 1, 2, 3]);
+    assert_eq!(decompress(&tiny).unwrap(), [1, 2, 3].as_slice());
+
+    assert!(decompress(&[]).is_err());
+    // reserved for LZ4
+    assert!(decompress(&[2, 1, 2, 3]).is_err());
+    assert!(decompress(&[100, 1, 2, 3]).is_err());
+}
+
+#[test]
+fn decompress_limit() {
+    let bytes = vec![0u8; 10_000];
+    let compressed = compress(&bytes, Compression::Deflate).unwrap();
+    assert_eq!(decompress_with_limit(&compressed, 10_000).unwrap(), bytes);
+    assert!(decompress_with_limit(&compressed, 9_999).is_err());
+    assert!(decompress_with_limit(&compressed, 0).is_err());
+    let stored = compress(&[1, 2, 3], Compression::Stored).unwrap();
+    assert!(decompress_with_limit(&stored, 2).is_err());
+}
+
+#[test]
+fn compress_row_columns() {
+    for n in [0, 1, 2, 1000] {
+        let rows = compressed_rows(n);
+        let bytes = to_vec(&ColumnarVec::<_, Vec<CompressedRow>>::new(&rows)).unwrap();
+        let decoded: ColumnarVec<CompressedRow, Vec<CompressedRow>> = from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.into_vec(), rows);
+    }
+
+    // the compressed columns that are not selected are skipped
+    let rows = compressed_rows(1000);
+    let bytes = to_vec(&ColumnarVec::<_, Vec<CompressedRow>>::new(&rows)).unwrap();
+    let projected: ColumnarVec<CompressedRow, Vec<CompressedRow>> =
+        from_bytes_projected(&bytes, &["children"]).unwrap();
+    for (a, b) in projected.into_vec().iter().zip(rows.iter()) {
+        assert_eq!(a.children, b.children);
+        assert_eq!(a.parent, Id::default());
+    }
+}
+
+#[test]
+fn compress_table_fields() {
+    for n in [0, 1, 1000] {
+        let table = Table {
+            rows: rows(n),
+            name: "table".repeat(100),
+            extra: rows(n / 2),
+        };
+        let bytes = to_vec(&table).unwrap();
+        let decoded: Table = from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, table);
+    }
+
+    let rows = rows(1000);
+    let plain = to_vec(&ColumnarVec::<_, Vec<Row>>::new(&rows)).unwrap();
+    let table = Table {
+        rows,
+        name: String::new(),
+        extra: vec![],
+    };
+    assert!(to_vec(&table).unwrap().len() < plain.len() / 2);
+}
@@ -0,0 +1,154 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_columnar::{
+    compute, AnyRleDecoder, AnyRleEncoder, BoolRleDecoder, BoolRleEncoder, DeltaRleDecoder,
+    DeltaRleEncoder,
+};
+
+/// Runs, constant deltas in both directions and literal values
+fn values(n: i64) -> Vec<i64> {
+    (0..n)
+        .map(|i| match i % 100 {
+            0..=29 => 7,
+            30..=59 => i * 3,
+            60..=79 => 1000 - i,
+            _ => i * i % 17 - 8,
+        })
+        .collect()
+}
+
+fn value_counts<T: std::hash::Hash + Eq + Clone>(values: &[T]) -> HashMap<T, usize> {
+    let mut counts = HashMap::new();
+    for v in values {
+        *counts.entry(v.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[test]
+fn compute_delta_rle() {
+    for n in [0, 1, 2, 1000] {
+        let values = values(n);
+        let mut encoder = DeltaRleEncoder::new();
+        for &v in values.iter() {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        type D<'a> = DeltaRleDecoder<'a, i64>;
+        assert_eq!(compute::count::<D>(&bytes).unwrap(), values.len());
+        assert_eq!(
+            compute::sum::<D>(&bytes).unwrap(),
+            values.iter().map(|&v| v as i128).sum::<i128>()
+        );
+        assert_eq!(
+            compute::min::<D>(&bytes).unwrap(),
+            values.iter().min().copied()
+        );
+        assert_eq!(
+            compute::max::<D>(&bytes).unwrap(),
+            values.iter().max().copied()
+        );
+        assert_eq!(
+            compute::distinct_count::<D>(&bytes).unwrap(),
+            values.iter().collect::<HashSet<_>>().len()
+        );
+        assert_eq!(
+            compute::value_counts::<D>(&bytes).unwrap(),
+            value_counts(&values)
+        );
+    }
+}
+
+#[test]
+fn compute_rle() {
+    for n in [0, 1, 2, 1000] {
+        let values = values(n);
+        let mut encoder = AnyRleEncoder::<i64>::new();
+        for &v in values.iter() {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        type D<'a> = AnyRleDecoder<'a, i64>;
+        assert_eq!(compute::count::<D>(&bytes).unwrap(), values.len());
+        assert_eq!(
+            compute::sum::<D>(&bytes).unwrap(),
+            values.iter().map(|&v| v as i128).sum::<i128>()
+        );
+        assert_eq!(
+            compute::min::<D>(&bytes).unwrap(),
+            values.iter().min().copied()
+        );
+        assert_eq!(
+            compute::max::<D>(&bytes).unwrap(),
+            values.iter().max().copied()
+        );
+        assert_eq!(
+            compute::value_counts::<D>(&bytes).unwrap(),
+            value_counts(&values)
+        );
+
+        let strings: Vec<String> = values.iter().map(|v| (v % 5).to_string()).collect();
+        let mut encoder = AnyRleEncoder::<String>::new();
+        for v in strings.iter() {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        type S<'a> = AnyRleDecoder<'a, String>;
+        assert_eq!(
+            compute::max::<S>(&bytes).unwrap(),
+            strings.iter().max().cloned()
+        );
+        assert_eq!(
+            compute::distinct_count::<S>(&bytes).unwrap(),
+            strings.iter().collect::<HashSet<_>>().len()
+        );
+        assert_eq!(
+            compute::value_counts::<S>(&bytes).unwrap(),
+            value_counts(&strings)
+        );
+    }
+}
+
+#[test]
+fn compute_bool_rle() {
+    for n in [0, 1, 2, 1000] {
+        let values: Vec<bool> = values(n).iter().map(|v| v % 3 == 1).collect();
+        let mut encoder = BoolRleEncoder::new();
+        for &v in values.iter() {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        assert_eq!(
+            compute::count::<BoolRleDecoder>(&bytes).unwrap(),
+            values.len()
+        );
+        assert_eq!(
+            compute::sum::<BoolRleDecoder>(&bytes).unwrap(),
+            values.iter().filter(|&&v| v).count() as i128
+        );
+        assert_eq!(
+            compute::min::<BoolRleDecoder>(&bytes).unwrap(),
+            values.iter().min().copied()
+        );
+        assert_eq!(
+            compute::max::<BoolRleDecoder>(&bytes).unwrap(),
+            values.iter().max().copied()
+        );
+        assert_eq!(
+            compute::distinct_count::<BoolRleDecoder>(&bytes).unwrap(),
+            values.iter().collect::<HashSet<_>>().len()
+        );
+        assert_eq!(
+            compute::value_counts::<BoolRleDecoder>(&bytes).unwrap(),
+            value_counts(&values)
+        );
+    }
+}
+
+#[test]
+fn compute_sum_overflow() {
+    let mut encoder = DeltaRleEncoder::new();
+    encoder.append_run(i128::MAX, 2).unwrap();
+    let bytes = encoder.finish().unwrap();
+    assert!(compute::sum::<DeltaRleDecoder<i128>>(&bytes).is_err());
+}
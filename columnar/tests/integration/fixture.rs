@@ -0,0 +1,124 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use serde_columnar::columnar;
+
+#[columnar(vec, map, ser, de, builder, columns, projection, parallel)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Id {
+    #[columnar(strategy = "Rle")]
+    pub peer: u64,
+    #[columnar(strategy = "DeltaRle")]
+    pub counter: u32,
+}
+
+#[columnar(vec, ser, de, builder, columns, projection, parallel)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Child {
+    #[columnar(strategy = "DeltaRle")]
+    pub counter: u32,
+    #[columnar(strategy = "Rle")]
+    pub content: String,
+}
+
+#[columnar(vec, ser, de, builder, columns, projection, parallel)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Row<'a> {
+    #[columnar(strategy = "Rle")]
+    pub rle: String,
+    #[columnar(strategy = "BoolRle")]
+    pub bool_rle: bool,
+    #[columnar(strategy = "DeltaRle")]
+    pub delta_rle: u32,
+    #[columnar(strategy = "DeltaOfDelta")]
+    pub delta_of_delta: i64,
+    #[columnar(strategy = "Dict")]
+    pub dict: String,
+    #[columnar(strategy = "Auto")]
+    pub auto: u64,
+    #[columnar(strategy = "FloatXor")]
+    pub float_xor: f64,
+    #[columnar(strategy = "BitPack")]
+    pub bit_pack: u16,
+    #[columnar(strategy = "DeltaBitPack")]
+    pub delta_bit_pack: i32,
+    pub generic: (u8, String),
+    #[columnar(borrow)]
+    pub borrowed: Cow<'a, str>,
+    #[columnar(skip)]
+    pub skipped: u8,
+    #[columnar(class = "vec")]
+    pub ids: Vec<Id>,
+    #[columnar(class = "map")]
+    pub id_map: BTreeMap<u32, Id>,
+    #[columnar(nullable, strategy = "DeltaRle")]
+    pub nullable: Option<u32>,
+    #[columnar(nullable)]
+    pub nullable_generic: Option<String>,
+    #[columnar(list, strategy = "Rle")]
+    pub list: Vec<String>,
+    #[columnar(list, strategy = "DeltaRle")]
+    pub list_copy: Vec<u32>,
+    #[columnar(flatten)]
+    pub id: Id,
+    #[columnar(class = "vec", flatten)]
+    pub children: Vec<Child>,
+    #[columnar(optional, index = 0, strategy = "DeltaRle")]
+    pub lamport: u32,
+    #[columnar(flatten, optional, index = 1)]
+    pub parent: Id,
+}
+
+pub fn rows(n: u32) -> Vec<Row<'static>> {
+    (0..n)
+        .map(|i| Row {
+            rle: (i / 10).to_string(),
+            bool_rle: i % 3 == 0,
+            delta_rle: i * 2,
+            delta_of_delta: i as i64 * 1000,
+            dict: ["a", "b", "c"][i as usize % 3].to_string(),
+            auto: 7,
+            float_xor: i as f64 / 4.0,
+            bit_pack: (i % 17) as u16,
+            delta_bit_pack: -(i as i32),
+            generic: (i as u8, i.to_string()),
+            borrowed: Cow::Borrowed("borrowed"),
+            skipped: 0,
+            ids: (0..i % 3)
+                .map(|j| Id {
+                    peer: 1,
+                    counter: i + j,
+                })
+                .collect(),
+            id_map: (0..i % 2)
+                .map(|j| {
+                    (
+                        j,
+                        Id {
+                            peer: 2,
+                            counter: j,
+                        },
+                    )
+                })
+                .collect(),
+            nullable: (i % 4 != 0).then_some(i),
+            nullable_generic: (i % 5 == 0).then(|| i.to_string()),
+            list: (0..i % 3).map(|j| j.to_string()).collect(),
+            list_copy: (0..i % 4).collect(),
+            id: Id {
+                peer: i as u64 / 7,
+                counter: i,
+            },
+            children: (0..i % 3)
+                .map(|j| Child {
+                    counter: i * 3 + j,
+                    content: "child".to_string(),
+                })
+                .collect(),
+            lamport: 100 + i,
+            parent: Id {
+                peer: 0,
+                counter: i.saturating_sub(1),
+            },
+        })
+        .collect()
+}
@@ -0,0 +1,117 @@
+use std::{collections::BTreeMap, io};
+
+use serde_columnar::{
+    columnar, from_bytes, from_reader, from_reader_with_buffer, to_vec, to_writer,
+    to_writer_with_capacity, ColumnarError,
+};
+
+#[columnar(vec, map, ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct Change {
+    #[columnar(strategy = "DeltaRle")]
+    lamport: u32,
+    #[columnar(strategy = "Rle")]
+    peer: String,
+    message: String,
+}
+
+#[columnar(ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct ChangeTable {
+    #[columnar(class = "vec")]
+    vec: Vec<Change>,
+    #[columnar(class = "map")]
+    map: BTreeMap<u32, Change>,
+}
+
+fn table() -> ChangeTable {
+    let changes: Vec<Change> = (0..1000)
+        .map(|i| Change {
+            lamport: i,
+            peer: (i / 100).to_string(),
+            message: format!("change {}", i),
+        })
+        .collect();
+    ChangeTable {
+        map: changes.iter().cloned().map(|c| (c.lamport, c)).collect(),
+        vec: changes,
+    }
+}
+
+/// Records the size of each write
+#[derive(Default)]
+struct RecordWriter {
+    bytes: Vec<u8>,
+    writes: Vec<usize>,
+}
+
+impl io::Write for RecordWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes.extend_from_slice(buf);
+        self.writes.push(buf.len());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn to_writer_is_same_as_to_vec() {
+    let table = table();
+    let bytes = to_vec(&table).unwrap();
+    let writer = to_writer(Vec::new(), &table).unwrap();
+    assert_eq!(bytes, writer);
+    for capacity in [1, 7, 64, 1024] {
+        let writer = to_writer_with_capacity(RecordWriter::default(), &table, capacity).unwrap();
+        assert_eq!(bytes, writer.bytes);
+        assert!(writer.writes.len() > 1);
+    }
+}
+
+#[test]
+fn to_writer_io_error() {
+    #[derive(Debug)]
+    struct FailWriter;
+    impl io::Write for FailWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = to_writer_with_capacity(FailWriter, &table(), 16).unwrap_err();
+    assert!(matches!(err, ColumnarError::IOError(e) if e.kind() == io::ErrorKind::BrokenPipe));
+    let err = to_writer(FailWriter, &table()).unwrap_err();
+    assert!(matches!(err, ColumnarError::IOError(_)));
+}
+
+#[test]
+fn from_reader_roundtrip() {
+    let table = table();
+    let bytes = to_vec(&table).unwrap();
+    let read: ChangeTable = from_reader(bytes.as_slice()).unwrap();
+    assert_eq!(table, read);
+    assert_eq!(table, from_bytes::<ChangeTable>(&bytes).unwrap());
+
+    let mut buf = vec![0; bytes.len()];
+    let read: ChangeTable = from_reader_with_buffer(bytes.as_slice(), &mut buf).unwrap();
+    assert_eq!(table, read);
+}
+
+#[test]
+fn from_reader_with_small_buffer() {
+    let bytes = to_vec(&table()).unwrap();
+    let mut buf = vec![0; 16];
+    let err = from_reader_with_buffer::<_, ChangeTable>(bytes.as_slice(), &mut buf).unwrap_err();
+    assert!(matches!(err, ColumnarError::ColumnarDecodeError(_)));
+
+    let mut buf = vec![0; bytes.len()];
+    let err =
+        from_reader_with_buffer::<_, ChangeTable>(&bytes[..bytes.len() / 2], &mut buf).unwrap_err();
+    assert!(matches!(err, ColumnarError::IOError(e) if e.kind() == io::ErrorKind::UnexpectedEof));
+}
@@ -12,12 +12,27 @@ fn row_iterable_strategy() {
         _bool_rle: bool,
         #[columnar(strategy = "DeltaOfDelta")]
         _delta_of_delta: u32,
+        #[columnar(strategy = "Dict")]
+        _dict: String,
+        #[columnar(strategy = "Auto")]
+        _auto: u64,
+        #[columnar(strategy = "FloatXor")]
+        _float_xor: f64,
+        #[columnar(strategy = "BitPack")]
+        _bit_pack: u8,
+        #[columnar(strategy = "DeltaBitPack")]
+        _delta_bit_pack: u32,
     }
     let _ = IterableA {
         _rle: AnyRleIter::new(&[]),
         _delta_rle: DeltaRleIter::new(&[]),
         _bool_rle: BoolRleIter::new(&[]),
         _delta_of_delta: DeltaOfDeltaIter::new(&[0, 0]),
+        _dict: DictIter::new(&[0]).unwrap(),
+        _auto: AutoIter::new(&[1]).unwrap(),
+        _float_xor: FloatXorIter::new(&[0]).unwrap(),
+        _bit_pack: BitPackIter::new(&[0]).unwrap(),
+        _delta_bit_pack: DeltaBitPackIter::new(&[128, 1, 4, 0]).unwrap(),
     };
 }
 
@@ -50,3 +65,594 @@ fn iterable_without_strategy() {
     let ans: Vec<Row> = decode.a.map(|x| x.unwrap()).collect();
     assert_eq!(t.a, ans);
 }
+
+#[test]
+fn iterable_dict() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Row {
+        #[columnar(strategy = "Dict")]
+        peer: String,
+        #[columnar(strategy = "DeltaRle")]
+        counter: u32,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct PeerTable {
+        #[columnar(class = "vec", iter = "Row")]
+        rows: Vec<Row>,
+    }
+
+    let t = PeerTable {
+        rows: (0..20)
+            .map(|i| Row {
+                peer: ["alice", "bob", "carol"][i % 3].to_string(),
+                counter: i as u32,
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<PeerTable>(&encode).unwrap();
+    let ans: Vec<Row> = decode.rows.map(|x| x.unwrap()).collect();
+    assert_eq!(t.rows, ans);
+}
+
+#[test]
+fn iterable_auto() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Row {
+        #[columnar(strategy = "Auto")]
+        counter: u32,
+        #[columnar(strategy = "Auto")]
+        lamport: u64,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct CounterTable {
+        #[columnar(class = "vec", iter = "Row")]
+        rows: Vec<Row>,
+    }
+
+    let t = CounterTable {
+        rows: (0..20)
+            .map(|i| Row {
+                counter: i as u32,
+                lamport: (i / 4) as u64,
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<CounterTable>(&encode).unwrap();
+    let ans: Vec<Row> = decode.rows.map(|x| x.unwrap()).collect();
+    assert_eq!(t.rows, ans);
+}
+
+#[test]
+fn iterable_float_xor() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point {
+        #[columnar(strategy = "FloatXor")]
+        x: f64,
+        #[columnar(strategy = "FloatXor")]
+        y: f32,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq)]
+    struct PointTable {
+        #[columnar(class = "vec", iter = "Point")]
+        points: Vec<Point>,
+    }
+
+    let t = PointTable {
+        points: (0..20)
+            .map(|i| Point {
+                x: i as f64 * 0.5,
+                y: -1.25,
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<PointTable>(&encode).unwrap();
+    let ans: Vec<Point> = decode.points.map(|x| x.unwrap()).collect();
+    assert_eq!(t.points, ans);
+}
+
+#[test]
+fn iterable_bit_pack() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Row {
+        #[columnar(strategy = "BitPack")]
+        kind: u8,
+        #[columnar(strategy = "BitPack")]
+        pos: i32,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct RowTable {
+        #[columnar(class = "vec", iter = "Row")]
+        rows: Vec<Row>,
+    }
+
+    let t = RowTable {
+        rows: (0..300)
+            .map(|i| Row {
+                kind: (i * 7 % 5) as u8,
+                pos: -100 + i * 13 % 64,
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<RowTable>(&encode).unwrap();
+    let ans: Vec<Row> = decode.rows.map(|x| x.unwrap()).collect();
+    assert_eq!(t.rows, ans);
+}
+
+#[test]
+fn iterable_delta_bit_pack() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Change {
+        #[columnar(strategy = "DeltaBitPack")]
+        lamport: u32,
+        #[columnar(strategy = "DeltaBitPack")]
+        counter: i64,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct ChangeTable {
+        #[columnar(class = "vec", iter = "Change")]
+        changes: Vec<Change>,
+    }
+
+    let mut lamport = 0;
+    let t = ChangeTable {
+        changes: (0..300)
+            .map(|i| {
+                lamport += 1 + i % 5;
+                Change {
+                    lamport,
+                    counter: 100 - i as i64 * 3,
+                }
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<ChangeTable>(&encode).unwrap();
+    let ans: Vec<Change> = decode.changes.map(|x| x.unwrap()).collect();
+    assert_eq!(t.changes, ans);
+}
+
+#[test]
+fn iterable_nullable() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Row {
+        #[columnar(nullable, strategy = "DeltaRle")]
+        counter: Option<u32>,
+        #[columnar(nullable, strategy = "Rle")]
+        peer: Option<String>,
+        #[columnar(nullable)]
+        name: Option<String>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct RowTable {
+        #[columnar(class = "vec", iter = "Row")]
+        rows: Vec<Row>,
+    }
+
+    let t = RowTable {
+        rows: (0..20)
+            .map(|i| Row {
+                counter: (i % 3 != 0).then_some(i),
+                peer: (i > 10).then(|| "bob".to_string()),
+                name: (i % 7 == 0).then(|| i.to_string()),
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<RowTable>(&encode).unwrap();
+    let ans: Vec<Row> = decode.rows.map(|x| x.unwrap()).collect();
+    assert_eq!(t.rows, ans);
+}
+
+#[test]
+fn iterable_tuple_struct() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Id(
+        #[columnar(strategy = "DeltaRle")] u64,
+        #[columnar(strategy = "Rle")] String,
+    );
+
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Counter(#[columnar(strategy = "DeltaRle")] u32);
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct IdTable(
+        #[columnar(class = "vec", iter = "Id")] Vec<Id>,
+        #[columnar(class = "vec", iter = "Counter")] Vec<Counter>,
+    );
+
+    let t = IdTable(
+        (0..20).map(|i| Id(i * 2, (i / 5).to_string())).collect(),
+        (0..20).map(Counter).collect(),
+    );
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<IdTable>(&encode).unwrap();
+    let ids: Vec<Id> = decode.0.map(|x| x.unwrap()).collect();
+    let counters: Vec<Counter> = decode.1.map(|x| x.unwrap()).collect();
+    assert_eq!(t.0, ids);
+    assert_eq!(t.1, counters);
+}
+
+#[test]
+fn iterable_flatten() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Id {
+        #[columnar(strategy = "Rle")]
+        peer: u64,
+        #[columnar(strategy = "DeltaRle")]
+        counter: u32,
+    }
+
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Change {
+        #[columnar(flatten)]
+        id: Id,
+        #[columnar(strategy = "Rle")]
+        message: String,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct ChangeTable {
+        #[columnar(class = "vec", iter = "Change")]
+        changes: Vec<Change>,
+    }
+
+    let t = ChangeTable {
+        changes: (0..20)
+            .map(|i| Change {
+                id: Id {
+                    peer: i / 7,
+                    counter: i as u32,
+                },
+                message: (i / 3).to_string(),
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<ChangeTable>(&encode).unwrap();
+    let ans: Vec<Change> = decode.changes.map(|x| x.unwrap()).collect();
+    assert_eq!(t.changes, ans);
+}
+
+#[test]
+fn iterable_flatten_nested_vec() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Op {
+        #[columnar(strategy = "DeltaRle")]
+        counter: u32,
+        #[columnar(strategy = "Rle")]
+        content: String,
+    }
+
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Change {
+        #[columnar(strategy = "DeltaRle")]
+        lamport: u32,
+        #[columnar(class = "vec", flatten)]
+        ops: Vec<Op>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct ChangeTable {
+        #[columnar(class = "vec", iter = "Change")]
+        changes: Vec<Change>,
+    }
+
+    let t = ChangeTable {
+        changes: (0..20)
+            .map(|i| Change {
+                lamport: i,
+                ops: (0..i % 3)
+                    .map(|j| Op {
+                        counter: i * 3 + j,
+                        content: (i / 5).to_string(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<ChangeTable>(&encode).unwrap();
+    let ans: Vec<Change> = decode.changes.map(|x| x.unwrap()).collect();
+    assert_eq!(t.changes, ans);
+}
+
+#[test]
+fn iterable_list() {
+    use serde_columnar::{
+        iterable::{DeltaRleIter, ListIter},
+        ColumnTrait, DeltaRleColumn, ListColumn,
+    };
+
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Change {
+        #[columnar(list, strategy = "DeltaRle")]
+        deps: Vec<u32>,
+        #[columnar(list, strategy = "Rle")]
+        peers: Vec<String>,
+        #[columnar(list)]
+        names: Vec<String>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct ChangeTable {
+        #[columnar(class = "vec", iter = "Change")]
+        changes: Vec<Change>,
+    }
+
+    let t = ChangeTable {
+        changes: (0..20)
+            .map(|i| Change {
+                deps: (0..i % 3).map(|d| i + d).collect(),
+                peers: (0..i % 2).map(|_| "bob".to_string()).collect(),
+                names: if i % 7 == 0 {
+                    vec![i.to_string()]
+                } else {
+                    vec![]
+                },
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let decode = serde_columnar::iter_from_bytes::<ChangeTable>(&encode).unwrap();
+    let ans: Vec<Change> = decode.changes.map(|x| x.unwrap()).collect();
+    assert_eq!(t.changes, ans);
+
+    // iterate the elements of each list without collecting them
+    let lists: Vec<Vec<u32>> = t.changes.iter().map(|c| c.deps.clone()).collect();
+    let bytes = ListColumn::<DeltaRleColumn<u32>>::from(lists.clone())
+        .encode()
+        .unwrap();
+    let mut iter = ListIter::<DeltaRleIter<u32>>::new(&bytes).unwrap();
+    for (i, list) in lists.iter().enumerate() {
+        let mut values = iter.next_list().unwrap().unwrap();
+        if i % 2 == 0 {
+            // the unconsumed elements are skipped by the next call
+            assert_eq!(values.next().transpose().unwrap(), list.first().copied());
+        } else {
+            let values: Vec<u32> = values.map(|v| v.unwrap()).collect();
+            assert_eq!(&values, list);
+        }
+    }
+    assert!(iter.next_list().unwrap().is_none());
+}
+
+#[test]
+fn iterable_nth_and_skip_rows() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Id {
+        #[columnar(strategy = "Rle")]
+        peer: u64,
+        #[columnar(strategy = "DeltaRle")]
+        counter: u32,
+    }
+
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Change {
+        #[columnar(strategy = "Rle")]
+        peer: String,
+        #[columnar(strategy = "DeltaRle")]
+        lamport: u32,
+        #[columnar(strategy = "BoolRle")]
+        deleted: bool,
+        #[columnar(strategy = "DeltaOfDelta")]
+        timestamp: i64,
+        #[columnar(strategy = "Dict")]
+        author: String,
+        #[columnar(strategy = "Auto")]
+        counter: u64,
+        #[columnar(strategy = "FloatXor")]
+        weight: f64,
+        message: String,
+        #[columnar(nullable, strategy = "DeltaRle")]
+        parent: Option<u32>,
+        #[columnar(list, strategy = "DeltaRle")]
+        deps: Vec<u32>,
+        #[columnar(flatten)]
+        id: Id,
+        #[columnar(class = "vec", flatten)]
+        children: Vec<Id>,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq)]
+    struct ChangeTable {
+        #[columnar(class = "vec", iter = "Change")]
+        changes: Vec<Change>,
+    }
+
+    let t = ChangeTable {
+        changes: (0..2000u32)
+            .map(|i| Change {
+                peer: (i / 300).to_string(),
+                lamport: if i % 100 < 50 { i } else { i * 3 },
+                deleted: i % 13 < 4,
+                timestamp: 1_700_000_000 + i as i64 * 60 + (i % 97 == 0) as i64,
+                author: ["alice", "bob", "carol"][i as usize / 700].to_string(),
+                counter: i as u64 / 3,
+                weight: i as f64 / 8.0,
+                message: format!("change {}", i),
+                parent: (i % 5 != 0).then_some(i / 2),
+                deps: (0..i % 3).collect(),
+                id: Id {
+                    peer: i as u64 % 3,
+                    counter: i,
+                },
+                children: (0..i % 4)
+                    .map(|j| Id {
+                        peer: 1,
+                        counter: i + j,
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    for n in [0, 1, 49, 50, 777, 1999, 2000, 5000] {
+        let mut changes = serde_columnar::iter_from_bytes::<ChangeTable>(&encode)
+            .unwrap()
+            .changes;
+        assert_eq!(
+            changes.nth(n).transpose().unwrap().as_ref(),
+            t.changes.get(n)
+        );
+    }
+
+    let mut changes = serde_columnar::iter_from_bytes::<ChangeTable>(&encode)
+        .unwrap()
+        .changes;
+    let mut expected = t.changes.iter();
+    for step in [3, 0, 100, 250, 1, 999] {
+        changes.skip_rows(step).unwrap();
+        if step > 0 {
+            expected.nth(step - 1);
+        }
+        assert_eq!(
+            changes.next().transpose().unwrap().as_ref(),
+            expected.next()
+        );
+    }
+}
+
+#[test]
+fn iterable_filter_by_runs() {
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Id {
+        #[columnar(strategy = "Rle")]
+        peer: u64,
+        #[columnar(strategy = "DeltaRle")]
+        counter: u32,
+    }
+
+    #[columnar(vec, ser, de, iterable)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Change {
+        #[columnar(strategy = "Rle")]
+        peer: String,
+        #[columnar(strategy = "DeltaRle")]
+        lamport: u32,
+        #[columnar(strategy = "BoolRle")]
+        deleted: bool,
+        #[columnar(strategy = "DeltaOfDelta")]
+        timestamp: i64,
+        message: String,
+        #[columnar(nullable, strategy = "DeltaRle")]
+        parent: Option<u32>,
+        #[columnar(list, strategy = "DeltaRle")]
+        deps: Vec<u32>,
+        #[columnar(flatten)]
+        id: Id,
+    }
+
+    #[columnar(ser, de)]
+    #[derive(Debug, PartialEq)]
+    struct ChangeTable {
+        #[columnar(class = "vec", iter = "Change")]
+        changes: Vec<Change>,
+    }
+
+    let t = ChangeTable {
+        changes: (0..2000u32)
+            .map(|i| Change {
+                peer: ["alice", "bob", "carol", "bob"][(i / 150 % 4) as usize].to_string(),
+                lamport: if i % 100 < 50 { i / 20 } else { i * 7 % 31 },
+                deleted: i % 13 < 4,
+                timestamp: 1_700_000_000 + i as i64 * 60,
+                message: format!("change {}", i),
+                parent: (i % 5 != 0).then_some(i / 2),
+                deps: (0..i % 3).collect(),
+                id: Id {
+                    peer: i as u64 % 3,
+                    counter: i,
+                },
+            })
+            .collect(),
+    };
+    let encode = serde_columnar::to_vec(&t).unwrap();
+    let changes = || {
+        serde_columnar::iter_from_bytes::<ChangeTable>(&encode)
+            .unwrap()
+            .changes
+    };
+
+    let mut evaluated = 0;
+    let bob: Vec<Change> = changes()
+        .filter_by_peer(|peer| {
+            evaluated += 1;
+            peer == "bob"
+        })
+        .map(|c| c.unwrap())
+        .collect();
+    assert_eq!(
+        bob,
+        t.changes
+            .iter()
+            .filter(|c| c.peer == "bob")
+            .cloned()
+            .collect::<Vec<_>>()
+    );
+    // once per run of `peer`
+    assert_eq!(evaluated, 2000 / 150 + 1);
+
+    let lamport: Vec<Change> = changes()
+        .filter_by_lamport(|lamport| lamport % 3 == 0)
+        .map(|c| c.unwrap())
+        .collect();
+    assert_eq!(
+        lamport,
+        t.changes
+            .iter()
+            .filter(|c| c.lamport % 3 == 0)
+            .cloned()
+            .collect::<Vec<_>>()
+    );
+
+    let deleted: Vec<Change> = changes()
+        .filter_by_deleted(|deleted| *deleted)
+        .map(|c| c.unwrap())
+        .collect();
+    assert_eq!(
+        deleted,
+        t.changes
+            .iter()
+            .filter(|c| c.deleted)
+            .cloned()
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(changes().filter_by_peer(|_| false).count(), 0);
+}
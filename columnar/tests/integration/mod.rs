@@ -1,6 +1,20 @@
 pub mod attribute;
+pub mod builder;
+pub mod columns;
 pub mod compatible;
+#[cfg(feature = "deflate")]
+pub mod compress;
+pub mod compute;
+pub mod fixture;
 pub mod fuzz_debug;
+pub mod io;
 pub mod iterable;
+pub mod paged;
+pub mod parallel;
+pub mod projection;
+pub mod runs;
+pub mod stats;
 #[cfg(feature = "bench")]
 pub mod test_size;
+pub mod unknown;
+pub mod view;
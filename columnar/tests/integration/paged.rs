@@ -0,0 +1,116 @@
+use serde_columnar::{
+    columnar, from_bytes, to_vec, ColumnarPage, ColumnarPages, ColumnarVec, PagedColumnarVec,
+};
+
+use super::fixture::{rows, Row};
+
+#[columnar(ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct Table<'a> {
+    #[columnar(class = "vec", page_rows = 100)]
+    rows: Vec<Row<'a>>,
+    #[columnar(optional, index = 0, class = "vec", page_rows = 7)]
+    extra: Vec<Row<'a>>,
+}
+
+#[columnar(ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct UnpagedTable<'a> {
+    #[columnar(class = "vec")]
+    rows: Vec<Row<'a>>,
+}
+
+/// [UnpagedTable] after its field is paged
+#[columnar(ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct RepagedTable<'a> {
+    #[columnar(class = "vec", page_rows = 100)]
+    rows: Vec<Row<'a>>,
+}
+
+#[test]
+fn paged_table_round_trip() {
+    for n in [0, 1, 99, 100, 101, 1050] {
+        let table = Table {
+            rows: rows(n),
+            extra: rows(n / 3),
+        };
+        let bytes = to_vec(&table).unwrap();
+        let decoded: Table = from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, table);
+    }
+}
+
+#[test]
+fn unpaged_layout_is_unchanged() {
+    let table = UnpagedTable { rows: rows(500) };
+    let bytes = to_vec(&table).unwrap();
+    let decoded: UnpagedTable = from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, table);
+}
+
+#[test]
+fn unpaged_bytes_readable_after_paging() {
+    for n in [0, 1, 500] {
+        let table = UnpagedTable { rows: rows(n) };
+        let bytes = to_vec(&table).unwrap();
+        let decoded: RepagedTable = from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.rows, table.rows);
+    }
+
+    let rows = rows(10);
+    let bytes = to_vec(&ColumnarVec::<_, Vec<Row>>::new(&rows)).unwrap();
+    let decoded: PagedColumnarVec<Row, Vec<Row>> = from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.into_vec(), rows);
+    // the unpaged rows have no pages
+    assert!(from_bytes::<ColumnarPages<Row>>(&bytes).is_err());
+}
+
+#[test]
+fn pages_decoded_on_demand() {
+    let rows = rows(1050);
+    let bytes = to_vec(&PagedColumnarVec::<_, Vec<Row>>::new(&rows, 64)).unwrap();
+    let pages: ColumnarPages<Row> = from_bytes(&bytes).unwrap();
+    assert_eq!(pages.len(), rows.len());
+    assert_eq!(pages.pages().len(), rows.len().div_ceil(64));
+    assert_eq!(pages.find_row(0), Some((0, 0)));
+    assert_eq!(pages.find_row(64), Some((1, 0)));
+    assert_eq!(pages.find_row(1049), Some((16, 25)));
+    assert_eq!(pages.find_row(1050), None);
+    for k in (0..rows.len()).step_by(37) {
+        assert_eq!(pages.get(k).unwrap().as_ref(), Some(&rows[k]));
+    }
+    assert_eq!(pages.get(rows.len()).unwrap(), None);
+    // every page is decoded without the pages before it
+    for (i, page) in pages.pages().iter().enumerate().rev() {
+        assert_eq!(
+            page.decode::<Row>().unwrap(),
+            rows[i * 64..(i * 64 + 64).min(1050)]
+        );
+    }
+    assert_eq!(pages.decode_page(1).unwrap().unwrap(), rows[64..128]);
+    assert_eq!(pages.decode_page(17).unwrap(), None);
+
+    let decoded: PagedColumnarVec<Row, Vec<Row>> = from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.into_vec(), rows);
+}
+
+#[test]
+fn empty_pages() {
+    let rows: Vec<Row> = vec![];
+    let bytes = to_vec(&PagedColumnarVec::<_, Vec<Row>>::new(&rows, 10)).unwrap();
+    let pages: ColumnarPages<Row> = from_bytes(&bytes).unwrap();
+    assert!(pages.is_empty());
+    assert_eq!(pages.find_row(0), None);
+    assert_eq!(pages.get(0).unwrap(), None);
+}
+
+#[test]
+fn corrupt_page_rows() {
+    let page = |rows| ColumnarPage { rows, bytes: &[] };
+    let bytes = to_vec(&(u32::MAX as usize, vec![page(usize::MAX), page(1)])).unwrap();
+    assert!(from_bytes::<ColumnarPages<Row>>(&bytes).is_err());
+    let bytes = to_vec(&(u32::MAX as usize, vec![page(usize::MAX), page(0)])).unwrap();
+    let pages: ColumnarPages<Row> = from_bytes(&bytes).unwrap();
+    assert_eq!(pages.len(), usize::MAX);
+}
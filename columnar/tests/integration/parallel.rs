@@ -0,0 +1,46 @@
+use serde_columnar::{columnar, from_bytes, from_bytes_projected, to_vec, ColumnarVec};
+
+use super::fixture::{rows, Row, RowColumns};
+
+#[test]
+fn parallel_same_as_sequential() {
+    for n in [0, 1, 2, 10_000] {
+        let rows = rows(n);
+        // the columns are always serialized one after another
+        let sequential = to_vec(&RowColumns::from_rows(rows.clone())).unwrap();
+        let bytes = to_vec(&ColumnarVec::<_, Vec<Row>>::new(&rows)).unwrap();
+        assert_eq!(bytes, sequential);
+        let decoded: ColumnarVec<Row, Vec<Row>> = from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.into_vec(), rows);
+    }
+}
+
+#[test]
+fn parallel_projection() {
+    #[columnar(vec, ser, de, parallel)]
+    #[derive(Debug, Clone, PartialEq)]
+    struct Group<'a> {
+        #[columnar(strategy = "DeltaRle")]
+        id: u32,
+        #[columnar(class = "vec")]
+        rows: Vec<Row<'a>>,
+    }
+
+    let groups: Vec<Group> = (0..10)
+        .map(|id| Group {
+            id,
+            rows: rows(id * 10),
+        })
+        .collect();
+    let bytes = to_vec(&ColumnarVec::<_, Vec<Group>>::new(&groups)).unwrap();
+    // the projection of the caller applies to the rows decoded on the thread pool
+    let decoded: ColumnarVec<Group, Vec<Group>> = from_bytes_projected(&bytes, &["dict"]).unwrap();
+    for (decoded, group) in decoded.into_vec().iter().zip(groups.iter()) {
+        assert_eq!(decoded.id, group.id);
+        assert_eq!(decoded.rows.len(), group.rows.len());
+        for (a, b) in decoded.rows.iter().zip(group.rows.iter()) {
+            assert_eq!(a.dict, b.dict);
+            assert_eq!(a.delta_rle, 0);
+        }
+    }
+}
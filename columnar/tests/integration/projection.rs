@@ -0,0 +1,103 @@
+use serde_columnar::{columnar, from_bytes, from_bytes_projected, to_vec};
+
+use super::fixture::{rows, Row};
+
+#[columnar(ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct History<'a> {
+    #[columnar(class = "vec")]
+    rows: Vec<Row<'a>>,
+}
+
+fn history() -> History<'static> {
+    History { rows: rows(100) }
+}
+
+#[test]
+fn projection_decodes_selected_fields() {
+    let history = history();
+    let bytes = to_vec(&history).unwrap();
+    let projected: History = from_bytes_projected(&bytes, &["delta_of_delta", "dict"]).unwrap();
+    let expected: Vec<Row> = history
+        .rows
+        .iter()
+        .map(|r| Row {
+            delta_of_delta: r.delta_of_delta,
+            dict: r.dict.clone(),
+            ..Default::default()
+        })
+        .collect();
+    assert_eq!(projected.rows, expected);
+}
+
+#[test]
+fn projection_of_composite_fields() {
+    let history = history();
+    let bytes = to_vec(&history).unwrap();
+    let projected: History = from_bytes_projected(
+        &bytes,
+        &["nullable", "list_copy", "id", "ids", "children", "lamport"],
+    )
+    .unwrap();
+    // the inner rows of the selected fields are decoded in full
+    let expected: Vec<Row> = history
+        .rows
+        .iter()
+        .map(|r| Row {
+            nullable: r.nullable,
+            list_copy: r.list_copy.clone(),
+            id: r.id.clone(),
+            ids: r.ids.clone(),
+            children: r.children.clone(),
+            lamport: r.lamport,
+            ..Default::default()
+        })
+        .collect();
+    assert_eq!(projected.rows, expected);
+}
+
+#[test]
+fn projection_without_selected_fields() {
+    let history = history();
+    let bytes = to_vec(&history).unwrap();
+    let projected: History = from_bytes_projected(&bytes, &["unknown"]).unwrap();
+    // the first field is still decoded to count the rows
+    let expected: Vec<Row> = history
+        .rows
+        .iter()
+        .map(|r| Row {
+            rle: r.rle.clone(),
+            ..Default::default()
+        })
+        .collect();
+    assert_eq!(projected.rows, expected);
+}
+
+#[test]
+fn projection_of_optional_field() {
+    let history = history();
+    let bytes = to_vec(&history).unwrap();
+    let projected: History = from_bytes_projected(&bytes, &["lamport"]).unwrap();
+    // the rows are counted by the first field that is not optional
+    let expected: Vec<Row> = history
+        .rows
+        .iter()
+        .map(|r| Row {
+            rle: r.rle.clone(),
+            lamport: r.lamport,
+            ..Default::default()
+        })
+        .collect();
+    assert_eq!(projected.rows, expected);
+}
+
+#[test]
+fn projection_is_scoped() {
+    let history = history();
+    let bytes = to_vec(&history).unwrap();
+    let _: History = from_bytes_projected(&bytes, &["dict"]).unwrap();
+    let decoded: History = from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, history);
+    let projected: History = from_bytes_projected(&bytes, &[]).unwrap();
+    assert_eq!(projected.rows.len(), history.rows.len());
+}
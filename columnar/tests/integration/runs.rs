@@ -0,0 +1,128 @@
+use serde_columnar::{
+    iterable::AnyRleIter, AnyRleDecoder, AnyRleEncoder, BoolRleDecoder, BoolRleEncoder,
+    DeltaRleDecoder, DeltaRleEncoder, RleRun,
+};
+
+/// `(value, len)` of runs, lone values and zero-length runs
+fn runs() -> Vec<(u32, usize)> {
+    (0..300u32)
+        .map(|i| match i % 10 {
+            0..=3 => (i, 1),
+            4 => (i / 10, 0),
+            5 => (7, 2),
+            _ => (i % 3, i as usize),
+        })
+        .collect()
+}
+
+fn expand<T: Clone>(runs: impl IntoIterator<Item = RleRun<T>>) -> Vec<T> {
+    let mut values = Vec::new();
+    for run in runs {
+        match run {
+            RleRun::Run { value, len } => {
+                assert!(len > 0);
+                values.extend(std::iter::repeat_n(value, len));
+            }
+            RleRun::Literal(literal) => {
+                assert!(!literal.is_empty());
+                values.extend(literal);
+            }
+        }
+    }
+    values
+}
+
+#[test]
+fn append_run_is_byte_identical() {
+    let mut rle = AnyRleEncoder::<u32>::new();
+    let mut rle_runs = AnyRleEncoder::<u32>::new();
+    let mut bool_rle = BoolRleEncoder::new();
+    let mut bool_rle_runs = BoolRleEncoder::new();
+    let mut delta_rle = DeltaRleEncoder::new();
+    let mut delta_rle_runs = DeltaRleEncoder::new();
+    for (value, len) in runs() {
+        for _ in 0..len {
+            rle.append(value).unwrap();
+            bool_rle.append(value % 2 == 0).unwrap();
+            delta_rle.append(value).unwrap();
+        }
+        rle_runs.append_run(value, len).unwrap();
+        bool_rle_runs.append_run(value % 2 == 0, len).unwrap();
+        delta_rle_runs.append_run(value, len).unwrap();
+    }
+    assert_eq!(rle.finish().unwrap(), rle_runs.finish().unwrap());
+    assert_eq!(bool_rle.finish().unwrap(), bool_rle_runs.finish().unwrap());
+    assert_eq!(
+        delta_rle.finish().unwrap(),
+        delta_rle_runs.finish().unwrap()
+    );
+}
+
+#[test]
+fn decode_runs() {
+    let values: Vec<u32> = runs()
+        .into_iter()
+        .flat_map(|(value, len)| std::iter::repeat_n(value, len))
+        .collect();
+    let mut rle = AnyRleEncoder::<u32>::new();
+    let mut bool_rle = BoolRleEncoder::new();
+    for &v in values.iter() {
+        rle.append(v).unwrap();
+        bool_rle.append(v % 2 == 0).unwrap();
+    }
+    let rle = rle.finish().unwrap();
+    let bool_rle = bool_rle.finish().unwrap();
+
+    let runs: Vec<_> = AnyRleDecoder::<u32>::new(&rle)
+        .runs()
+        .map(|r| r.unwrap())
+        .collect();
+    assert!(runs.iter().any(|r| matches!(r, RleRun::Literal(_))));
+    assert!(runs.iter().any(|r| matches!(r, RleRun::Run { .. })));
+    assert_eq!(expand(runs), values);
+    let mut iter = AnyRleIter::<u32>::new(&rle);
+    assert_eq!(expand(iter.runs().map(|r| r.unwrap())), values);
+    let runs: Vec<_> = BoolRleDecoder::new(&bool_rle)
+        .runs()
+        .map(|r| r.unwrap())
+        .collect();
+    assert!(runs
+        .windows(2)
+        .all(|w| matches!((&w[0], &w[1]), (RleRun::Run { value: a, .. }, RleRun::Run { value: b, .. }) if a != b)));
+    assert_eq!(
+        expand(runs),
+        values.iter().map(|v| v % 2 == 0).collect::<Vec<_>>()
+    );
+
+    // the first run is the rest of the current one
+    for skip in [1, 3, 100, 1000] {
+        let mut decoder = AnyRleDecoder::<u32>::new(&rle);
+        let mut iter = AnyRleIter::<u32>::new(&rle);
+        let mut bool_decoder = BoolRleDecoder::new(&bool_rle);
+        for _ in 0..skip {
+            decoder.next().unwrap().unwrap();
+            iter.next().unwrap().unwrap();
+            bool_decoder.next().unwrap().unwrap();
+        }
+        assert_eq!(expand(decoder.runs().map(|r| r.unwrap())), values[skip..]);
+        assert_eq!(expand(iter.runs().map(|r| r.unwrap())), values[skip..]);
+        assert_eq!(
+            expand(bool_decoder.runs().map(|r| r.unwrap())),
+            values[skip..]
+                .iter()
+                .map(|v| v % 2 == 0)
+                .collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn delta_rle_append_run() {
+    let mut encoder = DeltaRleEncoder::new();
+    encoder.append_run(5u32, 3).unwrap();
+    encoder.append_run(2u32, 0).unwrap();
+    encoder.append_run(2u32, 2).unwrap();
+    let bytes = encoder.finish().unwrap();
+    let values: Vec<u32> = DeltaRleDecoder::new(&bytes).decode().unwrap();
+    assert_eq!(values, vec![5, 5, 5, 2, 2]);
+}
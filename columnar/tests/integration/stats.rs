@@ -0,0 +1,169 @@
+use serde_columnar::{
+    columnar, from_bytes, to_vec, AnyRleDecoder, AnyRleEncoder, BoolRleEncoder, ColumnStats,
+    ColumnarVec, DeltaOfDeltaDecoder, DeltaOfDeltaEncoder, DeltaRleDecoder, DeltaRleEncoder,
+};
+
+#[columnar(vec, ser, de)]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Event {
+    #[columnar(strategy = "Rle")]
+    name: String,
+    #[columnar(strategy = "DeltaOfDelta", stats)]
+    timestamp: i64,
+    #[columnar(strategy = "DeltaRle", stats)]
+    counter: u32,
+    #[columnar(strategy = "BoolRle", stats)]
+    deleted: bool,
+}
+
+#[test]
+fn delta_of_delta_stats() {
+    let timestamps: Vec<i64> = (0..1000).map(|i| 1_700_000_000 + i * 60 - i % 7).collect();
+    let mut encoder = DeltaOfDeltaEncoder::with_stats();
+    for &t in timestamps.iter() {
+        encoder.append(t).unwrap();
+    }
+    let bytes = encoder.finish_with_stats().unwrap();
+    let stats = ColumnStats::read(&bytes).unwrap();
+    assert_eq!(
+        stats,
+        ColumnStats {
+            row_count: 1000,
+            null_count: 0,
+            min: timestamps.iter().min().map(|&t| t as i128),
+            max: timestamps.iter().max().map(|&t| t as i128),
+        }
+    );
+    assert!(stats.may_contain(1_700_000_000..1_700_000_001));
+    assert!(stats.may_contain(..=1_700_000_000));
+    assert!(!stats.may_contain(..1_700_000_000));
+    assert!(!stats.may_contain(1_800_000_000..));
+
+    let (_, payload) = ColumnStats::split(&bytes).unwrap();
+    let values: Vec<i64> = DeltaOfDeltaDecoder::new(payload).unwrap().decode().unwrap();
+    assert_eq!(values, timestamps);
+}
+
+#[test]
+fn rle_stats() {
+    let mut encoder = DeltaRleEncoder::with_stats();
+    encoder.append_run(5u32, 10).unwrap();
+    encoder.append(3u32).unwrap();
+    encoder.append(8u32).unwrap();
+    assert_eq!(encoder.stats().unwrap().row_count, 12);
+    let bytes = encoder.finish_with_stats().unwrap();
+    let (stats, payload) = ColumnStats::split(&bytes).unwrap();
+    assert_eq!(
+        (stats.row_count, stats.min, stats.max),
+        (12, Some(3), Some(8))
+    );
+    let values: Vec<u32> = DeltaRleDecoder::new(payload).decode().unwrap();
+    assert_eq!(values.len(), 12);
+
+    let mut encoder = AnyRleEncoder::<i32>::with_stats();
+    for v in [4, 4, 4, -2, 9, 1, 1] {
+        encoder.append(v).unwrap();
+    }
+    let bytes = encoder.finish_with_stats().unwrap();
+    let (stats, payload) = ColumnStats::split(&bytes).unwrap();
+    assert_eq!(
+        (stats.row_count, stats.min, stats.max),
+        (7, Some(-2), Some(9))
+    );
+    let values = AnyRleDecoder::<i32>::new(payload).decode().unwrap();
+    assert_eq!(values, vec![4, 4, 4, -2, 9, 1, 1]);
+
+    let mut encoder = BoolRleEncoder::with_stats();
+    encoder.append_run(true, 3).unwrap();
+    let stats = ColumnStats::read(&encoder.finish_with_stats().unwrap()).unwrap();
+    assert_eq!(
+        (stats.row_count, stats.min, stats.max),
+        (3, Some(1), Some(1))
+    );
+    assert!(!stats.may_contain(0..1));
+}
+
+#[test]
+fn stats_without_range() {
+    // the range is unknown, so the block cannot be pruned
+    let mut encoder = AnyRleEncoder::<u128>::with_stats();
+    encoder.append(u128::MAX).unwrap();
+    let stats = ColumnStats::read(&encoder.finish_with_stats().unwrap()).unwrap();
+    assert_eq!((stats.row_count, stats.min, stats.max), (1, None, None));
+    assert!(stats.may_contain(0..1));
+
+    // no rows
+    let stats =
+        ColumnStats::read(&DeltaRleEncoder::with_stats().finish_with_stats().unwrap()).unwrap();
+    assert_eq!(stats, ColumnStats::default());
+    assert!(!stats.may_contain(..));
+
+    // the caller knows the nulls
+    let stats = ColumnStats {
+        row_count: 4,
+        null_count: 4,
+        ..Default::default()
+    };
+    assert!(!stats.may_contain(..));
+    let bytes = stats.write(&[]).unwrap();
+    assert_eq!(ColumnStats::read(&bytes).unwrap(), stats);
+
+    assert!(ColumnStats::read(&[]).is_err());
+    assert!(ColumnStats::read(&[0, 1, 2]).is_err());
+}
+
+#[test]
+fn stats_are_opt_in() {
+    let mut encoder = DeltaOfDeltaEncoder::new();
+    encoder.append(1).unwrap();
+    assert_eq!(encoder.stats(), None);
+    assert!(encoder.finish_with_stats().is_err());
+
+    let mut encoder = AnyRleEncoder::<i32>::new();
+    encoder.append_run(1, 3).unwrap();
+    assert_eq!(encoder.stats(), None);
+    assert!(encoder.finish_with_stats().is_err());
+
+    let mut encoder = AnyRleEncoder::<i32>::with_stats();
+    encoder.append_run(7, 3).unwrap();
+    encoder.append(-1).unwrap();
+    assert_eq!(
+        encoder.stats(),
+        Some(ColumnStats {
+            row_count: 4,
+            null_count: 0,
+            min: Some(-1),
+            max: Some(7),
+        })
+    );
+}
+
+#[test]
+fn stats_of_derived_columns() {
+    let events: Vec<Event> = (0..100)
+        .map(|i| Event {
+            name: format!("event{}", i % 3),
+            timestamp: 1_700_000_000 + i * 60,
+            counter: 1000 - i as u32,
+            deleted: false,
+        })
+        .collect();
+    let bytes = to_vec(&ColumnarVec::<_, Vec<Event>>::new(&events)).unwrap();
+
+    let timestamp = ColumnStats::read_column(&bytes, 1).unwrap();
+    assert_eq!(
+        (timestamp.row_count, timestamp.min, timestamp.max),
+        (100, Some(1_700_000_000), Some(1_700_000_000 + 99 * 60))
+    );
+    assert!(!timestamp.may_contain(..1_700_000_000));
+    let counter = ColumnStats::read_column(&bytes, 2).unwrap();
+    assert_eq!((counter.min, counter.max), (Some(901), Some(1000)));
+    let deleted = ColumnStats::read_column(&bytes, 3).unwrap();
+    assert!(!deleted.may_contain(1..));
+    // the column of `name` has no stats
+    assert!(ColumnStats::read_column(&bytes, 0).is_err());
+    assert!(ColumnStats::read_column(&bytes, 4).is_err());
+
+    let decoded: ColumnarVec<Event, Vec<Event>> = from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.into_vec(), events);
+}
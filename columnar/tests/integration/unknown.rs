@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+
+use serde_columnar::{columnar, from_bytes, to_vec, ColumnarMap, ColumnarVec, UnknownFields};
+
+#[columnar(vec, map, ser, de)]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct NewRow {
+    #[columnar(strategy = "DeltaRle")]
+    id: u64,
+    #[columnar(optional, index = 0, strategy = "Rle")]
+    author: String,
+    #[columnar(optional, index = 1, strategy = "DeltaRle")]
+    lamport: u32,
+}
+
+#[columnar(vec, map, ser, de)]
+#[derive(Debug, Clone, Default, PartialEq)]
+struct OldRow {
+    #[columnar(strategy = "DeltaRle")]
+    id: u64,
+    #[columnar(optional, index = 0, strategy = "Rle")]
+    author: String,
+    #[columnar(unknown_fields)]
+    unknown: UnknownFields,
+}
+
+#[columnar(ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct NewTable {
+    #[columnar(class = "vec")]
+    rows: Vec<NewRow>,
+    #[columnar(optional, index = 0)]
+    name: String,
+    #[columnar(optional, index = 1)]
+    version: u32,
+}
+
+#[columnar(ser, de)]
+#[derive(Debug, Clone, PartialEq)]
+struct OldTable {
+    #[columnar(class = "vec")]
+    rows: Vec<OldRow>,
+    #[columnar(unknown_fields)]
+    unknown: UnknownFields,
+}
+
+fn new_rows(n: u64) -> Vec<NewRow> {
+    (0..n)
+        .map(|i| NewRow {
+            id: i * 2,
+            author: format!("author{}", i / 10),
+            lamport: i as u32 + 100,
+        })
+        .collect()
+}
+
+#[test]
+fn relay_unknown_fields_of_rows() {
+    for n in [1, 2, 50] {
+        let rows = new_rows(n);
+        let bytes = to_vec(&ColumnarVec::<_, Vec<NewRow>>::new(&rows)).unwrap();
+        let old: ColumnarVec<OldRow, Vec<OldRow>> = from_bytes(&bytes).unwrap();
+        let old = old.into_vec();
+        for (old, new) in old.iter().zip(rows.iter()) {
+            assert_eq!(old.id, new.id);
+            assert_eq!(old.author, new.author);
+            assert_eq!(old.unknown.len(), 1);
+            assert!(old.unknown.get(1).is_some());
+        }
+
+        let relayed = to_vec(&ColumnarVec::<_, Vec<OldRow>>::new(&old)).unwrap();
+        assert_eq!(relayed, bytes);
+        let decoded: ColumnarVec<NewRow, Vec<NewRow>> = from_bytes(&relayed).unwrap();
+        assert_eq!(decoded.into_vec(), rows);
+    }
+}
+
+#[test]
+fn relay_unknown_fields_of_map_rows() {
+    // the rows are written back in the order they were decoded
+    let rows: BTreeMap<String, NewRow> = new_rows(30)
+        .into_iter()
+        .map(|row| (row.id.to_string(), row))
+        .collect();
+    let bytes = to_vec(&ColumnarMap::<_, _, BTreeMap<_, _>>::new(&rows)).unwrap();
+    let old: ColumnarMap<String, OldRow, BTreeMap<String, OldRow>> = from_bytes(&bytes).unwrap();
+    let old = old.into_map();
+    assert!(old.values().all(|row| row.unknown.len() == 1));
+
+    let relayed = to_vec(&ColumnarMap::<_, _, BTreeMap<_, _>>::new(&old)).unwrap();
+    assert_eq!(relayed, bytes);
+    let decoded: ColumnarMap<String, NewRow, BTreeMap<String, NewRow>> =
+        from_bytes(&relayed).unwrap();
+    assert_eq!(decoded.into_map(), rows);
+}
+
+#[test]
+fn relay_unknown_fields_of_table() {
+    let table = NewTable {
+        rows: new_rows(20),
+        name: "table".to_string(),
+        version: 3,
+    };
+    let bytes = to_vec(&table).unwrap();
+    let old: OldTable = from_bytes(&bytes).unwrap();
+    assert_eq!(old.unknown.len(), 2);
+    assert_eq!(old.rows[3].unknown.len(), 1);
+
+    let relayed = to_vec(&old).unwrap();
+    assert_eq!(relayed, bytes);
+    let decoded: NewTable = from_bytes(&relayed).unwrap();
+    assert_eq!(decoded, table);
+}
+
+#[test]
+fn unknown_fields_of_changed_rows_are_dropped() {
+    let rows = new_rows(10);
+    let bytes = to_vec(&ColumnarVec::<_, Vec<NewRow>>::new(&rows)).unwrap();
+    let old: ColumnarVec<OldRow, Vec<OldRow>> = from_bytes(&bytes).unwrap();
+    let mut old = old.into_vec();
+    // the unknown columns no longer match the rows
+    old.swap(0, 1);
+    old.pop();
+
+    let relayed = to_vec(&ColumnarVec::<_, Vec<OldRow>>::new(&old)).unwrap();
+    let decoded: ColumnarVec<NewRow, Vec<NewRow>> = from_bytes(&relayed).unwrap();
+    for (decoded, row) in decoded.into_vec().iter().zip(old.iter()) {
+        assert_eq!(decoded.id, row.id);
+        assert_eq!(decoded.author, row.author);
+        assert_eq!(decoded.lamport, 0);
+    }
+    old.swap(0, 1);
+    let relayed = to_vec(&ColumnarVec::<_, Vec<OldRow>>::new(&old)).unwrap();
+    let decoded: ColumnarVec<NewRow, Vec<NewRow>> = from_bytes(&relayed).unwrap();
+    assert!(decoded.into_vec().iter().all(|row| row.lamport == 0));
+
+    // the data without unknown entries is unchanged
+    let old = vec![OldRow {
+        id: 1,
+        author: "a".to_string(),
+        unknown: UnknownFields::default(),
+    }];
+    let bytes = to_vec(&ColumnarVec::<_, Vec<OldRow>>::new(&old)).unwrap();
+    let decoded: ColumnarVec<OldRow, Vec<OldRow>> = from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.into_vec(), old);
+}
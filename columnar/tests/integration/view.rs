@@ -0,0 +1,84 @@
+use serde_columnar::{
+    AnyRleDecoder, AnyRleEncoder, BoolRleDecoder, BoolRleEncoder, ColumnView, DeltaRleDecoder,
+    DeltaRleEncoder,
+};
+
+/// Runs, literal runs and lone values
+fn values(n: u32) -> Vec<u32> {
+    (0..n)
+        .map(|i| match i % 100 {
+            0..=39 => 7,
+            40..=69 => i * 3,
+            _ => i / 10,
+        })
+        .collect()
+}
+
+#[test]
+fn any_rle_view() {
+    for n in [0, 1, 2, 1000] {
+        let values: Vec<String> = values(n).iter().map(|v| v.to_string()).collect();
+        let mut encoder = AnyRleEncoder::<String>::new();
+        for v in values.iter() {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        let view = ColumnView::<AnyRleDecoder<String>>::new(&bytes);
+        assert_eq!(view.len().unwrap(), values.len());
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(view.get(i).unwrap().as_ref(), Some(v));
+        }
+        assert_eq!(view.get(values.len()).unwrap(), None);
+        assert_eq!(view.range(0..values.len()).unwrap(), values);
+    }
+}
+
+#[test]
+fn delta_rle_view() {
+    for n in [0, 1, 2, 1000] {
+        let values = values(n);
+        let mut encoder = DeltaRleEncoder::new();
+        for &v in values.iter() {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        let view = ColumnView::<DeltaRleDecoder<u32>>::new(&bytes);
+        assert_eq!(view.len().unwrap(), values.len());
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(view.get(i).unwrap(), Some(*v));
+        }
+        assert_eq!(view.get(values.len()).unwrap(), None);
+        for start in (0..values.len()).step_by(37) {
+            assert_eq!(
+                view.range(start..start + 150).unwrap(),
+                values[start..(start + 150).min(values.len())]
+            );
+        }
+    }
+}
+
+#[test]
+fn bool_rle_view() {
+    for first in [true, false] {
+        let values: Vec<bool> = (0..1000).map(|i| (i % 7 < 3) == first).collect();
+        let mut encoder = BoolRleEncoder::new();
+        for &v in values.iter() {
+            encoder.append(v).unwrap();
+        }
+        let bytes = encoder.finish().unwrap();
+        let view = ColumnView::<BoolRleDecoder>::new(&bytes);
+        assert_eq!(view.len().unwrap(), values.len());
+        for (i, v) in values.iter().enumerate() {
+            assert_eq!(view.get(i).unwrap(), Some(*v));
+        }
+        assert_eq!(view.range(995..2000).unwrap(), values[995..]);
+        assert!(view.range(1000..1010).unwrap().is_empty());
+    }
+}
+
+#[test]
+fn view_of_invalid_bytes() {
+    let view = ColumnView::<DeltaRleDecoder<u32>>::new(&[2]);
+    assert!(view.len().is_err());
+    assert!(view.get(0).is_err());
+}
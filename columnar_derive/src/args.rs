@@ -9,6 +9,8 @@ use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
 use syn::{parse::ParseStream, spanned::Spanned, DeriveInput, Lifetime, LitStr, Token, Type};
 
+use crate::utils::single_type_arg;
+
 #[derive(Debug, Clone, Copy, FromMeta)]
 pub struct DeriveArgs {
     #[darling(default)]
@@ -22,6 +24,18 @@ pub struct DeriveArgs {
     // only row struct
     #[darling(default)]
     pub(crate) iterable: bool,
+    // only vec row struct
+    #[darling(default)]
+    pub(crate) builder: bool,
+    // only vec row struct
+    #[darling(default)]
+    pub(crate) columns: bool,
+    // only vec row struct
+    #[darling(default)]
+    pub(crate) projection: bool,
+    // only vec row struct
+    #[darling(default)]
+    pub(crate) parallel: bool,
 }
 
 #[derive(FromField, Debug, Clone)]
@@ -47,7 +61,37 @@ pub struct FieldArgs {
     /// Same as the `skip` of serde
     #[darling(default)]
     pub skip: bool,
+    /// The field is `Option<T>`, its presence is stored separately and only the
+    /// present values are encoded by the `strategy`.
+    #[darling(default)]
+    pub nullable: bool,
+    /// The field is a `vec` row struct, whose columns are nested in the columns of this row.
+    /// With `class = "vec"`, the field is `Vec<T>` of the row struct, and the inner rows of
+    /// all the rows share the same nested columns.
+    #[darling(default)]
+    pub flatten: bool,
+    /// The field is `Vec<T>`, the lengths of the lists are stored separately and the
+    /// elements of all rows are concatenated and encoded by the `strategy`.
+    #[darling(default)]
+    pub list: bool,
     pub iter: Option<Type>,
+    /// With `class = "vec"` on the field of a table, the rows are encoded in pages of
+    /// `page_rows` rows by `PagedColumnarVec`.
+    pub page_rows: Option<usize>,
+    /// The general-purpose compression of the bytes of the field, e.g. `"deflate"`, which
+    /// runs after the `strategy`.
+    pub compress: Option<String>,
+    /// The bytes of the column start with the header of `ColumnStats` of the values, which
+    /// is read without decoding the column. Only for `DeltaRle`, `DeltaOfDelta` or `BoolRle`.
+    #[darling(default)]
+    pub stats: bool,
+    /// The field is `UnknownFields`, which keeps the optional entries unknown to this
+    /// version. It is not a column, so it is also treated as `skip`.
+    #[darling(default)]
+    pub unknown_fields: bool,
+    /// The position of the field in the struct, which is the member of unnamed field.
+    #[darling(skip)]
+    pub position: usize,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -56,6 +100,11 @@ pub enum Strategy {
     DeltaRle,
     BoolRle,
     DeltaOfDelta,
+    Dict,
+    Auto,
+    FloatXor,
+    BitPack,
+    DeltaBitPack,
     None,
 }
 
@@ -67,7 +116,14 @@ impl Strategy {
                 "DeltaRle" => Self::DeltaRle,
                 "BoolRle" => Self::BoolRle,
                 "DeltaOfDelta" => Self::DeltaOfDelta,
-                _ => unreachable!("strategy should be Rle, BoolRle or DeltaRle"),
+                "Dict" => Self::Dict,
+                "Auto" => Self::Auto,
+                "FloatXor" => Self::FloatXor,
+                "BitPack" => Self::BitPack,
+                "DeltaBitPack" => Self::DeltaBitPack,
+                _ => unreachable!(
+                    "strategy should be Rle, BoolRle, DeltaRle, DeltaOfDelta, Dict, Auto, FloatXor, BitPack or DeltaBitPack"
+                ),
             }
         } else {
             Self::None
@@ -89,8 +145,14 @@ pub trait Args {
     fn strategy(&self) -> Strategy;
     fn can_copy(&self) -> bool {
         match self.strategy() {
-            Strategy::BoolRle | Strategy::DeltaRle | Strategy::DeltaOfDelta => true,
-            Strategy::Rle | Strategy::None => false,
+            Strategy::BoolRle
+            | Strategy::DeltaRle
+            | Strategy::DeltaOfDelta
+            | Strategy::Auto
+            | Strategy::FloatXor
+            | Strategy::BitPack
+            | Strategy::DeltaBitPack => true,
+            Strategy::Rle | Strategy::Dict | Strategy::None => false,
         }
     }
     fn class(&self) -> Option<AsType>;
@@ -106,6 +168,13 @@ pub trait Args {
             Strategy::DeltaOfDelta => {
                 Ok(quote::quote!(::serde_columnar::DeltaOfDeltaColumn::<#ty>))
             }
+            Strategy::Dict => Ok(quote::quote!(::serde_columnar::DictColumn::<#ty>)),
+            Strategy::Auto => Ok(quote::quote!(::serde_columnar::AutoColumn::<#ty>)),
+            Strategy::FloatXor => Ok(quote::quote!(::serde_columnar::FloatXorColumn::<#ty>)),
+            Strategy::BitPack => Ok(quote::quote!(::serde_columnar::BitPackColumn::<#ty>)),
+            Strategy::DeltaBitPack => {
+                Ok(quote::quote!(::serde_columnar::DeltaBitPackColumn::<#ty>))
+            }
             Strategy::None => {
                 if self.class().is_some() {
                     let self_ty = &self.ty();
@@ -129,6 +198,58 @@ pub trait Args {
     }
 }
 
+impl FieldArgs {
+    /// Parses the attributes of the field at `position` of the struct
+    pub fn from_field_at(field: &syn::Field, position: usize) -> darling::Result<Self> {
+        let mut args = Self::from_field(field)?;
+        args.position = position;
+        args.skip |= args.unknown_fields;
+        Ok(args)
+    }
+
+    /// `name` of the named field, or `0` of the unnamed field
+    pub fn member(&self) -> syn::Member {
+        match &self.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(self.position.into()),
+        }
+    }
+
+    /// The name of the variables generated for this field, `field_0` for the unnamed field
+    pub fn name(&self) -> syn::Ident {
+        match &self.ident {
+            Some(ident) => ident.clone(),
+            None => syn::Ident::new(&format!("field_{}", self.position), Span::call_site()),
+        }
+    }
+
+    /// The `T` of a `nullable` field of type `Option<T>`
+    pub fn nullable_inner_ty(&self) -> syn::Result<&Type> {
+        single_type_arg(&self.ty, "Option").ok_or_else(|| {
+            syn::Error::new_spanned(&self.ty, "nullable field must be of type `Option<T>`")
+        })
+    }
+
+    /// The `Compression` of the field with `compress`
+    pub fn compression(&self) -> Option<proc_macro2::TokenStream> {
+        match self.compress.as_deref()? {
+            "deflate" => Some(quote::quote!(::serde_columnar::Compression::Deflate)),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a `flatten` field of `class = "vec"`
+    pub fn is_nested(&self) -> bool {
+        self.flatten && self.class.is_some()
+    }
+
+    /// The `T` of a `list` field of type `Vec<T>`
+    pub fn list_inner_ty(&self) -> syn::Result<&Type> {
+        single_type_arg(&self.ty, "Vec")
+            .ok_or_else(|| syn::Error::new_spanned(&self.ty, "list field must be of type `Vec<T>`"))
+    }
+}
+
 impl Args for FieldArgs {
     fn ty(&self) -> Option<syn::Type> {
         Some(self.ty.clone())
@@ -213,7 +334,7 @@ impl Args for FieldArgs {
                                     self.ident.span(),
                                     format!(
                                         "field `{}` does not have lifetime {}",
-                                        self.ident.as_ref().unwrap(),
+                                        self.member().to_token_stream(),
                                         l,
                                     ),
                                 ));
@@ -252,26 +373,18 @@ pub fn get_derive_args(args: &[NestedMeta]) -> syn::Result<DeriveArgs> {
 
 pub fn parse_field_args(st: &mut DeriveInput) -> syn::Result<Option<Vec<FieldArgs>>> {
     match &mut st.data {
-        syn::Data::Struct(syn::DataStruct {
-            fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
-            ..
-        }) => {
-            let mut args = Vec::with_capacity(named.len());
-            for field in named.iter() {
-                let field_args = FieldArgs::from_field(field)?;
-                args.push(field_args);
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            let mut args = Vec::with_capacity(fields.len());
+            for (position, field) in fields.iter().enumerate() {
+                args.push(FieldArgs::from_field_at(field, position)?);
             }
             check_args_validate(&args)?;
             Ok(Some(args))
         }
-        syn::Data::Enum(syn::DataEnum { variants: _, .. }) => Err(syn::Error::new_spanned(
-            st,
-            "only supported named struct type",
-        )),
-        _ => Err(syn::Error::new_spanned(
-            st,
-            "only supported named struct type",
-        )),
+        syn::Data::Enum(syn::DataEnum { variants: _, .. }) => {
+            Err(syn::Error::new_spanned(st, "only supported struct type"))
+        }
+        _ => Err(syn::Error::new_spanned(st, "only supported struct type")),
     }
 }
 
@@ -279,11 +392,12 @@ pub fn check_args_validate(field_args: &[FieldArgs]) -> syn::Result<()> {
     // if some fields is not optional, but it appears after some optional fields, then we need to throw error
     let mut start_optional = false;
     let mut indexes = std::collections::HashSet::new();
+    let mut has_unknown_fields = false;
     for args in field_args {
         let field_name = &args.ident;
         let optional = args.optional;
         let index = args.index;
-        if start_optional && !optional {
+        if start_optional && !optional && !args.unknown_fields {
             return Err(syn::Error::new_spanned(
                 field_name,
                 "optional field must be placed after non-optional field",
@@ -314,6 +428,131 @@ pub fn check_args_validate(field_args: &[FieldArgs]) -> syn::Result<()> {
                 "strategy and class cannot be set at the same time",
             ));
         }
+        if args.nullable {
+            if class.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "nullable and class cannot be set at the same time",
+                ));
+            }
+            args.nullable_inner_ty()?;
+        }
+        if args.flatten {
+            if strategy.is_some() || args.nullable || args.list {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "flatten cannot be set with strategy, nullable or list",
+                ));
+            }
+            if let Some(class) = class {
+                if class != "vec" {
+                    return Err(syn::Error::new_spanned(
+                        field_name,
+                        "flatten can only be set with class = \"vec\"",
+                    ));
+                }
+                if single_type_arg(&args.ty, "Vec").is_none() {
+                    return Err(syn::Error::new_spanned(
+                        &args.ty,
+                        "flatten field of class = \"vec\" must be of type `Vec<T>`",
+                    ));
+                }
+            }
+        }
+        if args.list {
+            if class.is_some() || args.nullable {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "list cannot be set with class or nullable",
+                ));
+            }
+            args.list_inner_ty()?;
+        }
+        if let Some(page_rows) = args.page_rows {
+            if class.as_deref() != Some("vec") || args.flatten || args.iter.is_some() {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "page_rows can only be set with class = \"vec\", without flatten or iter",
+                ));
+            }
+            if page_rows == 0 {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "page_rows must be greater than 0",
+                ));
+            }
+        }
+        match args.compress.as_deref() {
+            None | Some("deflate") => {}
+            Some("lz4") => {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "lz4 compression is not supported yet",
+                ))
+            }
+            Some(_) => {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "unsupported compression, only \"deflate\" is supported",
+                ))
+            }
+        }
+        if args.compress.is_some() && (args.skip || args.iter.is_some()) {
+            return Err(syn::Error::new_spanned(
+                field_name,
+                "compress cannot be set with skip or iter",
+            ));
+        }
+        if args.stats {
+            if !matches!(
+                strategy.as_deref(),
+                Some("DeltaRle" | "DeltaOfDelta" | "BoolRle")
+            ) {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "stats can only be set with strategy \"DeltaRle\", \"DeltaOfDelta\" or \"BoolRle\"",
+                ));
+            }
+            if args.optional
+                || args.skip
+                || args.nullable
+                || args.list
+                || args.iter.is_some()
+                || args.compress.is_some()
+            {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "stats cannot be set with optional, skip, nullable, list, iter or compress",
+                ));
+            }
+        }
+        if args.unknown_fields {
+            if has_unknown_fields {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "unknown_fields can only be set on one field",
+                ));
+            }
+            has_unknown_fields = true;
+            if args.optional
+                || args.index.is_some()
+                || strategy.is_some()
+                || class.is_some()
+                || args.borrow.is_some()
+                || args.nullable
+                || args.flatten
+                || args.list
+                || args.iter.is_some()
+                || args.page_rows.is_some()
+                || args.compress.is_some()
+                || args.stats
+            {
+                return Err(syn::Error::new_spanned(
+                    field_name,
+                    "unknown_fields cannot be set with other attributes",
+                ));
+            }
+        }
     }
     Ok(())
 }
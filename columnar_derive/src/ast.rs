@@ -1,7 +1,21 @@
-use darling::FromField;
 use syn::{punctuated::Punctuated, Token};
 
-use crate::{args::FieldArgs, attr::Style};
+use crate::{
+    args::FieldArgs,
+    attr::{Style, Variant},
+};
+
+pub fn enum_from_ast(variants: &Punctuated<syn::Variant, Token![,]>) -> syn::Result<Vec<Variant>> {
+    let mut ans = Vec::with_capacity(variants.len());
+    for variant in variants {
+        let (_, fields) = struct_from_ast(&variant.fields)?;
+        ans.push(Variant {
+            ident: variant.ident.clone(),
+            fields,
+        });
+    }
+    Ok(ans)
+}
 
 pub fn struct_from_ast(fields: &syn::Fields) -> syn::Result<(Style, Vec<FieldArgs>)> {
     let ans = match fields {
@@ -17,9 +31,8 @@ pub fn struct_from_ast(fields: &syn::Fields) -> syn::Result<(Style, Vec<FieldArg
 
 fn fields_from_ast(fields: &Punctuated<syn::Field, Token![,]>) -> syn::Result<Vec<FieldArgs>> {
     let mut ans = Vec::with_capacity(fields.len());
-    for field in fields {
-        let args = FieldArgs::from_field(field)?;
-        ans.push(args);
+    for (position, field) in fields.iter().enumerate() {
+        ans.push(FieldArgs::from_field_at(field, position)?);
     }
     Ok(ans)
 }
@@ -3,7 +3,7 @@ use syn::{Generics, Visibility};
 
 use crate::{
     args::{DeriveArgs, FieldArgs},
-    ast::struct_from_ast,
+    ast::{enum_from_ast, struct_from_ast},
 };
 
 /// ```rust
@@ -18,18 +18,22 @@ use crate::{
 ///     bytes: &'a [u8]
 /// }
 /// ```
-#[allow(dead_code)]
 pub enum Data {
-    Enum,
+    Enum(Vec<Variant>),
     Struct(Style, Vec<FieldArgs>),
 }
 
+/// A variant of the enum, whose fields are stored in their own columns.
+pub struct Variant {
+    pub ident: Ident,
+    pub fields: Vec<FieldArgs>,
+}
+
 impl Data {
     pub fn fields(&self) -> &[FieldArgs] {
         match &self {
-            Data::Enum => unimplemented!("unsupported enum for now"),
-            Data::Struct(Style::Struct, fields) => fields,
-            _ => unimplemented!("only support named struct for now"),
+            Data::Enum(_) => unimplemented!("the fields of enum are in its variants"),
+            Data::Struct(_, fields) => fields,
         }
     }
 }
@@ -59,12 +63,7 @@ pub struct Context<'a> {
 impl<'a> Context<'a> {
     pub fn new(input: &'a syn::DeriveInput, derive_args: DeriveArgs) -> syn::Result<Self> {
         let data = match &input.data {
-            syn::Data::Enum(_) => {
-                return Err(syn::Error::new(
-                    input.ident.span(),
-                    "columnar does not support enum type",
-                ))
-            }
+            syn::Data::Enum(data) => Data::Enum(enum_from_ast(&data.variants)?),
             syn::Data::Struct(data) => {
                 let (style, fields) = struct_from_ast(&data.fields)?;
                 Data::Struct(style, fields)
@@ -89,4 +88,11 @@ impl<'a> Context<'a> {
     pub fn fields(&self) -> &[FieldArgs] {
         self.data.fields()
     }
+
+    pub fn style(&self) -> Style {
+        match &self.data {
+            Data::Enum(_) => unimplemented!("the style of enum is in its variants"),
+            Data::Struct(style, _) => *style,
+        }
+    }
 }
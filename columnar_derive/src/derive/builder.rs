@@ -0,0 +1,182 @@
+use crate::{
+    args::{Args, FieldArgs, Strategy},
+    utils::single_type_arg,
+};
+use proc_macro2::{Span, TokenStream};
+use syn::{DeriveInput, Ident, Type};
+
+use super::vec::ser_field_value;
+
+/// `{Row}ColumnsBuilder`
+fn builder_ident(input: &DeriveInput) -> Ident {
+    Ident::new(&format!("{}ColumnsBuilder", input.ident), Span::call_site())
+}
+
+/// The encoder that the values of `ty` are pushed into, which is the same as the one used
+/// by the column of the strategy
+fn strategy_builder(args: &FieldArgs, ty: &Type) -> TokenStream {
+    match args.strategy() {
+        Strategy::Rle => quote::quote!(::serde_columnar::AnyRleEncoder<#ty>),
+        Strategy::BoolRle => quote::quote!(::serde_columnar::BoolRleEncoder),
+        Strategy::DeltaRle => quote::quote!(::serde_columnar::DeltaRleEncoder),
+        Strategy::DeltaOfDelta => quote::quote!(::serde_columnar::DeltaOfDeltaEncoder),
+        Strategy::Dict => quote::quote!(::serde_columnar::DictEncoder<#ty>),
+        Strategy::Auto => quote::quote!(::serde_columnar::AutoEncoder<#ty>),
+        Strategy::FloatXor => quote::quote!(::serde_columnar::FloatXorEncoder<#ty>),
+        Strategy::BitPack => quote::quote!(::serde_columnar::BitPackEncoder<#ty>),
+        Strategy::DeltaBitPack => quote::quote!(::serde_columnar::DeltaBitPackEncoder),
+        Strategy::None => quote::quote!(::serde_columnar::GenericBuilder),
+    }
+}
+
+/// The [ColumnBuilder](serde_columnar::ColumnBuilder) of the field
+fn builder_type(args: &FieldArgs) -> syn::Result<TokenStream> {
+    let ty = &args.ty;
+    let ans = if args.is_nested() {
+        let inner = single_type_arg(ty, "Vec")
+            .ok_or_else(|| syn::Error::new_spanned(ty, "flatten field must be of type `Vec<T>`"))?;
+        quote::quote!(::serde_columnar::NestedBuilder<<#inner as ::serde_columnar::BuildColumns>::ColumnsBuilder>)
+    } else if args.flatten {
+        quote::quote!(<#ty as ::serde_columnar::BuildColumns>::ColumnsBuilder)
+    } else if args.nullable {
+        let builder = strategy_builder(args, args.nullable_inner_ty()?);
+        quote::quote!(::serde_columnar::NullableBuilder<#builder>)
+    } else if args.list {
+        let builder = strategy_builder(args, args.list_inner_ty()?);
+        quote::quote!(::serde_columnar::ListBuilder<#builder>)
+    } else {
+        strategy_builder(args, ty)
+    };
+    Ok(ans)
+}
+
+pub fn generate_derive_vec_row_builder(
+    input: &DeriveInput,
+    field_args: &[FieldArgs],
+) -> syn::Result<TokenStream> {
+    let struct_name = &input.ident;
+    let vis = &input.vis;
+    let builder_name = builder_ident(input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut push_generics = input.generics.clone();
+    push_generics.params.push(syn::parse_quote!('__row));
+    let (push_impl_generics, _, _) = push_generics.split_for_impl();
+
+    let mut builder_fields = Vec::with_capacity(field_args.len());
+    let mut names = Vec::with_capacity(field_args.len());
+    let mut pushes = Vec::with_capacity(field_args.len());
+    let mut writes = Vec::with_capacity(field_args.len());
+    for args in field_args.iter() {
+        if args.skip {
+            continue;
+        }
+        let name = args.name();
+        let member = args.member();
+        let builder = builder_type(args)?;
+        builder_fields.push(quote::quote!(#name: #builder));
+        let value = if args.list && args.can_copy() {
+            quote::quote!(row.#member.iter().copied())
+        } else if args.list {
+            quote::quote!(row.#member.iter())
+        } else {
+            ser_field_value(args, quote::quote!(row.#member))?
+        };
+        pushes.push(quote::quote!(
+            ::serde_columnar::PushColumn::push(&mut self.#name, #value)?;
+        ));
+        let column = quote::quote!(::serde_columnar::ColumnBuilder::finish(self.#name)?);
+        // the columns of the `flatten` row are nested as they are
+        let flatten = args.flatten && !args.is_nested();
+        let write = match (args.optional, flatten) {
+            (false, false) => quote::quote!(writer.column(&#column)?;),
+            (false, true) => quote::quote!(writer.flatten_column(&#column);),
+            (true, false) => {
+                let index = args.index.unwrap();
+                quote::quote!(writer.optional_column(#index, &#column)?;)
+            }
+            (true, true) => {
+                let index = args.index.unwrap();
+                quote::quote!(writer.optional_flatten_column(#index, #column)?;)
+            }
+        };
+        writes.push(write);
+        names.push(name);
+    }
+    let columns_len = names.len();
+    let doc = format!(
+        "Encodes [{}] one row at a time, into the same bytes as `ColumnarVec`.",
+        struct_name
+    );
+
+    let ret = quote::quote!(
+        #[doc = #doc]
+        #vis struct #builder_name #impl_generics #where_clause {
+            #(#builder_fields,)*
+            __len: usize,
+            __phantom: ::std::marker::PhantomData<fn(&#struct_name #ty_generics)>,
+        }
+
+        const _:()={
+            #[automatically_derived]
+            impl #impl_generics ::std::default::Default for #builder_name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#names: ::std::default::Default::default(),)*
+                        __len: 0,
+                        __phantom: ::std::marker::PhantomData,
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #builder_name #ty_generics #where_clause {
+                pub fn new() -> Self {
+                    ::std::default::Default::default()
+                }
+
+                /// Pushes the fields of `row` into their columns
+                pub fn push(&mut self, row: &#struct_name #ty_generics) -> ::std::result::Result<(), ::serde_columnar::ColumnarError> {
+                    #(#pushes)*
+                    self.__len += 1;
+                    Ok(())
+                }
+
+                /// The number of the pushed rows
+                pub fn len(&self) -> usize {
+                    self.__len
+                }
+
+                pub fn is_empty(&self) -> bool {
+                    self.__len == 0
+                }
+
+                /// Encodes the pushed rows into the same bytes as `ColumnarVec`
+                pub fn finish(self) -> ::std::result::Result<::std::vec::Vec<u8>, ::serde_columnar::ColumnarError> {
+                    let mut writer = ::serde_columnar::ColumnsWriter::new(#columns_len)?;
+                    #(#writes)*
+                    Ok(writer.finish())
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::serde_columnar::ColumnBuilder for #builder_name #ty_generics #where_clause {
+                fn finish(self) -> ::std::result::Result<::std::vec::Vec<u8>, ::serde_columnar::ColumnarError> {
+                    #builder_name::finish(self)
+                }
+            }
+
+            #[automatically_derived]
+            impl #push_impl_generics ::serde_columnar::PushColumn<&'__row #struct_name #ty_generics> for #builder_name #ty_generics #where_clause {
+                fn push(&mut self, row: &'__row #struct_name #ty_generics) -> ::std::result::Result<(), ::serde_columnar::ColumnarError> {
+                    #builder_name::push(self, row)
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::serde_columnar::BuildColumns for #struct_name #ty_generics #where_clause {
+                type ColumnsBuilder = #builder_name #ty_generics;
+            }
+        };
+    );
+    Ok(ret)
+}
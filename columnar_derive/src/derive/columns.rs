@@ -0,0 +1,276 @@
+use crate::args::{DeriveArgs, FieldArgs};
+use proc_macro2::{Span, TokenStream};
+use syn::{DeriveInput, Ident};
+
+use super::{
+    utils::generate_generics_phantom,
+    vec::{
+        de_field_value, decode_columns, encode_per_column_to_ser, ser_column_type, ser_field_value,
+    },
+};
+
+/// `{Row}Columns`
+fn columns_ident(input: &DeriveInput) -> Ident {
+    Ident::new(&format!("{}Columns", input.ident), Span::call_site())
+}
+
+/// `{Row}Ref`
+fn ref_ident(input: &DeriveInput) -> Ident {
+    Ident::new(&format!("{}Ref", input.ident), Span::call_site())
+}
+
+fn column_ident(args: &FieldArgs) -> Ident {
+    Ident::new(&format!("column_{}", args.name()), Span::call_site())
+}
+
+pub fn generate_derive_vec_row_columns(
+    derive_args: &DeriveArgs,
+    input: &DeriveInput,
+    field_args: &[FieldArgs],
+) -> syn::Result<TokenStream> {
+    let struct_name = &input.ident;
+    let vis = &input.vis;
+    let columns_name = columns_ident(input);
+    let ref_name = ref_ident(input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut ref_generics = input.generics.clone();
+    ref_generics.params.insert(0, syn::parse_quote!('__columns));
+    let (ref_impl_generics, ref_ty_generics, _) = ref_generics.split_for_impl();
+
+    let mut names = Vec::with_capacity(field_args.len());
+    let mut tys = Vec::with_capacity(field_args.len());
+    let mut members = Vec::with_capacity(field_args.len());
+    let mut skip_members = Vec::new();
+    for args in field_args.iter() {
+        if args.skip {
+            skip_members.push(args.member());
+            continue;
+        }
+        names.push(args.name());
+        tys.push(&args.ty);
+        members.push(args.member());
+    }
+    let len = match names.first() {
+        Some(first) => quote::quote!(self.#first.len()),
+        None => quote::quote!(0),
+    };
+
+    let columns_doc = format!(
+        "The columns of [{}], which are decoded from the same bytes as `ColumnarVec` without \
+         being reassembled into rows.",
+        struct_name
+    );
+    let ref_doc = format!(
+        "The borrowed row `i` of [{}], returned by [{}::row].",
+        columns_name, columns_name
+    );
+
+    let ser = if derive_args.ser {
+        generate_columns_ser(input, field_args)?
+    } else {
+        quote::quote!()
+    };
+    let de = if derive_args.de {
+        generate_columns_de(input, field_args)?
+    } else {
+        quote::quote!()
+    };
+
+    let ret = quote::quote!(
+        #[doc = #columns_doc]
+        #[derive(Clone)]
+        #vis struct #columns_name #impl_generics #where_clause {
+            #(pub #names: ::std::vec::Vec<#tys>,)*
+        }
+
+        #[doc = #ref_doc]
+        #[derive(Clone, Copy)]
+        #vis struct #ref_name #ref_impl_generics #where_clause {
+            #(pub #names: &'__columns #tys,)*
+        }
+
+        const _:()={
+            #[automatically_derived]
+            impl #impl_generics ::std::default::Default for #columns_name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#names: ::std::vec::Vec::new(),)*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #columns_name #ty_generics #where_clause {
+                /// The number of the rows
+                pub fn len(&self) -> usize {
+                    #len
+                }
+
+                pub fn is_empty(&self) -> bool {
+                    self.len() == 0
+                }
+
+                /// Splits the fields of `rows` into their columns
+                pub fn from_rows<__I>(rows: __I) -> Self
+                where
+                    __I: ::std::iter::IntoIterator<Item = #struct_name #ty_generics>,
+                {
+                    let mut columns = Self::default();
+                    for row in rows {
+                        #(columns.#names.push(row.#members);)*
+                    }
+                    columns
+                }
+
+                /// Reassembles the rows, the `skip` fields are filled with their default values
+                pub fn into_rows(self) -> ::std::vec::Vec<#struct_name #ty_generics> {
+                    ::serde_columnar::izip!(#(self.#names.into_iter()),*)
+                        .map(|(#(#names),*)| #struct_name {
+                            #(#members: #names,)*
+                            #(#skip_members: ::std::default::Default::default(),)*
+                        })
+                        .collect()
+                }
+
+                /// Clones the rows out of the columns, the `skip` fields are filled with their
+                /// default values
+                pub fn to_rows(&self) -> ::std::vec::Vec<#struct_name #ty_generics> {
+                    self.clone().into_rows()
+                }
+
+                /// Borrows the fields of row `i`, or `None` if it is out of bounds
+                pub fn row<'__columns>(&'__columns self, i: usize) -> ::std::option::Option<#ref_name #ref_ty_generics> {
+                    if i >= self.len() {
+                        return None;
+                    }
+                    Some(#ref_name {
+                        #(#names: &self.#names[i],)*
+                    })
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::std::iter::FromIterator<#struct_name #ty_generics> for #columns_name #ty_generics #where_clause {
+                fn from_iter<__I: ::std::iter::IntoIterator<Item = #struct_name #ty_generics>>(rows: __I) -> Self {
+                    Self::from_rows(rows)
+                }
+            }
+
+            #ser
+            #de
+        };
+    );
+    Ok(ret)
+}
+
+fn generate_columns_ser(input: &DeriveInput, field_args: &[FieldArgs]) -> syn::Result<TokenStream> {
+    let columns_name = columns_ident(input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut columns_quote = Vec::with_capacity(field_args.len());
+    for args in field_args.iter() {
+        if args.skip {
+            continue;
+        }
+        let name = args.name();
+        let column_name = column_ident(args);
+        let value = ser_field_value(args, quote::quote!((*__v)))?;
+        let column_type = ser_column_type(args)?;
+        columns_quote.push(quote::quote!(
+            let #column_name = self.#name.iter().map(|__v| #value).collect::<::std::vec::Vec<_>>();
+            let #column_name = #column_type::new(
+                #column_name,
+                ::serde_columnar::ColumnAttr{
+                    index: None,
+                }
+            );
+        ));
+    }
+    let ser_quote = encode_per_column_to_ser(field_args)?;
+
+    let ret = quote::quote!(
+        #[automatically_derived]
+        impl #impl_generics ::serde::Serialize for #columns_name #ty_generics #where_clause {
+            fn serialize<S>(&self, ser: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: serde::ser::Serializer,
+            {
+                use ::serde::ser::Error;
+                use ::serde::ser::SerializeSeq;
+                #(#columns_quote)*
+                #ser_quote
+            }
+        }
+    );
+    Ok(ret)
+}
+
+fn generate_columns_de(input: &DeriveInput, field_args: &[FieldArgs]) -> syn::Result<TokenStream> {
+    let columns_name = columns_ident(input);
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut de_generics = input.generics.clone();
+    de_generics.params.insert(0, syn::parse_quote!('__de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+    let phantom_data_fields = generate_generics_phantom(&input.generics);
+
+    let mut names = Vec::with_capacity(field_args.len());
+    let mut values = Vec::with_capacity(field_args.len());
+    for args in field_args.iter() {
+        if args.skip {
+            continue;
+        }
+        let column_name = column_ident(args);
+        let value = de_field_value(args, quote::quote!(__v))?;
+        values.push(quote::quote!(
+            #column_name.data.into_iter().map(|__v| #value).collect()
+        ));
+        names.push(args.name());
+    }
+    let decode = decode_columns(field_args, false, false)?;
+    let check_len = if names.is_empty() {
+        quote::quote!()
+    } else {
+        quote::quote!(
+            if #(columns.#names.len() != column_data_len)||* {
+                return Err(__A::Error::custom("the columns have different lengths"));
+            }
+        )
+    };
+
+    let ret = quote::quote!(
+        #[automatically_derived]
+        impl #de_impl_generics ::serde::Deserialize<'__de> for #columns_name #ty_generics #where_clause {
+            fn deserialize<__D>(de: __D) -> ::std::result::Result<Self, __D::Error>
+            where
+                __D: ::serde::Deserializer<'__de>,
+            {
+                use ::serde::de::Error as DeError;
+                use ::serde::de::Visitor;
+                use ::std::collections::HashMap;
+                use ::serde_columnar::ColumnTrait;
+
+                struct DeVisitor #ty_generics ((#phantom_data_fields));
+                impl #de_impl_generics Visitor<'__de> for DeVisitor #ty_generics #where_clause {
+                    type Value = #columns_name #ty_generics;
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        formatter.write_str("Columns de")
+                    }
+
+                    fn visit_seq<__A>(self, mut seq: __A) -> Result<Self::Value, __A::Error>
+                    where
+                        __A: ::serde::de::SeqAccess<'__de>,
+                    {
+                        #decode
+                        let columns = #columns_name {
+                            #(#names: #values,)*
+                        };
+                        #check_len
+                        Ok(columns)
+                    }
+                }
+                let visitor = DeVisitor(Default::default());
+                de.deserialize_seq(visitor)
+            }
+        }
+    );
+    Ok(ret)
+}
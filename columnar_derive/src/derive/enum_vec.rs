@@ -0,0 +1,251 @@
+use crate::{args::check_args_validate, attr::Variant};
+use proc_macro2::{Span, TokenStream};
+use syn::{DeriveInput, Ident, Member};
+
+use super::{
+    utils::generate_generics_phantom,
+    vec::{de_column_type, de_field_value, process_vec_generics, ser_column_type, ser_field_value},
+};
+
+/// `__column_{variant}_{field}`
+fn column_ident(variant_index: usize, field_index: usize) -> Ident {
+    Ident::new(
+        &format!("__column_{}_{}", variant_index, field_index),
+        Span::call_site(),
+    )
+}
+
+/// `__field_{field}`, the binding of the field in the pattern of its variant
+fn field_binding(field_index: usize) -> Ident {
+    Ident::new(&format!("__field_{}", field_index), Span::call_site())
+}
+
+pub fn check_enum_args_validate(variants: &[Variant]) -> syn::Result<()> {
+    for variant in variants {
+        for args in variant.fields.iter() {
+            if args.optional {
+                return Err(syn::Error::new_spanned(
+                    &args.ty,
+                    "optional field is not supported in enum variant",
+                ));
+            }
+            if args.iter.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &args.ty,
+                    "iter field is not supported in enum variant",
+                ));
+            }
+            if args.page_rows.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &args.ty,
+                    "page_rows is not supported in enum variant",
+                ));
+            }
+            if args.compress.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &args.ty,
+                    "compress is not supported in enum variant",
+                ));
+            }
+            if args.stats {
+                return Err(syn::Error::new_spanned(
+                    &args.ty,
+                    "stats is not supported in enum variant",
+                ));
+            }
+            if args.unknown_fields {
+                return Err(syn::Error::new_spanned(
+                    &args.ty,
+                    "unknown_fields is not supported in enum variant",
+                ));
+            }
+        }
+        check_args_validate(&variant.fields)?;
+    }
+    Ok(())
+}
+
+pub fn generate_derive_enum_vec_row_ser(
+    input: &DeriveInput,
+    variants: &[Variant],
+) -> syn::Result<TokenStream> {
+    let enum_name = &input.ident;
+    let generics_params_to_modify = input.generics.clone();
+    let mut impl_generics = input.generics.clone();
+    let (impl_generics, ty_generics, where_clause) = process_vec_generics(
+        enum_name,
+        &generics_params_to_modify,
+        &mut impl_generics,
+        true,
+    );
+
+    let mut tag_arms = Vec::with_capacity(variants.len());
+    let mut columns_quote = Vec::new();
+    let mut ser_elements = Vec::new();
+    for (variant_index, variant) in variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+        let tag = variant_index as u32;
+        tag_arms.push(quote::quote!(#enum_name::#variant_name{..} => #tag));
+        for (field_index, args) in variant.fields.iter().enumerate() {
+            if args.skip {
+                continue;
+            }
+            let column_name = column_ident(variant_index, field_index);
+            let member = args.member();
+            let row_content = ser_field_value(args, quote::quote!((*__field)))?;
+            let column_type = ser_column_type(args)?;
+            columns_quote.push(quote::quote!(
+                let #column_name = rows.into_iter().filter_map(|row| match row {
+                    #enum_name::#variant_name{#member: __field, ..} => Some(#row_content),
+                    _ => None,
+                }).collect::<::std::vec::Vec<_>>();
+                let #column_name = #column_type::new(
+                    #column_name,
+                    ::serde_columnar::ColumnAttr{
+                        index: None,
+                    }
+                );
+            ));
+            ser_elements.push(quote::quote!(
+                seq_encoder.serialize_element(&#column_name)?;
+            ));
+        }
+    }
+    let columns_len = ser_elements.len() + 1;
+
+    let ret = quote::quote!(
+        const _:()={
+            use ::serde::ser::SerializeSeq;
+            #[automatically_derived]
+            impl #impl_generics ::serde_columnar::RowSer<__IT> for #enum_name #ty_generics #where_clause {
+                #[allow(unreachable_patterns)]
+                fn serialize_columns<S>(rows: &__IT, ser: S) -> std::result::Result<S::Ok, S::Error>
+                where
+                    S: serde::ser::Serializer,
+                {
+                    let column_tag = rows.into_iter().map(|row| match row {
+                        #(#tag_arms),*
+                    }).collect::<::std::vec::Vec<u32>>();
+                    let column_tag = ::serde_columnar::RleColumn::<u32>::new(
+                        column_tag,
+                        ::serde_columnar::ColumnAttr{
+                            index: None,
+                        }
+                    );
+                    #(#columns_quote)*
+                    let mut seq_encoder = ser.serialize_seq(Some(#columns_len))?;
+                    seq_encoder.serialize_element(&column_tag)?;
+                    #(#ser_elements)*
+                    seq_encoder.end()
+                }
+            }
+        };
+    );
+    Ok(ret)
+}
+
+pub fn generate_derive_enum_vec_row_de(
+    input: &DeriveInput,
+    variants: &[Variant],
+) -> syn::Result<TokenStream> {
+    let enum_name = &input.ident;
+    let generics_params_to_modify = input.generics.clone();
+    let mut impl_generics = input.generics.clone();
+    let (impl_generics, ty_generics, where_clause) = process_vec_generics(
+        enum_name,
+        &generics_params_to_modify,
+        &mut impl_generics,
+        false,
+    );
+    let mut generics_params_add_it = input.generics.clone();
+    generics_params_add_it
+        .params
+        .push(syn::parse_quote! { __IT });
+    let (_, visitor_ty_generics, _) = generics_params_add_it.split_for_impl();
+    let phantom_data_fields = generate_generics_phantom(&generics_params_add_it);
+
+    let mut columns_quote = Vec::new();
+    let mut variant_arms = Vec::with_capacity(variants.len());
+    for (variant_index, variant) in variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+        let tag = variant_index as u32;
+        let mut next_fields = Vec::with_capacity(variant.fields.len());
+        let mut fields_build = Vec::with_capacity(variant.fields.len());
+        for (field_index, args) in variant.fields.iter().enumerate() {
+            let member = args.member();
+            if args.skip {
+                fields_build.push(quote::quote!(#member: ::std::default::Default::default()));
+                continue;
+            }
+            let column_name = column_ident(variant_index, field_index);
+            let column_type = de_column_type(args)?;
+            columns_quote.push(quote::quote!(
+                let #column_name: #column_type = seq.next_element()?.ok_or_else(||__A::Error::custom("DeserializeUnexpectedEnd"))?;
+                let mut #column_name = #column_name.data.into_iter();
+            ));
+            let binding = field_binding(field_index);
+            let message = format!(
+                "the column of `{}::{}` ends unexpectedly",
+                variant_name,
+                member_name(&member)
+            );
+            next_fields.push(quote::quote!(
+                let #binding = #column_name.next().ok_or_else(|| __A::Error::custom(#message))?;
+            ));
+            let value = de_field_value(args, quote::quote!(#binding))?;
+            fields_build.push(quote::quote!(#member: #value));
+        }
+        variant_arms.push(quote::quote!(
+            #tag => {
+                #(#next_fields)*
+                Ok(#enum_name::#variant_name{#(#fields_build),*})
+            }
+        ));
+    }
+
+    let ret = quote::quote!(
+        const _:()={
+            use ::serde::de::Error as DeError;
+            use ::serde::de::Visitor;
+            #[automatically_derived]
+            impl #impl_generics ::serde_columnar::RowDe<'__de, __IT> for #enum_name #ty_generics #where_clause {
+                fn deserialize_columns<__D>(de: __D) -> Result<__IT, __D::Error>
+                where
+                    __D: serde::Deserializer<'__de>
+                {
+                    struct DeVisitor #visitor_ty_generics ((#phantom_data_fields));
+                    impl #impl_generics Visitor<'__de> for DeVisitor #visitor_ty_generics #where_clause{
+                        type Value = __IT;
+                        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                            formatter.write_str("Vec de")
+                        }
+
+                        fn visit_seq<__A>(self, mut seq: __A) -> Result<Self::Value, __A::Error>
+                        where
+                            __A: ::serde::de::SeqAccess<'__de>,
+                        {
+                            let column_tag: ::serde_columnar::RleColumn<u32> = seq.next_element()?.ok_or_else(||__A::Error::custom("DeserializeUnexpectedEnd"))?;
+                            #(#columns_quote)*
+                            column_tag.data.into_iter().map(|tag| -> Result<#enum_name #ty_generics, __A::Error> {
+                                match tag {
+                                    #(#variant_arms)*
+                                    _ => Err(__A::Error::custom(format!("unknown variant tag {}", tag))),
+                                }
+                            }).collect()
+                        }
+                    }
+                    let visitor = DeVisitor(Default::default());
+                    de.deserialize_seq(visitor)
+                }
+            }
+        };
+    );
+    Ok(ret)
+}
+
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
@@ -2,7 +2,12 @@ use crate::args::{Args, FieldArgs};
 use syn::{DeriveInput, Generics};
 use syn::{ImplGenerics, TypeGenerics, WhereClause};
 
-use super::utils::{add_generics_clause_to_where, generate_generics_phantom};
+use super::utils::{
+    add_generics_clause_to_where, generate_generics_phantom, list_column_type, list_field_value,
+    list_row_value, list_ser_column_type, nullable_column_type, nullable_field_value,
+    nullable_row_value,
+};
+use super::vec::{de_unknown_fields, read_mapping, ser_unknown_fields, unknown_field_value};
 
 pub fn generate_derive_hashmap_row_ser(
     input: &DeriveInput,
@@ -153,31 +158,38 @@ fn generate_with_map_per_columns(
         if args.skip {
             continue;
         }
-        let field_name = &args.ident;
+        let field_name = args.name();
+        let member = args.member();
         let field_type = &args.ty;
         let field_attr_ty = &args.class;
         let column_name = syn::Ident::new(
-            &format!("column_{}", field_name.as_ref().unwrap()),
+            &format!("column_{}", field_name),
             proc_macro2::Span::call_site(),
         );
         columns_quote.push(quote::quote!(#column_name));
         let columns_type = quote::quote!(::std::vec::Vec<_>);
         columns_types.push(columns_type);
         let can_copy = args.can_copy(); //is_field_type_is_can_copy(args)?;
-        let cow_columns_field = if can_copy {
-            quote::quote!(v.#field_name)
+        let cow_columns_field = if args.flatten {
+            quote::quote!(&v.#member)
+        } else if args.nullable {
+            nullable_row_value(args, quote::quote!(v.#member))
+        } else if args.list {
+            list_row_value(quote::quote!(v.#member))
+        } else if can_copy {
+            quote::quote!(v.#member)
         } else if field_attr_ty.is_some() {
             match field_attr_ty.as_ref().unwrap_or(&"".to_string()).as_str() {
                 "vec" => {
-                    quote::quote!(::serde_columnar::ColumnarVec::<_, #field_type>::new(&v.#field_name))
+                    quote::quote!(::serde_columnar::ColumnarVec::<_, #field_type>::new(&v.#member))
                 }
                 "map" => {
-                    quote::quote!(::serde_columnar::ColumnarMap::<_, _, #field_type>::new(&v.#field_name))
+                    quote::quote!(::serde_columnar::ColumnarMap::<_, _, #field_type>::new(&v.#member))
                 }
                 _ => return Err(syn::Error::new_spanned(field_attr_ty, "unsupported type")),
             }
         } else {
-            quote::quote!(::std::borrow::Cow::Borrowed(&v.#field_name))
+            quote::quote!(::std::borrow::Cow::Borrowed(&v.#member))
         };
         cow_columns_fields.push(cow_columns_field);
         let this_ty = if can_copy {
@@ -186,7 +198,17 @@ fn generate_with_map_per_columns(
             quote::quote!(std::borrow::Cow<#field_type>)
         };
         // real columns
-        let column_type_token = args.get_strategy_column(this_ty)?;
+        let column_type_token = if args.is_nested() {
+            quote::quote!(::serde_columnar::NestedColumn::<&#field_type>)
+        } else if args.flatten {
+            quote::quote!(::serde_columnar::FlattenColumn::<&#field_type>)
+        } else if args.nullable {
+            nullable_column_type(args)?
+        } else if args.list {
+            list_ser_column_type(args)?
+        } else {
+            args.get_strategy_column(this_ty)?
+        };
         let column_content_token = quote::quote!(let #column_name = #column_type_token::new(
                 #column_name,
                 ::serde_columnar::ColumnAttr{
@@ -217,11 +239,11 @@ fn encode_map_per_column_to_ser(
             field_len -= 1;
             continue;
         }
-        let field_name = &args.ident;
+        let field_name = args.name();
         let optional = args.optional;
         let index = args.index;
         let column_index = syn::Ident::new(
-            &format!("column_{}", field_name.as_ref().unwrap()),
+            &format!("column_{}", field_name),
             proc_macro2::Span::call_site(),
         );
         let ser_element = if !optional {
@@ -240,11 +262,15 @@ fn encode_map_per_column_to_ser(
         };
         ser_elements.push(ser_element);
     }
+    let (unknown_fields, unknown_len, ser_unknown) =
+        ser_unknown_fields(field_args, quote::quote!((_, row)));
 
     let ret = quote::quote!(
-        let mut seq_encoder = ser.serialize_seq(Some(#field_len + 1))?;
+        #unknown_fields
+        let mut seq_encoder = ser.serialize_seq(Some(#field_len + 1 #unknown_len))?;
         seq_encoder.serialize_element(&vec_k)?;
         #(#ser_elements)*
+        #ser_unknown
         seq_encoder.end()
     );
     Ok(ret)
@@ -264,23 +290,37 @@ fn generate_map_per_column_to_de_columns(
     let mut field_names_build = Vec::with_capacity(field_len);
     let mut into_iter_quote = Vec::with_capacity(field_len);
     for args in field_args.iter() {
-        let field_name = &args.ident;
+        let field_name = args.name();
+        let member = args.member();
         let optional = args.optional;
         let index = args.index;
         let field_type = &args.ty;
         let class = &args.class;
+        if args.unknown_fields {
+            let value = unknown_field_value();
+            field_names_build.push(quote::quote!(#member: #value));
+            continue;
+        }
         if args.skip {
-            field_names_build.push(quote::quote!(#field_name: ::std::default::Default::default()));
+            field_names_build.push(quote::quote!(#member: ::std::default::Default::default()));
             continue;
         }
         let column_index = syn::Ident::new(
-            &format!("column_{}", field_name.as_ref().unwrap()),
+            &format!("column_{}", field_name),
             proc_macro2::Span::call_site(),
         );
         columns_quote.push(quote::quote!(#column_index));
         field_names.push(quote::quote!(#field_name));
         let is_num = args.can_copy(); //is_field_type_is_can_copy(args)?;
-        let column_type = if is_num {
+        let column_type = if args.is_nested() {
+            quote::quote!(::serde_columnar::NestedColumn<#field_type>)
+        } else if args.flatten {
+            quote::quote!(::serde_columnar::FlattenColumn<#field_type>)
+        } else if args.nullable {
+            nullable_column_type(args)?
+        } else if args.list {
+            list_column_type(args)?
+        } else if is_num {
             args.get_strategy_column(quote::quote!(#field_type))?
         } else if class.is_some() {
             match class.as_ref().unwrap_or(&"".to_string()).as_str() {
@@ -309,13 +349,7 @@ fn generate_map_per_column_to_de_columns(
             )
         } else {
             if !add_mapping {
-                elements.push(quote::quote!(
-                    let mut mapping = HashMap::new();
-                    while let Ok(Some((index, bytes))) = seq.next_element::<(usize, Vec<u8>)>() {
-                        // ignore
-                        mapping.insert(index, bytes);
-                    }
-                ));
+                elements.push(read_mapping());
                 add_mapping = true;
             }
             let index = index.unwrap();
@@ -331,20 +365,26 @@ fn generate_map_per_column_to_de_columns(
 
         columns_types.push(column_type);
 
-        let field_name_build = if is_num {
-            quote::quote!(#field_name: #field_name)
+        let field_name_build = if args.nullable {
+            let value = nullable_field_value(args, quote::quote!(#field_name));
+            quote::quote!(#member: #value)
+        } else if args.list {
+            let value = list_field_value(args, quote::quote!(#field_name));
+            quote::quote!(#member: #value)
+        } else if is_num || args.flatten {
+            quote::quote!(#member: #field_name)
         } else if class.is_some() {
             match class.as_ref().unwrap_or(&"".to_string()).as_str() {
                 "vec" => {
-                    quote::quote!(#field_name: #field_name.into_vec())
+                    quote::quote!(#member: #field_name.into_vec())
                 }
                 "map" => {
-                    quote::quote!(#field_name: #field_name.into_map())
+                    quote::quote!(#member: #field_name.into_map())
                 }
                 _ => return Err(syn::Error::new_spanned(class, "unsupported type")),
             }
         } else {
-            quote::quote!(#field_name: #field_name.into_owned())
+            quote::quote!(#member: #field_name.into_owned())
         };
         field_names_build.push(field_name_build);
 
@@ -353,6 +393,12 @@ fn generate_map_per_column_to_de_columns(
         );
         into_iter_quote.push(into_element);
     }
+    if field_args.iter().any(|args| args.unknown_fields) {
+        if !add_mapping {
+            elements.push(read_mapping());
+        }
+        elements.push(de_unknown_fields(field_args, quote::quote!(vec_k.len())));
+    }
 
     let ret = quote::quote!(
         let mut column_data_len: usize = 0;
@@ -1,11 +1,22 @@
+mod builder;
+mod columns;
+mod enum_vec;
 mod map;
 mod utils;
 mod vec;
-use crate::args::{DeriveArgs, FieldArgs};
+use crate::{
+    args::{DeriveArgs, FieldArgs},
+    attr::Variant,
+};
 use darling::Error as DarlingError;
 use syn::DeriveInput;
 
 use self::{
+    builder::generate_derive_vec_row_builder,
+    columns::generate_derive_vec_row_columns,
+    enum_vec::{
+        check_enum_args_validate, generate_derive_enum_vec_row_de, generate_derive_enum_vec_row_ser,
+    },
     map::{generate_derive_hashmap_row_de, generate_derive_hashmap_row_ser},
     vec::{generate_derive_vec_row_de, generate_derive_vec_row_ser},
 };
@@ -26,15 +37,70 @@ pub fn process_derive_args(
     //     tokens.extend(compatible_de);
     // }
 
+    if derive_args.vec || derive_args.hashmap {
+        if let Some(args) = field_args.iter().find(|args| args.page_rows.is_some()) {
+            return Err(syn::Error::new_spanned(
+                &args.ty,
+                "page_rows is only supported on the fields of a table, not of a row",
+            )
+            .into());
+        }
+    }
+    if derive_args.hashmap || derive_args.iterable || derive_args.builder {
+        if let Some(args) = field_args.iter().find(|args| args.compress.is_some()) {
+            return Err(syn::Error::new_spanned(
+                &args.ty,
+                "compress is not supported by the rows of `map`, `iterable` or `builder`",
+            )
+            .into());
+        }
+        if let Some(args) = field_args.iter().find(|args| args.stats) {
+            return Err(syn::Error::new_spanned(
+                &args.ty,
+                "stats is not supported by the rows of `map`, `iterable` or `builder`",
+            )
+            .into());
+        }
+    }
+    if derive_args.iterable || derive_args.builder || derive_args.columns {
+        if let Some(args) = field_args.iter().find(|args| args.unknown_fields) {
+            return Err(syn::Error::new_spanned(
+                &args.ty,
+                "unknown_fields is not supported by `iterable`, `builder` or `columns`",
+            )
+            .into());
+        }
+    }
     if derive_args.vec {
         if derive_args.ser {
-            let vec = generate_derive_vec_row_ser(input, field_args)?;
+            let vec = generate_derive_vec_row_ser(input, field_args, derive_args.parallel)?;
             tokens.extend(vec);
         }
         if derive_args.de {
-            let vec = generate_derive_vec_row_de(input, field_args)?;
+            let vec = generate_derive_vec_row_de(
+                input,
+                field_args,
+                derive_args.projection,
+                derive_args.parallel,
+            )?;
             tokens.extend(vec);
         }
+        if derive_args.builder {
+            let builder = generate_derive_vec_row_builder(input, field_args)?;
+            tokens.extend(builder);
+        }
+        if derive_args.columns {
+            let columns = generate_derive_vec_row_columns(derive_args, input, field_args)?;
+            tokens.extend(columns);
+        }
+    } else if derive_args.builder {
+        return Err(syn::Error::new_spanned(&input.ident, "`builder` requires `vec`").into());
+    } else if derive_args.columns {
+        return Err(syn::Error::new_spanned(&input.ident, "`columns` requires `vec`").into());
+    } else if derive_args.projection {
+        return Err(syn::Error::new_spanned(&input.ident, "`projection` requires `vec`").into());
+    } else if derive_args.parallel {
+        return Err(syn::Error::new_spanned(&input.ident, "`parallel` requires `vec`").into());
     }
     if derive_args.hashmap {
         if derive_args.ser {
@@ -48,3 +114,63 @@ pub fn process_derive_args(
     }
     Ok(tokens)
 }
+
+/// Enum can only be the row of vec-like container.
+pub fn process_enum_derive_args(
+    derive_args: &DeriveArgs,
+    input: &DeriveInput,
+    variants: &[Variant],
+) -> syn::Result<proc_macro2::TokenStream> {
+    if derive_args.hashmap {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "enum does not support `map`",
+        ));
+    }
+    if derive_args.iterable {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "enum does not support `iterable`",
+        ));
+    }
+    if derive_args.builder {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "enum does not support `builder`",
+        ));
+    }
+    if derive_args.columns {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "enum does not support `columns`",
+        ));
+    }
+    if derive_args.projection {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "enum does not support `projection`",
+        ));
+    }
+    if derive_args.parallel {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "enum does not support `parallel`",
+        ));
+    }
+    if !derive_args.vec {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "enum only supports `vec`",
+        ));
+    }
+    check_enum_args_validate(variants)?;
+
+    let mut tokens = proc_macro2::TokenStream::new();
+    if derive_args.ser {
+        tokens.extend(generate_derive_enum_vec_row_ser(input, variants)?);
+    }
+    if derive_args.de {
+        tokens.extend(generate_derive_enum_vec_row_de(input, variants)?);
+    }
+    Ok(tokens)
+}
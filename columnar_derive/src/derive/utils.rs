@@ -1,5 +1,7 @@
 use syn::{Generics, WherePredicate};
 
+use crate::args::{Args, FieldArgs};
+
 pub fn add_generics_clause_to_where(
     generics: Vec<WherePredicate>,
     where_clause: Option<&syn::WhereClause>,
@@ -37,3 +39,81 @@ pub fn generate_generics_phantom(generics: &Generics) -> proc_macro2::TokenStrea
     }
     phantom_data_fields
 }
+
+/// The value of a `nullable` field pushed into its [NullableColumn](serde_columnar::NullableColumn)
+pub fn nullable_row_value(
+    args: &FieldArgs,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if args.can_copy() {
+        value
+    } else {
+        quote::quote!(#value.as_ref().map(::std::borrow::Cow::Borrowed))
+    }
+}
+
+/// `NullableColumn<C>` of a `nullable` field, where `C` is the column of its strategy
+pub fn nullable_column_type(args: &FieldArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let inner = args.nullable_inner_ty()?;
+    let item = if args.can_copy() {
+        quote::quote!(#inner)
+    } else {
+        quote::quote!(::std::borrow::Cow<#inner>)
+    };
+    let column = args.get_strategy_column(item)?;
+    Ok(quote::quote!(::serde_columnar::NullableColumn::<#column>))
+}
+
+/// Converts the decoded item `value` of a `nullable` field back to the field value
+pub fn nullable_field_value(
+    args: &FieldArgs,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if args.can_copy() {
+        value
+    } else {
+        quote::quote!(#value.map(|v| v.into_owned()))
+    }
+}
+
+/// The value of a `list` field pushed into its [ListColumn](serde_columnar::ListColumn),
+/// the list is borrowed until the column is encoded
+pub fn list_row_value(value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote::quote!(&#value)
+}
+
+/// `ListColumn<C>` of a `list` field, where `C` is the column of its strategy
+pub fn list_column_type(args: &FieldArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let column = list_items_column(args)?;
+    Ok(quote::quote!(::serde_columnar::ListColumn::<#column>))
+}
+
+/// `ListColumn<C, &Vec<T>>` that a `list` field is serialized by, which holds the
+/// references of the lists
+pub fn list_ser_column_type(args: &FieldArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let field_type = &args.ty;
+    let column = list_items_column(args)?;
+    Ok(quote::quote!(::serde_columnar::ListColumn::<#column, &#field_type>))
+}
+
+fn list_items_column(args: &FieldArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let inner = args.list_inner_ty()?;
+    let item = if args.can_copy() {
+        quote::quote!(#inner)
+    } else {
+        quote::quote!(::std::borrow::Cow<#inner>)
+    };
+    args.get_strategy_column(item)
+}
+
+/// Converts the decoded item `value` of a `list` field back to the field value
+pub fn list_field_value(
+    args: &FieldArgs,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if args.can_copy() {
+        value
+    } else {
+        quote::quote!(#value.into_iter().map(|v| v.into_owned()).collect())
+    }
+}
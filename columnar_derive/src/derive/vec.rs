@@ -2,17 +2,22 @@ use crate::args::{Args, FieldArgs};
 use syn::{DeriveInput, Generics};
 use syn::{ImplGenerics, TypeGenerics, WhereClause};
 
-use super::utils::{add_generics_clause_to_where, generate_generics_phantom};
+use super::utils::{
+    add_generics_clause_to_where, generate_generics_phantom, list_column_type, list_field_value,
+    list_row_value, list_ser_column_type, nullable_column_type, nullable_field_value,
+    nullable_row_value,
+};
 
 pub fn generate_derive_vec_row_ser(
     input: &DeriveInput,
     field_args: &Vec<FieldArgs>,
+    parallel: bool,
 ) -> syn::Result<proc_macro2::TokenStream> {
     let fields_len = field_args.len();
     let struct_name_ident = &input.ident;
     let generics_params_to_modify = input.generics.clone();
     let mut impl_generics = input.generics.clone();
-    let (impl_generics, ty_generics, where_clause) = process_vec_generics(
+    let (impl_generics, ty_generics, mut where_clause) = process_vec_generics(
         struct_name_ident,
         &generics_params_to_modify,
         &mut impl_generics,
@@ -21,12 +26,23 @@ pub fn generate_derive_vec_row_ser(
 
     // generate ser columns
     let mut columns_quote = Vec::with_capacity(fields_len);
-    for args in field_args {
-        let col = generate_per_field_to_column(args)?;
-        columns_quote.push(col);
-    }
-    // generate ser
-    let ser_quote = encode_per_column_to_ser(field_args)?;
+    let ser_quote = if parallel {
+        // the rows are shared by the threads that encode the columns
+        where_clause
+            .predicates
+            .push(syn::parse_quote! { __IT: Sync });
+        where_clause
+            .predicates
+            .extend(send_sync_predicates(&input.generics));
+        encode_columns_parallel(field_args)?
+    } else {
+        for args in field_args {
+            let col = generate_per_field_to_column(args)?;
+            columns_quote.push(col);
+        }
+        // generate ser
+        encode_per_column_to_ser(field_args)?
+    };
 
     let ret = quote::quote!(
         const _:()={
@@ -47,7 +63,7 @@ pub fn generate_derive_vec_row_ser(
     Ok(ret)
 }
 
-fn process_vec_generics<'a>(
+pub(super) fn process_vec_generics<'a>(
     struct_name: &proc_macro2::Ident,
     generics_params_to_modify: &'a Generics,
     impl_generics: &'a mut Generics,
@@ -84,35 +100,14 @@ fn generate_per_field_to_column(field_arg: &FieldArgs) -> syn::Result<proc_macro
     if field_arg.skip {
         return Ok(quote::quote! {});
     }
-    let field_name = &field_arg.ident;
-    let field_type = &field_arg.ty;
-    let field_attr_ty = &field_arg.class;
+    let field_name = field_arg.name();
+    let member = field_arg.member();
     let column_name = syn::Ident::new(
-        &format!("column_{}", field_name.as_ref().unwrap()),
+        &format!("column_{}", field_name),
         proc_macro2::Span::call_site(),
     );
-    let can_copy = field_arg.can_copy(); //is_field_type_is_can_copy(field_arg)?;
-    let row_content = if can_copy {
-        quote::quote!(row.#field_name)
-    } else if field_attr_ty.is_some() {
-        match field_attr_ty.as_ref().unwrap_or(&"".to_string()).as_str() {
-            "vec" => {
-                quote::quote!(::serde_columnar::ColumnarVec::<_, #field_type>::new(&row.#field_name))
-            }
-            "map" => {
-                quote::quote!(::serde_columnar::ColumnarMap::<_, _, #field_type>::new(&row.#field_name))
-            }
-            _ => return Err(syn::Error::new_spanned(field_attr_ty, "unsupported type")),
-        }
-    } else {
-        quote::quote!(std::borrow::Cow::Borrowed(&row.#field_name))
-    };
-    let this_ty = if can_copy {
-        quote::quote!(#field_type)
-    } else {
-        quote::quote!(std::borrow::Cow<#field_type>)
-    };
-    let column_type_token = field_arg.get_strategy_column(this_ty)?;
+    let row_content = ser_field_value(field_arg, quote::quote!(row.#member))?;
+    let column_type_token = ser_column_type(field_arg)?;
     let column_content_token = 
         quote::quote!(let #column_name = 
             #column_type_token::new(
@@ -133,11 +128,82 @@ fn generate_per_field_to_column(field_arg: &FieldArgs) -> syn::Result<proc_macro
     Ok(ret)
 }
 
-fn encode_per_column_to_ser(field_args: &[FieldArgs]) -> syn::Result<proc_macro2::TokenStream> {
+/// The value of the field pushed into its column, `value` is the place of the field
+pub(super) fn ser_field_value(
+    field_arg: &FieldArgs,
+    value: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let field_type = &field_arg.ty;
+    let field_attr_ty = &field_arg.class;
+    let ans = if field_arg.flatten {
+        quote::quote!(&#value)
+    } else if field_arg.nullable {
+        nullable_row_value(field_arg, value)
+    } else if field_arg.list {
+        list_row_value(value)
+    } else if field_arg.can_copy() {
+        value
+    } else if field_attr_ty.is_some() {
+        match field_attr_ty.as_ref().unwrap_or(&"".to_string()).as_str() {
+            "vec" => {
+                quote::quote!(::serde_columnar::ColumnarVec::<_, #field_type>::new(&#value))
+            }
+            "map" => {
+                quote::quote!(::serde_columnar::ColumnarMap::<_, _, #field_type>::new(&#value))
+            }
+            _ => return Err(syn::Error::new_spanned(field_attr_ty, "unsupported type")),
+        }
+    } else {
+        quote::quote!(std::borrow::Cow::Borrowed(&#value))
+    };
+    Ok(ans)
+}
+
+/// The column type that the field values are collected into when serializing
+pub(super) fn ser_column_type(field_arg: &FieldArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let field_type = &field_arg.ty;
+    if field_arg.is_nested() {
+        return Ok(quote::quote!(::serde_columnar::NestedColumn::<&#field_type>));
+    }
+    if field_arg.flatten {
+        return Ok(quote::quote!(::serde_columnar::FlattenColumn::<&#field_type>));
+    }
+    if field_arg.nullable {
+        return nullable_column_type(field_arg);
+    }
+    if field_arg.list {
+        return list_ser_column_type(field_arg);
+    }
+    let this_ty = if field_arg.can_copy() {
+        quote::quote!(#field_type)
+    } else {
+        quote::quote!(std::borrow::Cow<#field_type>)
+    };
+    Ok(stats_column_type(
+        field_arg,
+        field_arg.get_strategy_column(this_ty)?,
+    ))
+}
+
+/// The column of the field with `stats`, whose bytes start with the header of `ColumnStats`
+fn stats_column_type(
+    field_arg: &FieldArgs,
+    column_type: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if field_arg.stats {
+        quote::quote!(::serde_columnar::StatsColumn::<#column_type>)
+    } else {
+        column_type
+    }
+}
+
+pub(super) fn encode_per_column_to_ser(
+    field_args: &[FieldArgs],
+) -> syn::Result<proc_macro2::TokenStream> {
     let mut field_len = field_args.len();
     let mut ser_elements = Vec::with_capacity(field_len);
     for args in field_args.iter() {
-        let field_name = &args.ident;
+        let field_name = args.name();
         let optional = args.optional;
         let index = args.index;
         if args.skip {
@@ -145,26 +211,210 @@ fn encode_per_column_to_ser(field_args: &[FieldArgs]) -> syn::Result<proc_macro2
             continue;
         }
         let column_index = syn::Ident::new(
-            &format!("column_{}", field_name.as_ref().unwrap()),
+            &format!("column_{}", field_name),
             proc_macro2::Span::call_site(),
         );
+        let column = ser_column(args, &column_index);
         let ser_element = if !optional {
             quote::quote!(
-                seq_encoder.serialize_element(&#column_index)?;
+                seq_encoder.serialize_element(&#column)?;
             )
         } else {
             let index = index.unwrap();
             quote::quote!(
-                {let bytes = ::postcard::to_allocvec(&#column_index).map_err(S::Error::custom)?;
+                {let bytes = ::postcard::to_allocvec(&#column).map_err(S::Error::custom)?;
                 seq_encoder.serialize_element(&(#index , bytes))?;}
             )
         };
         ser_elements.push(ser_element);
     }
+    let (unknown_fields, unknown_len, ser_unknown) =
+        ser_unknown_fields(field_args, quote::quote!(row));
 
     let ret = quote::quote!(
-        let mut seq_encoder = ser.serialize_seq(Some(#field_len))?;
+        #unknown_fields
+        let mut seq_encoder = ser.serialize_seq(Some(#field_len #unknown_len))?;
         #(#ser_elements)*
+        #ser_unknown
+        seq_encoder.end()
+    );
+    Ok(ret)
+}
+
+/// The entries of the `unknown_fields` field shared by the rows, which are written back
+/// after all the known fields. `row` is the pattern of the items of `rows`.
+///
+/// Returns the statement that finds the entries, the count added to the length of the
+/// sequence and the statement that writes them.
+pub(super) fn ser_unknown_fields(
+    field_args: &[FieldArgs],
+    row: proc_macro2::TokenStream,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let Some(args) = field_args.iter().find(|args| args.unknown_fields) else {
+        return (quote::quote!(), quote::quote!(), quote::quote!());
+    };
+    let member = args.member();
+    (
+        quote::quote!(
+            let __unknown_fields = ::serde_columnar::UnknownFields::shared_by_rows(
+                rows.into_iter().map(|#row| &row.#member),
+            );
+        ),
+        quote::quote!(+ __unknown_fields.map_or(0, |fields| fields.len())),
+        quote::quote!(if let Some(__unknown_fields) = __unknown_fields {
+            __unknown_fields.serialize_entries(&mut seq_encoder)?;
+        }),
+    )
+}
+
+/// Reads all the `(index, bytes)` entries of the optional fields into `mapping`
+pub(super) fn read_mapping() -> proc_macro2::TokenStream {
+    quote::quote!(
+        let mut mapping = HashMap::new();
+        while let Ok(Some((index, bytes))) = seq.next_element::<(usize, Vec<u8>)>() {
+            // ignore
+            mapping.insert(index, bytes);
+        }
+    )
+}
+
+/// Collects the entries of `mapping` that are not the optional fields into
+/// `__unknown_fields`, which are shared by the `rows` decoded together
+pub(super) fn de_unknown_fields(
+    field_args: &[FieldArgs],
+    rows: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    // the optional fields that are not projected are left in the mapping
+    let known = field_args
+        .iter()
+        .filter(|args| args.optional)
+        .filter_map(|args| args.index);
+    quote::quote!(
+        #(mapping.remove(&#known);)*
+        let __unknown_fields = ::serde_columnar::UnknownFields::from_entries(mapping, #rows);
+        let mut __row: usize = 0;
+    )
+}
+
+/// The value of the `unknown_fields` field of the next row, see [de_unknown_fields]
+pub(super) fn unknown_field_value() -> proc_macro2::TokenStream {
+    quote::quote!({
+        __row += 1;
+        __unknown_fields.at_row(__row - 1)
+    })
+}
+
+/// The column of the field that is serialized, which is compressed with `compress`
+fn ser_column(args: &FieldArgs, column_index: &syn::Ident) -> proc_macro2::TokenStream {
+    match args.compression() {
+        Some(compression) => {
+            quote::quote!(::serde_columnar::Compressed(&#column_index, #compression))
+        }
+        None => quote::quote!(#column_index),
+    }
+}
+
+/// The `Send + Sync` bounds of the type parameters of the rows marked `parallel`, whose
+/// values are encoded and decoded on the thread pool
+fn send_sync_predicates(generics: &Generics) -> Vec<syn::WherePredicate> {
+    generics
+        .type_params()
+        .map(|param| {
+            let ident = &param.ident;
+            syn::parse_quote! { #ident: Send + Sync }
+        })
+        .collect()
+}
+
+/// Whether the column of the field is encoded into its own bytes, which can be done on the
+/// thread pool. The columns of `flatten` fields are serialized as sequences of columns.
+fn is_parallel_column(args: &FieldArgs) -> bool {
+    !args.skip && (args.optional || args.compress.is_some() || !args.flatten || args.is_nested())
+}
+
+/// Like [encode_per_column_to_ser], but the columns are collected and encoded on the thread
+/// pool, and then written in the order of the fields.
+fn encode_columns_parallel(field_args: &[FieldArgs]) -> syn::Result<proc_macro2::TokenStream> {
+    let mut slots = Vec::with_capacity(field_args.len());
+    let mut tasks = Vec::with_capacity(field_args.len());
+    let mut sequential = Vec::with_capacity(field_args.len());
+    let mut ser_elements = Vec::with_capacity(field_args.len());
+    for args in field_args.iter().filter(|args| !args.skip) {
+        let field_name = args.name();
+        let column = generate_per_field_to_column(args)?;
+        let column_index = syn::Ident::new(
+            &format!("column_{}", field_name),
+            proc_macro2::Span::call_site(),
+        );
+        if !is_parallel_column(args) {
+            sequential.push(column);
+            ser_elements.push(quote::quote!(
+                seq_encoder.serialize_element(&#column_index)?;
+            ));
+            continue;
+        }
+        let encoded = syn::Ident::new(
+            &format!("__encoded_{}", field_name),
+            proc_macro2::Span::call_site(),
+        );
+        let slot = syn::Ident::new(
+            &format!("__slot_{}", field_name),
+            proc_macro2::Span::call_site(),
+        );
+        let encode = if args.optional {
+            let column = ser_column(args, &column_index);
+            quote::quote!(::postcard::to_allocvec(&#column).map_err(::serde_columnar::ColumnarError::from))
+        } else if let Some(compression) = args.compression() {
+            quote::quote!(::serde_columnar::Compressed(&#column_index, #compression).to_bytes())
+        } else {
+            quote::quote!(::serde_columnar::EncodeColumn::encode_column(&#column_index))
+        };
+        slots.push(quote::quote!(
+            let mut #encoded = None;
+            let #slot = &mut #encoded;
+        ));
+        tasks.push(quote::quote!(
+            __scope.spawn(move || {
+                *#slot = Some({
+                    #column
+                    #encode
+                });
+            });
+        ));
+        let bytes = quote::quote!(
+            #encoded.unwrap().map_err(|e| S::Error::custom(e.to_string()))?
+        );
+        let ser_element = if args.optional {
+            let index = args.index.unwrap();
+            quote::quote!(
+                {let bytes = #bytes;
+                seq_encoder.serialize_element(&(#index , bytes))?;}
+            )
+        } else {
+            quote::quote!(
+                seq_encoder.serialize_element(&::serde_columnar::EncodedColumn(&#bytes))?;
+            )
+        };
+        ser_elements.push(ser_element);
+    }
+    let field_len = ser_elements.len();
+    let (unknown_fields, unknown_len, ser_unknown) =
+        ser_unknown_fields(field_args, quote::quote!(row));
+
+    let ret = quote::quote!(
+        #(#slots)*
+        ::serde_columnar::column_scope(|__scope| {
+            #(#tasks)*
+        });
+        #(#sequential)*
+        #unknown_fields
+        let mut seq_encoder = ser.serialize_seq(Some(#field_len #unknown_len))?;
+        #(#ser_elements)*
+        #ser_unknown
         seq_encoder.end()
     );
     Ok(ret)
@@ -174,16 +424,25 @@ fn encode_per_column_to_ser(field_args: &[FieldArgs]) -> syn::Result<proc_macro2
 pub fn generate_derive_vec_row_de(
     input: &DeriveInput,
     field_args: &[FieldArgs],
+    projection: bool,
+    parallel: bool,
 ) -> syn::Result<proc_macro2::TokenStream> {
     let struct_name_ident = &input.ident;
     let generics_params_to_modify = input.generics.clone();
     let mut impl_generics = input.generics.clone();
-    let (impl_generics, ty_generics, where_clause) = process_vec_generics(
+    let (impl_generics, ty_generics, mut where_clause) = process_vec_generics(
         struct_name_ident,
         &generics_params_to_modify,
         &mut impl_generics,
         false,
     );
+    // the columns of the rows marked `projection` are decoded one after another
+    let parallel = parallel && !projection;
+    if parallel {
+        where_clause
+            .predicates
+            .extend(send_sync_predicates(&input.generics));
+    }
     let mut generics_params_add_it = input.generics.clone();
     generics_params_add_it
         .params
@@ -191,7 +450,7 @@ pub fn generate_derive_vec_row_de(
     let (_, visitor_ty_generics, _) = generics_params_add_it.split_for_impl();
     let phantom_data_fields = generate_generics_phantom(&generics_params_add_it);
     // generate de columns
-    let de = generate_per_column_to_de_columns(field_args, input)?;
+    let de = generate_per_column_to_de_columns(field_args, input, projection, parallel)?;
 
     let ret = quote::quote!(
         const _:()={
@@ -231,126 +490,321 @@ pub fn generate_derive_vec_row_de(
 fn generate_per_column_to_de_columns(
     field_args: &[FieldArgs],
     input: &DeriveInput,
+    projection: bool,
+    parallel: bool,
 ) -> syn::Result<proc_macro2::TokenStream> {
     let struct_name = &input.ident;
     let field_len = field_args.len();
-    let mut elements = Vec::with_capacity(field_len);
-    let mut add_mapping = false;
-    let mut columns_quote = Vec::with_capacity(field_len);
-    let mut columns_types = Vec::with_capacity(field_len);
     let mut into_iter_quote = Vec::with_capacity(field_len);
     let mut field_names = Vec::with_capacity(field_len);
     let mut field_names_build = Vec::with_capacity(field_len);
-    for args in field_args.iter() {
-        let field_name = &args.ident;
-        let optional = args.optional;
-        let index = args.index;
-        let field_type = &args.ty;
-        let class = &args.class;
+    for (i, args) in field_args.iter().filter(|args| !args.skip).enumerate() {
+        let field_name = args.name();
+        let member = args.member();
 
-        if args.skip {
+        let column_index = syn::Ident::new(
+            &format!("column_{}", field_name),
+            proc_macro2::Span::call_site(),
+        );
+        let field_value = de_field_value(args, quote::quote!(#field_name))?;
+        if projection {
+            // the fields that are not selected are filled with their default values
+            into_iter_quote.push(quote::quote!(
+                #column_index.map(|c| c.data).unwrap_or_default().into_iter().map(Some)
+                    .chain(::std::iter::repeat_with(|| None))
+            ));
+            field_names_build.push(quote::quote!(
+                #member: match #field_name {
+                    Some(#field_name) if __projected[#i] => #field_value,
+                    _ => ::std::default::Default::default(),
+                }
+            ));
+        } else {
+            into_iter_quote.push(quote::quote!(
+                #column_index.data.into_iter()
+            ));
+            field_names_build.push(quote::quote!(#member: #field_value));
+        }
+        field_names.push(field_name);
+    }
+    for args in field_args.iter().filter(|args| args.skip) {
+        let member = args.member();
+        if args.unknown_fields {
+            let value = unknown_field_value();
+            field_names_build.push(quote::quote!(#member: #value));
+        } else {
             field_names_build.push(quote::quote!(
-                #field_name: ::std::default::Default::default()
+                #member: ::std::default::Default::default()
             ));
-            continue;
         }
+    }
+    let decode = decode_columns(field_args, projection, parallel)?;
+    let take = if projection {
+        quote::quote!(.take(column_data_len))
+    } else {
+        quote::quote!()
+    };
+
+    // generate
+    let ret = quote::quote!(
+        #decode
+        let ans = ::serde_columnar::izip!(
+            #(#into_iter_quote),*
+        )#take.map(|(#(#field_names),*)| #struct_name{
+            #(#field_names_build),*
+        }).collect();
+        Ok(ans)
+    );
+    Ok(ret)
+}
+
+/// Decodes the columns of the fields from `seq` into the variables `column_{field}`.
+///
+/// With `projection`, the variables are `Option` of the columns and only the columns of
+/// the fields selected by `__projected` are decoded.
+///
+/// With `parallel`, the bytes of the columns that are not optional are read first, and then
+/// decoded on the thread pool.
+pub(super) fn decode_columns(
+    field_args: &[FieldArgs],
+    projection: bool,
+    parallel: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut elements = Vec::with_capacity(field_args.len());
+    let mut slots = Vec::new();
+    let mut tasks = Vec::new();
+    let mut decoded_columns = Vec::new();
+    let mut add_mapping = false;
+    let mut projected_names = Vec::with_capacity(field_args.len());
+    let mut projected_optional = Vec::with_capacity(field_args.len());
+    for (i, args) in field_args.iter().filter(|args| !args.skip).enumerate() {
+        let field_name = args.name();
+        let optional = args.optional;
+        let index = args.index;
+        projected_names.push(field_name.to_string());
+        projected_optional.push(optional);
 
         let column_index = syn::Ident::new(
-            &format!("column_{}", field_name.as_ref().unwrap()),
+            &format!("column_{}", field_name),
             proc_macro2::Span::call_site(),
         );
-        columns_quote.push(quote::quote!(#column_index));
-        let is_num = args.can_copy();
-        let column_type_token = args.get_strategy_column(quote::quote!(#field_type))?;
-        let column_type = if is_num {
-            column_type_token
-        } else if class.is_some() {
-            match class.as_ref().unwrap_or(&"".to_string()).as_str() {
-                "vec" => args.get_strategy_column(
-                    quote::quote!(::serde_columnar::ColumnarVec<_, #field_type>),
-                )?,
-                "map" => {
-                    args.get_strategy_column(
-                        quote::quote!(::serde_columnar::ColumnarMap<_, _, #field_type>),
-                    )?
-                    // quote::quote!(::serde_columnar::Column<::serde_columnar::ColumnarMap<_, _, #field_type>>)
-                }
-                _ => return Err(syn::Error::new_spanned(class, "unsupported type")),
+        let column_type = de_column_type(args)?;
+        // the inner rows of a selected field are decoded in full
+        let decode_element = |element: proc_macro2::TokenStream| {
+            if projection && (args.flatten || args.class.is_some()) {
+                quote::quote!(::serde_columnar::without_projection(|| #element))
+            } else {
+                element
             }
-        } else {
-            args.get_strategy_column(quote::quote!(::std::borrow::Cow<#field_type>))?
-            // quote::quote!(::serde_columnar::Column<::std::borrow::Cow<#field_type>>)
         };
 
-        let q = if !optional {
-            quote::quote!(
-                let #column_index: #column_type = seq.next_element()?.ok_or_else(||__A::Error::custom("DeserializeUnexpectedEnd"))?;
+        let q = if !optional && parallel && is_parallel_column(args) {
+            let bytes = syn::Ident::new(
+                &format!("__bytes_{}", field_name),
+                proc_macro2::Span::call_site(),
+            );
+            let decoded = syn::Ident::new(
+                &format!("__decoded_{}", field_name),
+                proc_macro2::Span::call_site(),
+            );
+            let slot = syn::Ident::new(
+                &format!("__slot_{}", field_name),
+                proc_macro2::Span::call_site(),
+            );
+            slots.push(quote::quote!(
+                let mut #decoded: Option<Result<#column_type, ::serde::de::value::Error>> = None;
+                let #slot = &mut #decoded;
+            ));
+            let decompress = if args.compress.is_some() {
+                quote::quote!(.map(|c: ::serde_columnar::Decompressed<#column_type>| c.0))
+            } else {
+                quote::quote!()
+            };
+            tasks.push(quote::quote!(
+                __scope.spawn(move || {
+                    *#slot = Some(::serde::Deserialize::deserialize(
+                        ::serde::de::value::BorrowedBytesDeserializer::new(#bytes),
+                    )#decompress);
+                });
+            ));
+            decoded_columns.push(quote::quote!(
+                let #column_index: #column_type = #decoded.unwrap().map_err(__A::Error::custom)?;
                 column_data_len = ::std::cmp::max(column_data_len, #column_index.len());
+            ));
+            quote::quote!(
+                let #bytes: &'__de [u8] = seq.next_element()?.ok_or_else(||__A::Error::custom("DeserializeUnexpectedEnd"))?;
             )
+        } else if !optional {
+            let next_element = if args.compress.is_some() {
+                quote::quote!(seq.next_element::<::serde_columnar::Decompressed<#column_type>>().map(|c| c.map(|c| c.0)))
+            } else {
+                quote::quote!(seq.next_element())
+            };
+            let next_element = decode_element(next_element);
+            let decode = quote::quote!(
+                let #column_index: #column_type = #next_element?.ok_or_else(||__A::Error::custom("DeserializeUnexpectedEnd"))?;
+                column_data_len = ::std::cmp::max(column_data_len, #column_index.len());
+            );
+            if !projection {
+                decode
+            } else if args.flatten && !args.is_nested() {
+                // the nested columns are not prefixed by their length, so they cannot be skipped
+                quote::quote!(
+                    #decode
+                    let #column_index = Some(#column_index);
+                )
+            } else {
+                quote::quote!(
+                    let #column_index: Option<#column_type> = if __projected[#i] {
+                        #decode
+                        Some(#column_index)
+                    } else {
+                        seq.next_element::<::serde_columnar::SkipColumn>()?.ok_or_else(||__A::Error::custom("DeserializeUnexpectedEnd"))?;
+                        None
+                    };
+                )
+            }
         } else {
             if !add_mapping {
-                elements.push(quote::quote!(
-                    let mut mapping = HashMap::new();
-                    while let Ok(Some((index, bytes))) = seq.next_element::<(usize, Vec<u8>)>() {
-                        // ignore
-                        mapping.insert(index, bytes);
-                    }
-                ));
+                // the optional columns are filled to the length of the other columns
+                elements.push(decode_columns_on_pool(&slots, &tasks, &decoded_columns));
+                elements.push(read_mapping());
                 add_mapping = true;
             }
             // have checked before
             let index = index.unwrap();
-            quote::quote!(
-                let #column_index: #column_type = if let Some(bytes) = mapping.remove(&#index){
-                    postcard::from_bytes(&bytes).map_err(__A::Error::custom)?
+            let from_bytes = if args.compress.is_some() {
+                quote::quote!(postcard::from_bytes::<::serde_columnar::Decompressed<#column_type>>(&bytes).map(|c| c.0))
+            } else {
+                quote::quote!(postcard::from_bytes(&bytes))
+            };
+            let from_bytes = decode_element(from_bytes);
+            let decode = quote::quote!(
+                if let Some(bytes) = mapping.remove(&#index){
+                    #from_bytes.map_err(__A::Error::custom)?
                 }else{
                     vec![Default::default(); column_data_len].into()
-                };
-            )
-        };
-        elements.push(q);
-
-        columns_types.push(column_type);
-        let into_element = quote::quote!(
-            #column_index.data.into_iter()
-        );
-
-        into_iter_quote.push(into_element);
-
-        field_names.push(field_name);
-        let field_name_build = if is_num {
-            quote::quote!(
-                #field_name: #field_name
-            )
-        } else if class.is_some() {
-            match class.as_ref().unwrap_or(&"".to_string()).as_str() {
-                "vec" => {
-                    quote::quote!(#field_name: #field_name.into_vec())
-                }
-                "map" => {
-                    quote::quote!(#field_name: #field_name.into_map())
                 }
-                _ => return Err(syn::Error::new_spanned(class, "unsupported type")),
+            );
+            if projection {
+                quote::quote!(
+                    let #column_index: Option<#column_type> = if __projected[#i] {
+                        Some(#decode)
+                    } else {
+                        None
+                    };
+                )
+            } else {
+                quote::quote!(
+                    let #column_index: #column_type = #decode;
+                )
             }
-        } else {
-            quote::quote!(
-                #field_name: #field_name.into_owned()
-            )
         };
-
-        field_names_build.push(field_name_build);
+        elements.push(q);
+    }
+    if !add_mapping {
+        elements.push(decode_columns_on_pool(&slots, &tasks, &decoded_columns));
+    }
+    if field_args.iter().any(|args| args.unknown_fields) {
+        if !add_mapping {
+            elements.push(read_mapping());
+        }
+        elements.push(de_unknown_fields(
+            field_args,
+            quote::quote!(column_data_len),
+        ));
     }
 
-    // generate
+    let projected = if projection {
+        quote::quote!(
+            let __projected = ::serde_columnar::projected_fields(
+                [#(#projected_names),*],
+                [#(#projected_optional),*],
+            );
+        )
+    } else {
+        quote::quote!()
+    };
     let ret = quote::quote!(
+        #projected
         let mut column_data_len: usize = 0;
         #(#elements)*;
-        let ans = ::serde_columnar::izip!(
-            #(#into_iter_quote),*
-        ).map(|(#(#field_names),*)| #struct_name{
-            #(#field_names_build),*
-        }).collect();
-        Ok(ans)
     );
     Ok(ret)
 }
+
+/// Decodes the bytes of the columns read by [decode_columns] on the thread pool
+fn decode_columns_on_pool(
+    slots: &[proc_macro2::TokenStream],
+    tasks: &[proc_macro2::TokenStream],
+    decoded_columns: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    if tasks.is_empty() {
+        return quote::quote!();
+    }
+    quote::quote!(
+        #(#slots)*
+        ::serde_columnar::column_scope(|__scope| {
+            #(#tasks)*
+        });
+        #(#decoded_columns)*
+    )
+}
+
+/// The column type that the field values are decoded from
+pub(super) fn de_column_type(args: &FieldArgs) -> syn::Result<proc_macro2::TokenStream> {
+    let field_type = &args.ty;
+    let class = &args.class;
+    let column_type = if args.is_nested() {
+        quote::quote!(::serde_columnar::NestedColumn<#field_type>)
+    } else if args.flatten {
+        quote::quote!(::serde_columnar::FlattenColumn<#field_type>)
+    } else if args.nullable {
+        nullable_column_type(args)?
+    } else if args.list {
+        list_column_type(args)?
+    } else if args.can_copy() {
+        stats_column_type(args, args.get_strategy_column(quote::quote!(#field_type))?)
+    } else if class.is_some() {
+        match class.as_ref().unwrap_or(&"".to_string()).as_str() {
+            "vec" => args.get_strategy_column(
+                quote::quote!(::serde_columnar::ColumnarVec<_, #field_type>),
+            )?,
+            "map" => {
+                args.get_strategy_column(
+                    quote::quote!(::serde_columnar::ColumnarMap<_, _, #field_type>),
+                )?
+                // quote::quote!(::serde_columnar::Column<::serde_columnar::ColumnarMap<_, _, #field_type>>)
+            }
+            _ => return Err(syn::Error::new_spanned(class, "unsupported type")),
+        }
+    } else {
+        args.get_strategy_column(quote::quote!(::std::borrow::Cow<#field_type>))?
+        // quote::quote!(::serde_columnar::Column<::std::borrow::Cow<#field_type>>)
+    };
+    Ok(column_type)
+}
+
+/// Converts the decoded item `value` of the column back to the field value
+pub(super) fn de_field_value(
+    args: &FieldArgs,
+    value: proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let class = &args.class;
+    let ans = if args.nullable {
+        nullable_field_value(args, value)
+    } else if args.list {
+        list_field_value(args, value)
+    } else if args.can_copy() || args.flatten {
+        value
+    } else if class.is_some() {
+        match class.as_ref().unwrap_or(&"".to_string()).as_str() {
+            "vec" => quote::quote!(#value.into_vec()),
+            "map" => quote::quote!(#value.into_map()),
+            _ => return Err(syn::Error::new_spanned(class, "unsupported type")),
+        }
+    } else {
+        quote::quote!(#value.into_owned())
+    };
+    Ok(ans)
+}
@@ -1,13 +1,14 @@
 use darling::util::Override;
 use proc_macro2::{Ident, TokenStream};
-use syn::{parse_quote, GenericArgument, Generics, LitStr, Type, Visibility};
+use quote::ToTokens;
+use syn::{parse_quote, GenericArgument, Generics, LitStr, Member, Type, Visibility};
 
 use crate::{
     args::{Args, Strategy},
-    attr::Context,
+    attr::{Context, Style},
     de::{borrowed_lifetimes, BorrowedLifetimes},
     serde::de::{split_with_de_lifetime, WithGenericsBorrow},
-    utils::add_lifetime_to_type,
+    utils::{add_lifetime_to_type, single_type_arg},
 };
 
 // TODO: map-like support
@@ -16,6 +17,7 @@ const ITER_LIFETIME: &str = "'__iter";
 
 pub struct TableIterFieldAttr {
     name: Ident,
+    member: Member,
     ty: Type,
     class: Option<String>,
     iter_item: Option<Type>,
@@ -24,6 +26,9 @@ pub struct TableIterFieldAttr {
     optional: bool,
     borrow: Option<Override<LitStr>>,
     skip: bool,
+    nullable: bool,
+    flatten: bool,
+    list: bool,
 }
 
 impl TableIterFieldAttr {
@@ -47,18 +52,12 @@ impl TableIterFieldAttr {
                 "class `map` have not impl `iter`",
             ));
         }
-        let mut iter_item = self.iter_item.clone().ok_or(syn::Error::new_spanned(
+        let iter_item = self.iter_item.clone().ok_or(syn::Error::new_spanned(
             self.ty.clone(),
             "iter should not be empty",
         ))?;
-        add_lifetime_to_type(
-            &mut iter_item,
-            GenericArgument::Lifetime(parse_quote!('__iter)),
-            Some(|ident: &mut Ident| {
-                *ident = Ident::new(&format!("Iterable{}", ident), ident.span())
-            }),
-        )?;
-        let name = &self.name;
+        let iter_item = iterable_row_type(&iter_item)?;
+        let name = self.field_name_colon();
 
         // no borrow and class
         let attrs = self.add_generic_columnar_attributes();
@@ -69,7 +68,7 @@ impl TableIterFieldAttr {
         };
         let ans = quote::quote!(
             #attrs
-            pub #name: #iter_item
+            pub #name #iter_item
         );
         Ok(ans)
     }
@@ -97,7 +96,7 @@ impl TableIterFieldAttr {
 
     /// id: u32
     fn generate_table_normal_field(&self) -> syn::Result<TokenStream> {
-        let name = &self.name;
+        let name = self.field_name_colon();
         let ty = &self.ty;
         let mut attributes = self.generate_table_normal_field_columnar_attribute()?;
         attributes.extend(self.add_generic_columnar_attributes());
@@ -109,7 +108,7 @@ impl TableIterFieldAttr {
 
         let ans = quote::quote!(
             #attrs
-            pub #name: #ty
+            pub #name #ty
         );
         Ok(ans)
     }
@@ -117,16 +116,31 @@ impl TableIterFieldAttr {
     // =============row iter
     /// a: DeltaRleIter<'i, u32>,
     fn generate_row_iter_field(&self) -> syn::Result<TokenStream> {
-        let name = &self.name;
-        let ty = &self.ty;
-        let ans = match self.strategy {
-            Strategy::Rle => quote::quote!(#name: AnyRleIter<'__iter, #ty>),
-            Strategy::BoolRle => quote::quote!(#name: BoolRleIter<'__iter>),
-            Strategy::DeltaRle => quote::quote!(#name: DeltaRleIter<'__iter, #ty>),
-            Strategy::DeltaOfDelta => quote::quote!(#name: DeltaOfDeltaIter<'__iter, #ty>),
-            Strategy::None => {
-                parse_quote!(#name: GenericIter<'__iter, #ty>)
-            }
+        let name = self.field_name_colon();
+        let ans = if self.flatten && self.class.is_some() {
+            let inner = single_type_arg(&self.ty, "Vec").ok_or_else(|| {
+                syn::Error::new_spanned(&self.ty, "flatten field must be of type `Vec<T>`")
+            })?;
+            let iter = iterable_row_type(inner)?;
+            quote::quote!(#name ListIter<'__iter, #iter>)
+        } else if self.flatten {
+            let iter = iterable_row_type(&self.ty)?;
+            quote::quote!(#name #iter)
+        } else if self.nullable {
+            let inner = single_type_arg(&self.ty, "Option").ok_or_else(|| {
+                syn::Error::new_spanned(&self.ty, "nullable field must be of type `Option<T>`")
+            })?;
+            let iter = self.strategy_iter(inner);
+            quote::quote!(#name NullableIter<'__iter, #iter>)
+        } else if self.list {
+            let inner = single_type_arg(&self.ty, "Vec").ok_or_else(|| {
+                syn::Error::new_spanned(&self.ty, "list field must be of type `Vec<T>`")
+            })?;
+            let iter = self.strategy_iter(inner);
+            quote::quote!(#name ListIter<'__iter, #iter>)
+        } else {
+            let iter = self.strategy_iter(&self.ty);
+            quote::quote!(#name #iter)
         };
         let mut attrs = self.generate_row_columnar_attribute()?;
         attrs.extend(self.add_generic_columnar_attributes());
@@ -143,11 +157,28 @@ impl TableIterFieldAttr {
         Ok(ans)
     }
 
+    /// DeltaRleIter<'i, u32>
+    fn strategy_iter(&self, ty: &Type) -> TokenStream {
+        match self.strategy {
+            Strategy::Rle => quote::quote!(AnyRleIter<'__iter, #ty>),
+            Strategy::BoolRle => quote::quote!(BoolRleIter<'__iter>),
+            Strategy::DeltaRle => quote::quote!(DeltaRleIter<'__iter, #ty>),
+            Strategy::DeltaOfDelta => quote::quote!(DeltaOfDeltaIter<'__iter, #ty>),
+            Strategy::Dict => quote::quote!(DictIter<'__iter, #ty>),
+            Strategy::Auto => quote::quote!(AutoIter<'__iter, #ty>),
+            Strategy::FloatXor => quote::quote!(FloatXorIter<'__iter, #ty>),
+            Strategy::BitPack => quote::quote!(BitPackIter<'__iter, #ty>),
+            Strategy::DeltaBitPack => quote::quote!(DeltaBitPackIter<'__iter, #ty>),
+            Strategy::None => quote::quote!(GenericIter<'__iter, #ty>),
+        }
+    }
+
     // let a = self.a.next();
     fn generate_row_per_iter_next_field(&self) -> TokenStream {
         let name = &self.name;
+        let member = &self.member;
         let ans = quote::quote!(
-            let #name = match self.#name.next().transpose(){
+            let #name = match self.#member.next().transpose(){
                 Ok(v) => v,
                 Err(e) => return Some(Err(e)),
             };
@@ -155,6 +186,17 @@ impl TableIterFieldAttr {
         ans
     }
 
+    /// Whether the row iter has `filter_by_{name}`, which needs the runs of the column
+    fn filterable(&self) -> bool {
+        matches!(self.member, Member::Named(_))
+            && !(self.skip || self.nullable || self.list || self.flatten)
+            && self.class.is_none()
+            && matches!(
+                self.strategy,
+                Strategy::Rle | Strategy::BoolRle | Strategy::DeltaRle
+            )
+    }
+
     fn generate_row_columnar_attribute(&self) -> syn::Result<Vec<TokenStream>> {
         let mut ans = Vec::new();
         if let Some(borrow) = &self.borrow {
@@ -172,6 +214,14 @@ impl TableIterFieldAttr {
         Ok(ans)
     }
 
+    /// `name:` of the named field, nothing for the unnamed field
+    fn field_name_colon(&self) -> TokenStream {
+        match &self.member {
+            Member::Named(name) => quote::quote!(#name:),
+            Member::Unnamed(_) => quote::quote!(),
+        }
+    }
+
     fn add_generic_columnar_attributes(&self) -> Vec<TokenStream> {
         let mut attrs = Vec::with_capacity(2);
         if self.skip {
@@ -185,12 +235,24 @@ impl TableIterFieldAttr {
     }
 }
 
+/// `Row<T>` -> `IterableRow<'__iter, T>`
+fn iterable_row_type(ty: &Type) -> syn::Result<Type> {
+    let mut ty = ty.clone();
+    add_lifetime_to_type(
+        &mut ty,
+        GenericArgument::Lifetime(parse_quote!('__iter)),
+        Some(|ident: &mut Ident| *ident = Ident::new(&format!("Iterable{}", ident), ident.span())),
+    )?;
+    Ok(ty)
+}
+
 // info that table struct needs
 pub struct TableIterParameter {
     ident: Ident,
     vis: Visibility,
     generics: Generics,
     iterable: bool,
+    style: Style,
     field_attrs: Vec<TableIterFieldAttr>,
     borrow: BorrowedLifetimes,
 }
@@ -200,12 +262,9 @@ impl TableIterParameter {
         let borrow = borrowed_lifetimes(ctx.fields())?;
         let mut field_attrs = Vec::with_capacity(ctx.fields().len());
         for f in ctx.fields() {
-            let name = f.ident.clone().ok_or(syn::Error::new_spanned(
-                ctx.ident.clone(),
-                "only support `vec` or `map` columnar class",
-            ))?;
             let tf = TableIterFieldAttr {
-                name,
+                name: f.name(),
+                member: f.member(),
                 ty: f.ty.clone(),
                 class: f.class.clone(),
                 iter_item: f.iter.clone(),
@@ -214,6 +273,9 @@ impl TableIterParameter {
                 optional: f.optional,
                 borrow: f.borrow.clone(),
                 skip: f.skip,
+                nullable: f.nullable,
+                flatten: f.flatten,
+                list: f.list,
             };
             field_attrs.push(tf);
         }
@@ -223,6 +285,7 @@ impl TableIterParameter {
             generics: ctx.generics.clone(),
             field_attrs,
             iterable: ctx.derive_args.iterable,
+            style: ctx.style(),
             borrow,
         };
         Ok(ans)
@@ -255,12 +318,11 @@ impl TableIterParameter {
         );
         let (de_impl_generics, de_ty_generics, ty_generics, where_clause) =
             split_with_de_lifetime(self);
+        let table_body = self.struct_body(&per_field, &where_clause);
 
         let ans = quote::quote!(
             #[columnar(de)]
-            #vis struct #this_table_iter_struct_name #de_ty_generics #where_clause{
-                #(#per_field),*
-            }
+            #vis struct #this_table_iter_struct_name #de_ty_generics #table_body
             const _: () = {
                 use ::serde_columnar::iterable::TableIter;
                 impl #de_impl_generics TableIter<'__iter> for #struct_name_ident #ty_generics #where_clause{
@@ -309,6 +371,7 @@ impl TableIterParameter {
         );
         let (iter_impl_generics, iter_ty_generics, ty_generics, where_clause) =
             split_with_de_lifetime(self);
+        let row_body = self.struct_body(&per_field, &where_clause);
 
         let next_tuple: Vec<_> = self
             .field_attrs
@@ -318,6 +381,15 @@ impl TableIterParameter {
                 quote::quote!(#ident)
             })
             .collect();
+        let next_members: Vec<_> = self
+            .field_attrs
+            .iter()
+            .map(|f| {
+                let member = &f.member;
+                let ident = &f.name;
+                quote::quote!(#member: #ident)
+            })
+            .collect();
         let next_some_tuple: Vec<_> = self
             .field_attrs
             .iter()
@@ -327,11 +399,33 @@ impl TableIterParameter {
             })
             .collect();
 
+        let skip_members = self.field_attrs.iter().map(|f| &f.member);
+        let filter_by_fields = self
+            .field_attrs
+            .iter()
+            .filter(|f| f.filterable())
+            .map(|f| self.generate_row_filter_by_field(f));
+
         let ans = quote::quote!(
             use ::serde_columnar::iterable::*;
             #[columnar(de)]
-            #vis struct #this_row_iter_struct_name #iter_ty_generics #where_clause{
-                #(#per_field),*
+            #vis struct #this_row_iter_struct_name #iter_ty_generics #row_body
+
+            impl #iter_impl_generics #this_row_iter_struct_name #iter_ty_generics #where_clause{
+                /// Skips `n` rows in every column, the runs of the columns are skipped at once
+                pub fn skip_rows(&mut self, n: usize) -> ::std::result::Result<(), ::serde_columnar::ColumnarError> {
+                    if n == 0 {
+                        return Ok(());
+                    }
+                    #(
+                        if let Some(Err(e)) = Iterator::nth(&mut self.#skip_members, n - 1) {
+                            return Err(e);
+                        }
+                    )*
+                    Ok(())
+                }
+
+                #(#filter_by_fields)*
             }
 
             impl #iter_impl_generics Iterator for #this_row_iter_struct_name #iter_ty_generics #where_clause{
@@ -339,17 +433,116 @@ impl TableIterParameter {
                 fn next(&mut self) -> Option<Self::Item> {
                     #(#per_iter_next_field);*
                     if let (#(#next_some_tuple),*) = (#(#next_tuple),*){
-                        Some(::std::result::Result::Ok(#struct_name_ident{#(#next_tuple),*}))
+                        Some(::std::result::Result::Ok(#struct_name_ident{#(#next_members),*}))
                     }else{
                         None
                     }
                 }
+
+                fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                    if let Err(e) = self.skip_rows(n) {
+                        return Some(Err(e));
+                    }
+                    self.next()
+                }
             }
         );
 
         Ok(ans)
     }
 
+    /// `where {..}` of the named struct, or `(..) where ;` of the tuple struct
+    fn struct_body(&self, fields: &[TokenStream], where_clause: &impl ToTokens) -> TokenStream {
+        match self.style {
+            Style::Struct | Style::Unit => quote::quote!(#where_clause{
+                #(#fields),*
+            }),
+            Style::Tuple | Style::Newtype => quote::quote!((
+                #(#fields),*
+            ) #where_clause;),
+        }
+    }
+
+    /// `filter_by_{name}`, the other columns skip the rejected runs and are zipped with the
+    /// value of the accepted runs
+    fn generate_row_filter_by_field(&self, field: &TableIterFieldAttr) -> TokenStream {
+        let struct_name_ident = &self.ident;
+        let (_, _, ty_generics, _) = split_with_de_lifetime(self);
+        let name = &field.name;
+        let member = &field.member;
+        let ty = &field.ty;
+        let field_name = name.to_string();
+        let field_name = field_name.trim_start_matches("r#");
+        let method = Ident::new(&format!("filter_by_{}", field_name), name.span());
+        let doc = format!(
+            "Keeps the rows whose `{0}` matches `predicate`, which is evaluated once per run of \
+             `{0}`. The other columns skip the rows of the rejected runs at once.",
+            field_name
+        );
+
+        let others: Vec<_> = self
+            .field_attrs
+            .iter()
+            .filter(|f| f.member != field.member)
+            .collect();
+        let other_members: Vec<_> = others.iter().map(|f| &f.member).collect();
+        let other_names: Vec<_> = others.iter().map(|f| &f.name).collect();
+        let row_members = self.field_attrs.iter().map(|f| &f.member);
+        let row_names = self.field_attrs.iter().map(|f| &f.name);
+        let row = quote::quote!(
+            Some(Ok(#struct_name_ident{#(#row_members: #row_names),*}))
+        );
+        let next_row = if others.is_empty() {
+            row
+        } else {
+            quote::quote!(
+                #(
+                    let #other_names = match __rows.#other_members.next().transpose() {
+                        Ok(v) => v,
+                        Err(e) => return Some(Err(e)),
+                    };
+                )*
+                if let (#(Some(#other_names)),*) = (#(#other_names),*) {
+                    #row
+                } else {
+                    None
+                }
+            )
+        };
+
+        quote::quote!(
+            #[doc = #doc]
+            #[allow(non_snake_case)]
+            pub fn #method<__P>(
+                self,
+                predicate: __P,
+            ) -> ::serde_columnar::iterable::FilterRuns<Self, #ty, #struct_name_ident #ty_generics, __P>
+            where
+                __P: FnMut(&#ty) -> bool,
+            {
+                ::serde_columnar::iterable::FilterRuns::new(
+                    self,
+                    predicate,
+                    |__rows: &mut Self| __rows.#member.next_value_run(),
+                    |__rows: &mut Self, n: usize| {
+                        if n == 0 {
+                            return Ok(());
+                        }
+                        #(
+                            if let Some(Err(e)) = Iterator::nth(&mut __rows.#other_members, n - 1) {
+                                return Err(e);
+                            }
+                        )*
+                        Ok(())
+                    },
+                    |__rows: &mut Self, #name: #ty| {
+                        #next_row
+                    },
+                )
+            }
+        )
+    }
+
     fn generate_row_per_field(&self, field: &TableIterFieldAttr) -> syn::Result<TokenStream> {
         field.generate_row_iter_field()
     }
@@ -12,9 +12,9 @@ extern crate syn;
 extern crate proc_macro;
 extern crate proc_macro2;
 
-use attr::Context;
+use attr::{Context, Data};
 use darling::{export::NestedMeta, Error};
-use derive::process_derive_args;
+use derive::{process_derive_args, process_enum_derive_args};
 use iterable::TableIterParameter;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
@@ -39,7 +39,11 @@ pub(crate) mod utils;
 /// Convenience macro to use the [`columnar`] system.
 ///
 /// Each field of a struct can be annotated with `#[columnar(...)]` to specify which transformations should be applied.
-/// `columnar` is *not* supported on enum and tuple struct temporarily.
+/// Tuple structs are also supported, `#[columnar(...)]` can be put on their positional fields.
+///
+/// An enum can be annotated with `#[columnar(vec, ser, de)]` to be used as a row of vec-like container.
+/// The variant of each row is stored in a `Rle` column, and the fields of each variant are stored
+/// in their own columns.
 ///
 /// [`columnar`]: <https://github.com/loro-dev/columnar/>
 ///
@@ -111,6 +115,11 @@ fn expand_columnar(args: Vec<NestedMeta>, mut st: DeriveInput) -> syn::Result<To
 
     let mut ans = vec![quote::quote!(#st)];
     let context = Context::new(&st, derive_args)?;
+    if let Data::Enum(variants) = &context.data {
+        // enum rows only derive `RowSer` and `RowDe`
+        ans.push(process_enum_derive_args(&derive_args, &st, variants)?);
+        return Ok(quote!(#(#ans)*).into());
+    }
     if derive_args.ser {
         ans.push(SerParameter::from_ctx(&context).derive_ser()?);
     }
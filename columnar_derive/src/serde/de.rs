@@ -1,6 +1,6 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
-use syn::{punctuated::Punctuated, ExprPath, Generics, LifetimeParam, Path, Type};
+use syn::{punctuated::Punctuated, ExprPath, Generics, LifetimeParam, Member, Path, Type};
 
 use crate::{
     attr::Context,
@@ -10,21 +10,61 @@ use crate::{
 
 struct DeFieldAttrs {
     name: Ident,
+    member: Member,
     ty: Type,
     optional: bool,
     index: Option<usize>,
     class: Option<String>,
     skip: bool,
+    page_rows: Option<usize>,
+    compress: bool,
+    unknown_fields: bool,
 }
 
 const DE_LIFETIME: &str = "'de";
 
 impl DeFieldAttrs {
-    fn generate_vec_wrapper(&self) -> TokenStream {
+    /// Reads the next element of `ty`, which is decompressed with `compress`
+    fn next_element(&self, ty: TokenStream) -> TokenStream {
+        if self.compress {
+            quote::quote!(
+                seq.next_element::<::serde_columnar::Decompressed<#ty>>()?.ok_or_else(|| __A::Error::custom("DeserializeUnexpectedEnd"))?.0
+            )
+        } else {
+            quote::quote!(
+                seq.next_element::<#ty>()?.ok_or_else(|| __A::Error::custom("DeserializeUnexpectedEnd"))?
+            )
+        }
+    }
+
+    /// Deserializes `ty` from the `bytes` of an optional field, which are decompressed with
+    /// `compress`
+    fn element_from_bytes(&self, ty: TokenStream) -> TokenStream {
+        if self.compress {
+            quote::quote!(
+                ::postcard::from_bytes::<::serde_columnar::Decompressed<#ty>>(bytes).map_err(__A::Error::custom)?.0
+            )
+        } else {
+            quote::quote!(::postcard::from_bytes::<#ty>(bytes).map_err(__A::Error::custom)?)
+        }
+    }
+
+    /// `ColumnarVec`, or `PagedColumnarVec` with `page_rows`
+    fn vec_wrapper_type(&self) -> TokenStream {
         let field_type = &self.ty;
+        if self.page_rows.is_some() {
+            quote::quote!(::serde_columnar::PagedColumnarVec<_, #field_type>)
+        } else {
+            quote::quote!(::serde_columnar::ColumnarVec<_, #field_type>)
+        }
+    }
+
+    fn generate_vec_wrapper(&self) -> TokenStream {
+        let wrapper_type = self.vec_wrapper_type();
         let field_name = &self.name;
+        let next_element = self.next_element(wrapper_type.clone());
         quote::quote!(
-            let wrapper: ::serde_columnar::ColumnarVec<_, #field_type> = seq.next_element()?.ok_or_else(|| __A::Error::custom("DeserializeUnexpectedEnd"))?;
+            let wrapper: #wrapper_type = #next_element;
             let #field_name = wrapper.into_vec();
         )
     }
@@ -32,19 +72,22 @@ impl DeFieldAttrs {
     fn generate_map_wrapper(&self) -> TokenStream {
         let field_type = &self.ty;
         let field_name = &self.name;
+        let wrapper_type = quote::quote!(::serde_columnar::ColumnarMap<_, _, #field_type>);
+        let next_element = self.next_element(wrapper_type.clone());
         quote::quote!(
-            let wrapper: ::serde_columnar::ColumnarMap<_, _, #field_type> = seq.next_element()?.ok_or_else(|| __A::Error::custom("DeserializeUnexpectedEnd"))?;
+            let wrapper: #wrapper_type = #next_element;
             let #field_name = wrapper.into_map();
         )
     }
 
     fn generate_vec_wrapper_from_mapping(&self) -> TokenStream {
-        let field_type = &self.ty;
+        let wrapper_type = self.vec_wrapper_type();
         let field_name = &self.name;
         let index = self.index.unwrap();
+        let from_bytes = self.element_from_bytes(wrapper_type.clone());
         quote::quote!(
             let #field_name = if let Some(bytes) = mapping.remove(&#index){
-                let wrapper: ::serde_columnar::ColumnarVec<_, #field_type> = ::postcard::from_bytes(bytes).map_err(__A::Error::custom)?;
+                let wrapper: #wrapper_type = #from_bytes;
                 wrapper.into_vec()
             }else{
                 Default::default()
@@ -55,9 +98,11 @@ impl DeFieldAttrs {
         let field_type = &self.ty;
         let field_name = &self.name;
         let index = self.index.unwrap();
+        let wrapper_type = quote::quote!(::serde_columnar::ColumnarMap<_, _, #field_type>);
+        let from_bytes = self.element_from_bytes(wrapper_type.clone());
         quote::quote!(
             let #field_name = if let Some(bytes) = mapping.remove(&#index){
-                let wrapper: ::serde_columnar::ColumnarMap<_, _, #field_type> = ::postcard::from_bytes(bytes).map_err(__A::Error::custom)?;
+                let wrapper: #wrapper_type = #from_bytes;
                 wrapper.into_map()
             }else{
                 Default::default()
@@ -67,7 +112,13 @@ impl DeFieldAttrs {
 
     fn generate_normal_field(&self, params: &DeParameter) -> TokenStream {
         let field_name = &self.name;
-        if let Some(path) = self.borrow_with() {
+        if self.compress {
+            // the decompressed bytes cannot be borrowed
+            let next_element = self.next_element(quote::quote!(_));
+            quote::quote!(
+                let #field_name = #next_element;
+            )
+        } else if let Some(path) = self.borrow_with() {
             let ty = &self.ty;
             let (wrapper, wrapper_ty) = wrap_deserialize_with(params, &quote::quote!(#ty), &path);
             quote::quote!(
@@ -88,7 +139,16 @@ impl DeFieldAttrs {
     fn generate_normal_field_from_mapping(&self, params: &DeParameter) -> TokenStream {
         let field_name = &self.name;
         let index = self.index.unwrap();
-        if let Some(path) = self.borrow_with() {
+        if self.compress {
+            let from_bytes = self.element_from_bytes(quote::quote!(_));
+            quote::quote!(
+                let #field_name = if let Some(bytes) = mapping.remove(&#index){
+                    #from_bytes
+                }else{
+                    Default::default()
+                };
+            )
+        } else if let Some(path) = self.borrow_with() {
             let ty = &self.ty;
             let (wrapper, wrapper_ty) = wrap_deserialize_with(params, &quote::quote!(#ty), &path);
 
@@ -166,12 +226,16 @@ impl DeParameter {
         let mut field_attrs = Vec::with_capacity(ctx.fields().len());
         for f in ctx.fields() {
             let attr = DeFieldAttrs {
-                name: f.ident.clone().unwrap(),
+                name: f.name(),
+                member: f.member(),
                 ty: f.ty.clone(),
                 optional: f.optional,
                 index: f.index,
                 class: f.class.clone(),
                 skip: f.skip,
+                page_rows: f.page_rows,
+                compress: f.compress.is_some(),
+                unknown_fields: f.unknown_fields,
             };
             field_attrs.push(attr);
         }
@@ -213,14 +277,7 @@ impl DeParameter {
             }
         } else {
             if !init_hashmap {
-                elements.push(quote::quote!(
-                    let mut mapping = HashMap::new();
-                    while let Ok(Some((index, bytes))) = seq.next_element::<(usize, &'de [u8])>() {
-                        // ignore
-                        mapping.insert(index, bytes);
-                    }
-
-                ));
+                elements.push(read_mapping());
                 init_hashmap = true;
             }
             // have checked before
@@ -251,6 +308,7 @@ impl DeParameter {
             split_with_de_lifetime(self);
         let delife = self.borrow.de_lifetime(DE_LIFETIME);
         let field_names = self.field_attrs.iter().map(|args| &args.name);
+        let field_members = self.field_attrs.iter().map(|args| &args.member);
         let mut init_hashmap = false;
         let mut per_field_de = Vec::with_capacity(self.field_length());
         for field in &self.field_attrs {
@@ -259,13 +317,23 @@ impl DeParameter {
                     self.per_field_de(field, init_hashmap, &mut per_field_de)?;
                 init_hashmap = flag;
                 per_field_de.push(field_token);
-            } else {
+            } else if !field.unknown_fields {
                 let field_name = &field.name;
                 per_field_de.push(quote::quote!(
                     let #field_name = Default::default();
                 ))
             }
         }
+        // the entries left in the mapping are unknown to this version
+        if let Some(field) = self.field_attrs.iter().find(|f| f.unknown_fields) {
+            if !init_hashmap {
+                per_field_de.push(read_mapping());
+            }
+            let field_name = &field.name;
+            per_field_de.push(quote::quote!(
+                let #field_name = ::serde_columnar::UnknownFields::from_entries(mapping, 0);
+            ));
+        }
 
         let ans = quote::quote!(
             const _:()={
@@ -292,7 +360,7 @@ impl DeParameter {
                             {
                                 #(#per_field_de)*
                                 Ok(#struct_name_ident {
-                                    #(#field_names),*
+                                    #(#field_members: #field_names),*
                                 })
                             }
                         }
@@ -308,6 +376,18 @@ impl DeParameter {
     }
 }
 
+/// Reads all the `(index, bytes)` entries of the optional fields into `mapping`
+fn read_mapping() -> TokenStream {
+    quote::quote!(
+        let mut mapping = HashMap::new();
+        while let Ok(Some((index, bytes))) = seq.next_element::<(usize, &'de [u8])>() {
+            // ignore
+            mapping.insert(index, bytes);
+        }
+
+    )
+}
+
 pub trait WithGenericsBorrow {
     fn generics(&self) -> Generics;
     fn generics_borrow(&self) -> &Generics;
@@ -1,15 +1,19 @@
 use proc_macro2::{Ident, TokenStream};
-use syn::{Generics, Type};
+use syn::{Generics, Member, Type};
 
 use crate::attr::Context;
 
 struct SerFieldAttrs {
     name: Ident,
+    member: Member,
     ty: Type,
     optional: bool,
     index: Option<usize>,
     class: Option<String>,
     skip: bool,
+    page_rows: Option<usize>,
+    compression: Option<TokenStream>,
+    unknown_fields: bool,
 }
 
 /// All the parameters of `Serialize`
@@ -28,12 +32,16 @@ impl SerParameter {
                 .fields()
                 .iter()
                 .map(|f| SerFieldAttrs {
-                    name: f.ident.clone().unwrap(),
+                    name: f.name(),
+                    member: f.member(),
                     ty: f.ty.clone(),
                     optional: f.optional,
                     index: f.index,
                     class: f.class.clone(),
                     skip: f.skip,
+                    page_rows: f.page_rows,
+                    compression: f.compression(),
+                    unknown_fields: f.unknown_fields,
                 })
                 .collect(),
         }
@@ -45,15 +53,21 @@ impl SerParameter {
 
     fn per_field_ser(&self, field: &SerFieldAttrs) -> syn::Result<TokenStream> {
         let field_name = &field.name;
+        let member = &field.member;
         let field_type = &field.ty;
 
         let field_token = if let Some(class) = &field.class {
             match class.as_str() {
-                "vec" => {
-                    quote::quote!(&::serde_columnar::ColumnarVec::<_, #field_type>::new(&self.#field_name))
-                }
+                "vec" => match field.page_rows {
+                    Some(page_rows) => quote::quote!(
+                        &::serde_columnar::PagedColumnarVec::<_, #field_type>::new(&self.#member, #page_rows)
+                    ),
+                    None => quote::quote!(
+                        &::serde_columnar::ColumnarVec::<_, #field_type>::new(&self.#member)
+                    ),
+                },
                 "map" => {
-                    quote::quote!(&::serde_columnar::ColumnarMap::<_, _, #field_type>::new(&self.#field_name))
+                    quote::quote!(&::serde_columnar::ColumnarMap::<_, _, #field_type>::new(&self.#member))
                 }
                 _ => {
                     return Err(syn::Error::new_spanned(
@@ -63,7 +77,13 @@ impl SerParameter {
                 }
             }
         } else {
-            quote::quote!(&self.#field_name)
+            quote::quote!(&self.#member)
+        };
+        let field_token = match &field.compression {
+            Some(compression) => {
+                quote::quote!(&::serde_columnar::Compressed(#field_token, #compression))
+            }
+            None => field_token,
         };
         let ans = if !field.optional {
             quote::quote!(
@@ -91,6 +111,17 @@ impl SerParameter {
                 per_element_body.push(self.per_field_ser(field)?)
             }
         }
+        // the unknown entries are written back after all the known ones
+        let field_length = match self.field_attrs.iter().find(|f| f.unknown_fields) {
+            Some(field) => {
+                let member = &field.member;
+                per_element_body.push(quote::quote!(
+                    self.#member.serialize_entries(&mut seq)?;
+                ));
+                quote::quote!(#field_length + self.#member.len())
+            }
+            None => quote::quote!(#field_length),
+        };
 
         let ans = quote::quote!(
             const _:()={
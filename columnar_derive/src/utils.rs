@@ -79,6 +79,23 @@ pub fn is_option(ty: &syn::Type, elem: fn(&syn::Type) -> bool) -> bool {
         }
 }
 
+/// The `T` of a type that looks like `name<T>`, e.g. `Option<T>` or `Vec<T>`
+pub fn single_type_arg<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+    let path = match ungroup(ty) {
+        syn::Type::Path(ty) => &ty.path,
+        _ => return None,
+    };
+    let seg = path.segments.last()?;
+    let args = match &seg.arguments {
+        syn::PathArguments::AngleBracketed(bracketed) => &bracketed.args,
+        _ => return None,
+    };
+    match (seg.ident == name, args.len(), args.first()) {
+        (true, 1, Some(syn::GenericArgument::Type(arg))) => Some(arg),
+        _ => None,
+    }
+}
+
 // Whether the type looks like it might be `&T` where elem="T". This can have
 // false negatives and false positives.
 //